@@ -14,12 +14,24 @@
  * limitations under the License.
  */
 
+//! Writes the accuracy reports of Lingua, CLD2 and Whatlang for the test data bundled
+//! with the language models. Lingua is measured both in high accuracy mode, which is the
+//! default, and in low accuracy mode.
+//!
+//! Run it from the repository root. To measure the effect of a smoothing strategy on
+//! the accuracy of Lingua, pass its name and parameter. The resulting reports can then
+//! be compared with the ones of the default strategy:
+//!
+//! ```text
+//! cargo run --release --example accuracy_reports -- penalized-back-off 0.4
+//! ```
+
 use cld2::{detect_language, Format, Lang as CLD2Language};
 use fraction::{Decimal, Zero};
 use include_dir::Dir;
 use indoc::formatdoc;
 use itertools::Itertools;
-use lingua::{Language, LanguageDetectorBuilder};
+use lingua::{Language, LanguageDetectorBuilder, Smoothing};
 use lingua_afrikaans_language_model::AFRIKAANS_TESTDATA_DIRECTORY;
 use lingua_albanian_language_model::ALBANIAN_TESTDATA_DIRECTORY;
 use lingua_arabic_language_model::ARABIC_TESTDATA_DIRECTORY;
@@ -329,13 +341,22 @@ impl Statistic {
 fn main() {
     let now = Instant::now();
 
+    let smoothing = parse_smoothing();
     let lingua_detector = LanguageDetectorBuilder::from_all_languages()
+        .with_smoothing(smoothing)
+        .with_preloaded_language_models()
+        .build();
+    let lingua_low_accuracy_detector = LanguageDetectorBuilder::from_all_languages()
+        .with_smoothing(smoothing)
+        .with_low_accuracy_mode()
         .with_preloaded_language_models()
         .build();
     let whatlang_detector = Detector::new();
 
     let accuracy_reports_directory = Path::new("accuracy-reports");
     let lingua_reports_directory = accuracy_reports_directory.join("lingua");
+    let lingua_low_accuracy_reports_directory =
+        accuracy_reports_directory.join("lingua-low-accuracy");
     let cld2_reports_directory = accuracy_reports_directory.join("cld2");
     let whatlang_reports_directory = accuracy_reports_directory.join("whatlang");
 
@@ -344,6 +365,11 @@ fn main() {
             .expect("Lingua reports directory could not be created");
     }
 
+    if !lingua_low_accuracy_reports_directory.is_dir() {
+        fs::create_dir_all(&lingua_low_accuracy_reports_directory)
+            .expect("Lingua low accuracy reports directory could not be created");
+    }
+
     if !cld2_reports_directory.is_dir() {
         fs::create_dir_all(&cld2_reports_directory)
             .expect("CLD2 reports directory could not be created");
@@ -371,7 +397,11 @@ fn main() {
         "average-lingua",
         "single-words-lingua",
         "word-pairs-lingua",
-        "sentences-lingua\n",
+        "sentences-lingua",
+        "average-lingua-low-accuracy",
+        "single-words-lingua-low-accuracy",
+        "word-pairs-lingua-low-accuracy",
+        "sentences-lingua-low-accuracy\n",
     ];
 
     aggregated_report_file
@@ -393,6 +423,7 @@ fn main() {
         let sentences = get_file_content("sentences.txt", &language);
 
         let mut lingua_statistics = DetectorStatistics::new();
+        let mut lingua_low_accuracy_statistics = DetectorStatistics::new();
         let mut cld2_statistics = DetectorStatistics::new();
         let mut whatlang_statistics = DetectorStatistics::new();
 
@@ -400,6 +431,11 @@ fn main() {
             let lingua_language = lingua_detector.detect_language_of(single_word);
             lingua_statistics.add_single_word_counts(lingua_language, single_word);
 
+            let lingua_low_accuracy_language =
+                lingua_low_accuracy_detector.detect_language_of(single_word);
+            lingua_low_accuracy_statistics
+                .add_single_word_counts(lingua_low_accuracy_language, single_word);
+
             let cld2_language = map_cld2_to_lingua(detect_language(single_word, Format::Text).0);
             cld2_statistics.add_single_word_counts(cld2_language, single_word);
 
@@ -412,6 +448,11 @@ fn main() {
             let lingua_language = lingua_detector.detect_language_of(word_pair);
             lingua_statistics.add_word_pair_counts(lingua_language, word_pair);
 
+            let lingua_low_accuracy_language =
+                lingua_low_accuracy_detector.detect_language_of(word_pair);
+            lingua_low_accuracy_statistics
+                .add_word_pair_counts(lingua_low_accuracy_language, word_pair);
+
             let cld2_language = map_cld2_to_lingua(detect_language(word_pair, Format::Text).0);
             cld2_statistics.add_word_pair_counts(cld2_language, word_pair);
 
@@ -424,6 +465,11 @@ fn main() {
             let lingua_language = lingua_detector.detect_language_of(sentence);
             lingua_statistics.add_sentence_counts(lingua_language, sentence);
 
+            let lingua_low_accuracy_language =
+                lingua_low_accuracy_detector.detect_language_of(sentence);
+            lingua_low_accuracy_statistics
+                .add_sentence_counts(lingua_low_accuracy_language, sentence);
+
             let cld2_language = map_cld2_to_lingua(detect_language(sentence, Format::Text).0);
             cld2_statistics.add_sentence_counts(cld2_language, sentence);
 
@@ -432,24 +478,30 @@ fn main() {
         }
 
         lingua_statistics.compute_accuracy_values();
+        lingua_low_accuracy_statistics.compute_accuracy_values();
         cld2_statistics.compute_accuracy_values();
         whatlang_statistics.compute_accuracy_values();
 
         let lingua_report = lingua_statistics.create_report_data(&language);
+        let lingua_low_accuracy_report =
+            lingua_low_accuracy_statistics.create_report_data(&language);
         let cld2_report = cld2_statistics.create_report_data(&language);
         let whatlang_report = whatlang_statistics.create_report_data(&language);
 
         let lingua_aggregated_report_row =
             lingua_statistics.create_aggregated_report_row(&language);
+        let lingua_low_accuracy_aggregated_report_row =
+            lingua_low_accuracy_statistics.create_aggregated_report_row(&language);
         let cld2_aggregated_report_row = cld2_statistics.create_aggregated_report_row(&language);
         let whatlang_aggregated_report_row =
             whatlang_statistics.create_aggregated_report_row(&language);
         let total_aggregated_report_row = format!(
-            "{:?},{},{},{}\n",
+            "{:?},{},{},{},{}\n",
             &language,
             cld2_aggregated_report_row,
             whatlang_aggregated_report_row,
-            lingua_aggregated_report_row
+            lingua_aggregated_report_row,
+            lingua_low_accuracy_aggregated_report_row
         );
 
         aggregated_report_file
@@ -458,6 +510,8 @@ fn main() {
 
         let report_file_name = titlecase(&format!("{:?}.txt", &language));
         let lingua_reports_file_path = lingua_reports_directory.join(&report_file_name);
+        let lingua_low_accuracy_reports_file_path =
+            lingua_low_accuracy_reports_directory.join(&report_file_name);
         let cld2_reports_file_path = cld2_reports_directory.join(&report_file_name);
         let whatlang_reports_file_path = whatlang_reports_directory.join(&report_file_name);
 
@@ -466,6 +520,11 @@ fn main() {
                 .expect("Lingua reports file could not be written");
         }
 
+        if let Some(report) = lingua_low_accuracy_report {
+            fs::write(lingua_low_accuracy_reports_file_path, report)
+                .expect("Lingua low accuracy reports file could not be written");
+        }
+
         if let Some(report) = cld2_report {
             fs::write(cld2_reports_file_path, report)
                 .expect("CLD2 reports file could not be written");
@@ -485,6 +544,32 @@ fn main() {
     );
 }
 
+fn parse_smoothing() -> Smoothing {
+    let args = std::env::args().skip(1).collect_vec();
+    let parameter = || {
+        args.get(1)
+            .expect("smoothing strategy requires a parameter")
+            .parse::<f64>()
+            .expect("smoothing parameter must be a number")
+    };
+
+    match args.first().map(|arg| arg.as_str()) {
+        None | Some("back-off") => Smoothing::BackOff,
+        Some("additive") => Smoothing::Additive { alpha: parameter() },
+        Some("penalized-back-off") => Smoothing::PenalizedBackOff {
+            penalty: parameter(),
+        },
+        Some("linear-interpolation") => Smoothing::LinearInterpolation {
+            weight: parameter(),
+        },
+        Some(other) => panic!(
+            "unknown smoothing strategy '{}', expected one of \
+             back-off, additive, penalized-back-off, linear-interpolation",
+            other
+        ),
+    }
+}
+
 fn get_file_content<'a>(file_name: &'a str, language: &'a Language) -> Vec<&'a str> {
     let directory = get_test_data_directory(language);
     directory
@@ -505,7 +590,8 @@ fn map_cld2_to_lingua(language: Option<CLD2Language>) -> Option<Language> {
     match language {
         Some(cld2_language) => {
             for lingua_language in Language::iter() {
-                if cld2_language.0 == lingua_language.iso_code_639_1().to_string() {
+                let iso_code = lingua_language.checked_iso_code_639_1();
+                if iso_code.map(|it| it.to_string()).as_deref() == Some(cld2_language.0) {
                     return Some(lingua_language);
                 }
             }
@@ -652,5 +738,6 @@ fn get_test_data_directory(language: &Language) -> Dir<'static> {
         &Language::Xhosa => XHOSA_TESTDATA_DIRECTORY,
         &Language::Yoruba => YORUBA_TESTDATA_DIRECTORY,
         &Language::Zulu => ZULU_TESTDATA_DIRECTORY,
+        _ => panic!("there is no test data for {:?}", language),
     }
 }
@@ -0,0 +1,194 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Fits the temperature for calibrated probabilities and the rejection thresholds
+//! for texts in unknown languages on the test data bundled with the language models.
+//! The rejection rates are reported for each test data file, which contain texts
+//! of different lengths. The output of this example for all test data is kept in
+//! `accuracy-reports/calibration.txt`.
+//!
+//! Run it from the repository root with an optional maximum number of lines
+//! to use from each test data file:
+//!
+//! ```text
+//! cargo run --release --example calibration -- 200
+//! ```
+
+use itertools::Itertools;
+use lingua::{
+    DetectionOutcome, Language, LanguageDetector, LanguageDetectorBuilder, UndeterminedReason,
+};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+const TEST_DATA_FILE_NAMES: [&str; 3] = ["single-words.txt", "word-pairs.txt", "sentences.txt"];
+const FALSE_REJECTION_RATE: f64 = 0.01;
+const HELD_OUT_LANGUAGE: Language = Language::Latin;
+
+fn main() {
+    let now = Instant::now();
+
+    let max_lines = std::env::args()
+        .nth(1)
+        .map(|arg| {
+            arg.parse::<usize>()
+                .expect("maximum line count must be a number")
+        })
+        .unwrap_or(usize::MAX);
+
+    let detector = LanguageDetectorBuilder::from_all_languages()
+        .with_preloaded_language_models()
+        .build();
+
+    let mut all_samples = vec![];
+    let mut samples_per_file = vec![];
+
+    for file_name in TEST_DATA_FILE_NAMES.iter() {
+        let samples = Language::all()
+            .into_iter()
+            .sorted()
+            .flat_map(|language| {
+                get_file_content(file_name, &language)
+                    .into_iter()
+                    .take(max_lines)
+                    .map(move |line| (language.clone(), line))
+            })
+            .collect_vec();
+
+        report_temperature(&detector, file_name, &samples);
+        all_samples.extend(samples.iter().cloned());
+        samples_per_file.push((*file_name, samples));
+    }
+
+    report_temperature(&detector, "all test data", &all_samples);
+    report_rejection_thresholds(&all_samples, &samples_per_file);
+
+    println!("All calibrations finished in {:.2?}", now.elapsed());
+}
+
+fn report_temperature(
+    detector: &LanguageDetector,
+    description: &str,
+    samples: &[(Language, String)],
+) {
+    let temperature = detector.fit_calibration_temperature(samples);
+
+    let calibrated_detector = LanguageDetectorBuilder::from_all_languages()
+        .with_calibration_temperature(temperature)
+        .build();
+
+    let mean_probability = samples
+        .iter()
+        .map(|(language, text)| {
+            calibrated_detector
+                .compute_calibrated_probabilities(text.as_str())
+                .into_iter()
+                .find(|(it, _)| it == language)
+                .map_or(0.0, |(_, probability)| probability)
+        })
+        .sum::<f64>()
+        / samples.len() as f64;
+
+    println!(
+        "{} ({} samples): temperature {:.4}, mean probability of expected language {:.4}",
+        description,
+        samples.len(),
+        temperature,
+        mean_probability
+    );
+}
+
+fn report_rejection_thresholds(
+    samples: &[(Language, String)],
+    samples_per_file: &[(&str, Vec<(Language, String)>)],
+) {
+    let mut builder = LanguageDetectorBuilder::from_all_languages_without(&[HELD_OUT_LANGUAGE]);
+    let known_samples = samples
+        .iter()
+        .filter(|(language, _)| language != &HELD_OUT_LANGUAGE)
+        .cloned()
+        .collect_vec();
+
+    let thresholds = builder
+        .build()
+        .fit_rejection_thresholds(&known_samples, FALSE_REJECTION_RATE);
+
+    println!(
+        "rejection thresholds at a false rejection rate of {:.2}:",
+        FALSE_REJECTION_RATE
+    );
+    for (language, text_length, threshold) in thresholds.iter() {
+        println!(
+            "    (Language::{:?}, TextLength::{:?}, {:.4}),",
+            language, text_length, threshold
+        );
+    }
+
+    let detector = builder.with_rejection_thresholds(&thresholds).build();
+
+    println!(
+        "rejection rates of known languages and of {:?}, held out as an unknown language:",
+        HELD_OUT_LANGUAGE
+    );
+    for (file_name, samples) in samples_per_file.iter() {
+        let (known_samples, unknown_samples): (Vec<_>, Vec<_>) = samples
+            .iter()
+            .partition(|(language, _)| language != &HELD_OUT_LANGUAGE);
+
+        println!(
+            "    {}: {:.4} of {} known texts, {:.4} of {} unknown texts",
+            file_name,
+            compute_rejection_rate(&detector, &known_samples),
+            known_samples.len(),
+            compute_rejection_rate(&detector, &unknown_samples),
+            unknown_samples.len()
+        );
+    }
+}
+
+fn compute_rejection_rate(detector: &LanguageDetector, samples: &[&(Language, String)]) -> f64 {
+    let rejected_count = samples
+        .iter()
+        .filter(|(_, text)| {
+            detector.detect_language_outcome_of(text.as_str())
+                == DetectionOutcome::Undetermined {
+                    reason: UndeterminedReason::UnknownLanguage,
+                }
+        })
+        .count();
+
+    rejected_count as f64 / samples.len() as f64
+}
+
+fn get_file_content(file_name: &str, language: &Language) -> Vec<String> {
+    let file_path = Path::new("language-models")
+        .join(
+            language
+                .checked_iso_code_639_1()
+                .unwrap_or_else(|| panic!("{:?} has no test data", language))
+                .to_string(),
+        )
+        .join("testdata")
+        .join(file_name);
+
+    fs::read_to_string(&file_path)
+        .unwrap_or_else(|_| panic!("test data file {} could not be read", file_path.display()))
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect_vec()
+}
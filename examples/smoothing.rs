@@ -0,0 +1,141 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Compares the accuracy of all smoothing strategies on the test data bundled with the
+//! language models and prints the results as a Markdown table. The output of this example
+//! is kept in `accuracy-reports/smoothing-comparison.md`.
+//!
+//! Run it from the repository root with an optional maximum number of lines
+//! to use from each test data file:
+//!
+//! ```text
+//! cargo run --release --example smoothing -- 200
+//! ```
+
+use itertools::Itertools;
+use lingua::{Language, LanguageDetectorBuilder, Smoothing};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+const TEST_DATA_FILE_NAMES: [&str; 3] = ["single-words.txt", "word-pairs.txt", "sentences.txt"];
+
+const SMOOTHING_STRATEGIES: [Smoothing; 7] = [
+    Smoothing::BackOff,
+    Smoothing::Additive { alpha: 1e-6 },
+    Smoothing::Additive { alpha: 1e-4 },
+    Smoothing::PenalizedBackOff { penalty: 0.4 },
+    Smoothing::PenalizedBackOff { penalty: 0.1 },
+    Smoothing::LinearInterpolation { weight: 0.9 },
+    Smoothing::LinearInterpolation { weight: 0.5 },
+];
+
+fn main() {
+    let now = Instant::now();
+
+    let max_lines = std::env::args()
+        .nth(1)
+        .map(|arg| {
+            arg.parse::<usize>()
+                .expect("maximum line count must be a number")
+        })
+        .unwrap_or(usize::MAX);
+
+    let languages = Language::all().into_iter().sorted().collect_vec();
+    let test_data = TEST_DATA_FILE_NAMES
+        .iter()
+        .map(|file_name| {
+            languages
+                .iter()
+                .map(|language| {
+                    let lines = get_file_content(file_name, language)
+                        .into_iter()
+                        .take(max_lines)
+                        .collect_vec();
+                    (language.clone(), lines)
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+
+    println!(
+        "Mean accuracy over {} languages with at most {} lines per test data file:",
+        languages.len(),
+        max_lines
+    );
+    println!();
+    println!("| Smoothing | Single words | Word pairs | Sentences | Average |");
+    println!("| --- | ---: | ---: | ---: | ---: |");
+
+    for smoothing in SMOOTHING_STRATEGIES.iter() {
+        let detector = LanguageDetectorBuilder::from_all_languages()
+            .with_smoothing(*smoothing)
+            .with_preloaded_language_models()
+            .build();
+
+        let accuracies = test_data
+            .iter()
+            .map(|samples_of_languages| {
+                samples_of_languages
+                    .iter()
+                    .map(|(language, lines)| {
+                        let correct_count = detector
+                            .detect_languages_of(lines)
+                            .into_iter()
+                            .filter(|detected_language| {
+                                detected_language.as_ref() == Some(language)
+                            })
+                            .count();
+                        correct_count as f64 / lines.len() as f64
+                    })
+                    .sum::<f64>()
+                    / samples_of_languages.len() as f64
+            })
+            .collect_vec();
+
+        println!(
+            "| `{:?}` | {} | {:.2}% |",
+            smoothing,
+            accuracies
+                .iter()
+                .map(|accuracy| format!("{:.2}%", accuracy * 100.0))
+                .join(" | "),
+            accuracies.iter().sum::<f64>() / accuracies.len() as f64 * 100.0
+        );
+    }
+
+    println!();
+    println!("Compared in {:.2?}", now.elapsed());
+}
+
+fn get_file_content(file_name: &str, language: &Language) -> Vec<String> {
+    let file_path = Path::new("language-models")
+        .join(
+            language
+                .checked_iso_code_639_1()
+                .unwrap_or_else(|| panic!("{:?} has no test data", language))
+                .to_string(),
+        )
+        .join("testdata")
+        .join(file_name);
+
+    fs::read_to_string(&file_path)
+        .unwrap_or_else(|_| panic!("test data file {} could not be read", file_path.display()))
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect_vec()
+}
@@ -17,12 +17,16 @@
 use crate::language::Language;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-#[derive(EnumIter, Eq, PartialEq, Hash)]
-pub(crate) enum Alphabet {
+/// This enum specifies the alphabets which the supported languages are written in.
+///
+/// It is used to describe the alphabets of a [CustomLanguage](crate::CustomLanguage).
+#[derive(Clone, Debug, EnumIter, Eq, PartialEq, Hash, Serialize)]
+pub enum Alphabet {
     Arabic,
     Armenian,
     Bengali,
@@ -44,7 +48,7 @@ pub(crate) enum Alphabet {
 }
 
 impl Alphabet {
-    pub fn matches(&self, text: &str) -> bool {
+    pub(crate) fn matches(&self, text: &str) -> bool {
         match self {
             Alphabet::Arabic => ARABIC.is_match(text),
             Alphabet::Armenian => ARMENIAN.is_match(text),
@@ -67,7 +71,7 @@ impl Alphabet {
         }
     }
 
-    pub fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
+    pub(crate) fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
         let mut alphabets = HashMap::new();
         for alphabet in Alphabet::iter() {
             let supported_languages = alphabet.supported_languages();
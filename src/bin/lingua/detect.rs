@@ -0,0 +1,368 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::ArgMatches;
+use lingua::{Language, LanguageDetector};
+use serde_json::json;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+/// The number of lines whose languages are detected at once in line mode.
+const LINE_BATCH_SIZE: usize = 1000;
+
+/// The name under which standard input is reported.
+const STANDARD_INPUT: &str = "-";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum OutputFormat {
+    Tsv,
+    Json,
+}
+
+/// The detected language of a single document or line.
+struct Detection<'a> {
+    source: &'a str,
+    line_number: Option<usize>,
+    language: Option<Language>,
+    confidence_values: Option<Vec<(Language, f64)>>,
+}
+
+struct Output<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    with_confidence_values: bool,
+}
+
+pub(crate) fn run(detector: &LanguageDetector, matches: &ArgMatches) -> Result<(), String> {
+    let inputs = match matches.values_of("inputs") {
+        Some(values) => resolve_inputs(values)?,
+        None => vec![STANDARD_INPUT.to_string()],
+    };
+    let format = match matches.value_of("format") {
+        Some("json") => OutputFormat::Json,
+        _ => OutputFormat::Tsv,
+    };
+    let stdout = io::stdout();
+    let mut output = Output {
+        writer: BufWriter::new(stdout.lock()),
+        format,
+        with_confidence_values: matches.is_present("confidence"),
+    };
+
+    if matches.is_present("lines") {
+        for input in inputs.iter() {
+            let reader = open_input(input)?;
+            detect_languages_of_lines(detector, input, reader, &mut output)
+                .map_err(|err| format!("{}: {}", input, err))?;
+        }
+    } else {
+        detect_languages_of_documents(detector, &inputs, &mut output)?;
+    }
+
+    output.writer.flush().map_err(|err| err.to_string())
+}
+
+/// Expands glob patterns to the files matching them.
+/// Inputs naming an existing file or standard input are kept as they are.
+fn resolve_inputs<'a, I: Iterator<Item = &'a str>>(values: I) -> Result<Vec<String>, String> {
+    let mut inputs = vec![];
+
+    for value in values {
+        if value == STANDARD_INPUT || PathBuf::from(value).is_file() {
+            inputs.push(value.to_string());
+            continue;
+        }
+
+        let paths = glob::glob(value)
+            .map_err(|err| format!("'{}' is not a valid glob pattern: {}", value, err))?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() {
+            return Err(format!("'{}' does not match any file", value));
+        }
+
+        inputs.extend(paths.into_iter().map(|path| path.display().to_string()));
+    }
+
+    Ok(inputs)
+}
+
+fn open_input(input: &str) -> Result<Box<dyn BufRead>, String> {
+    if input == STANDARD_INPUT {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else {
+        let file = File::open(input).map_err(|err| format!("{}: {}", input, err))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Detects the language of each document while reading it piece by piece,
+/// so that large documents are never held in memory entirely.
+fn detect_languages_of_documents<W: Write>(
+    detector: &LanguageDetector,
+    inputs: &[String],
+    output: &mut Output<W>,
+) -> Result<(), String> {
+    for input in inputs.iter() {
+        let reader = open_input(input)?;
+        let (language, confidence_values) = if output.with_confidence_values {
+            let (language, confidence_values) = detector
+                .detect_language_with_confidence_values_of_reader(reader)
+                .map_err(|err| format!("{}: {}", input, err))?;
+            (language, Some(confidence_values))
+        } else {
+            let language = detector
+                .detect_language_of_reader(reader)
+                .map_err(|err| format!("{}: {}", input, err))?;
+            (language, None)
+        };
+
+        output
+            .write(&Detection {
+                source: input,
+                line_number: None,
+                language,
+                confidence_values,
+            })
+            .map_err(|err| err.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn detect_languages_of_lines<R: BufRead, W: Write>(
+    detector: &LanguageDetector,
+    source: &str,
+    reader: R,
+    output: &mut Output<W>,
+) -> io::Result<()> {
+    let mut lines = reader.lines();
+    let mut first_line_number = 1;
+
+    loop {
+        let batch = lines
+            .by_ref()
+            .take(LINE_BATCH_SIZE)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let detections = detect_languages(detector, &batch, output.with_confidence_values);
+
+        for (index, (language, confidence_values)) in detections.into_iter().enumerate() {
+            output.write(&Detection {
+                source,
+                line_number: Some(first_line_number + index),
+                language,
+                confidence_values,
+            })?;
+        }
+
+        first_line_number += batch.len();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn detect_languages(
+    detector: &LanguageDetector,
+    texts: &[String],
+    with_confidence_values: bool,
+) -> Vec<(Option<Language>, Option<Vec<(Language, f64)>>)> {
+    if with_confidence_values {
+        detector
+            .detect_languages_with_confidence_values_of(texts)
+            .into_iter()
+            .map(|(language, confidence_values)| (language, Some(confidence_values)))
+            .collect()
+    } else {
+        detector
+            .detect_languages_of(texts)
+            .into_iter()
+            .map(|language| (language, None))
+            .collect()
+    }
+}
+
+impl<W: Write> Output<W> {
+    fn write(&mut self, detection: &Detection) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Tsv => self.write_tsv(detection),
+            OutputFormat::Json => self.write_json(detection),
+        }
+    }
+
+    fn write_tsv(&mut self, detection: &Detection) -> io::Result<()> {
+        let mut fields = vec![detection.source.to_string()];
+
+        if let Some(line_number) = detection.line_number {
+            fields.push(line_number.to_string());
+        }
+
+        match &detection.language {
+            Some(language) => {
+                fields.push(format!("{:?}", language));
+                fields.push(iso_code_to_string(language.checked_iso_code_639_1()));
+                fields.push(iso_code_to_string(language.checked_iso_code_639_3()));
+            }
+            None => fields.extend(vec![String::new(); 3]),
+        }
+
+        if self.with_confidence_values {
+            let confidence_values = detection
+                .confidence_values
+                .iter()
+                .flatten()
+                .map(|(language, confidence)| format!("{:?}:{:.2}", language, confidence))
+                .collect::<Vec<_>>();
+            fields.push(confidence_values.join(","));
+        }
+
+        writeln!(self.writer, "{}", fields.join("\t"))
+    }
+
+    fn write_json(&mut self, detection: &Detection) -> io::Result<()> {
+        let mut object = json!({ "source": detection.source });
+
+        if let Some(line_number) = detection.line_number {
+            object["line"] = json!(line_number);
+        }
+
+        match &detection.language {
+            Some(language) => {
+                object["language"] = json!(format!("{:?}", language));
+                object["iso_code_639_1"] =
+                    json!(language.checked_iso_code_639_1().map(|it| it.to_string()));
+                object["iso_code_639_3"] =
+                    json!(language.checked_iso_code_639_3().map(|it| it.to_string()));
+            }
+            None => {
+                object["language"] = json!(null);
+                object["iso_code_639_1"] = json!(null);
+                object["iso_code_639_3"] = json!(null);
+            }
+        }
+
+        if let Some(confidence_values) = &detection.confidence_values {
+            object["confidence_values"] = confidence_values
+                .iter()
+                .map(|(language, confidence)| {
+                    json!({ "language": format!("{:?}", language), "confidence": confidence })
+                })
+                .collect();
+        }
+
+        writeln!(self.writer, "{}", object)
+    }
+}
+
+fn iso_code_to_string<T: ToString>(iso_code: Option<T>) -> String {
+    iso_code.map_or_else(String::new, |it| it.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingua::Language::*;
+    use lingua::LanguageDetectorBuilder;
+
+    fn detector() -> LanguageDetector {
+        LanguageDetectorBuilder::from_languages(&[English, German]).build()
+    }
+
+    fn write_detections(format: OutputFormat, with_confidence_values: bool) -> String {
+        let mut output = Output {
+            writer: vec![],
+            format,
+            with_confidence_values,
+        };
+        let text = "languages are awesome\n\nAlter\n";
+
+        detect_languages_of_lines(&detector(), "input.txt", text.as_bytes(), &mut output).unwrap();
+
+        String::from_utf8(output.writer).unwrap()
+    }
+
+    #[test]
+    fn assert_lines_are_written_as_tsv() {
+        assert_eq!(
+            write_detections(OutputFormat::Tsv, false),
+            "input.txt\t1\tEnglish\ten\teng\n\
+             input.txt\t2\t\t\t\n\
+             input.txt\t3\tGerman\tde\tdeu\n"
+        );
+    }
+
+    #[test]
+    fn assert_lines_are_written_as_json() {
+        let output = write_detections(OutputFormat::Json, true);
+        let objects = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(objects.len(), 3);
+        assert_eq!(objects[0]["line"], json!(1));
+        assert_eq!(objects[0]["language"], json!("English"));
+        assert_eq!(objects[0]["iso_code_639_3"], json!("eng"));
+        assert_eq!(
+            objects[0]["confidence_values"][0]["language"],
+            json!("English")
+        );
+        assert_eq!(objects[0]["confidence_values"][0]["confidence"], json!(1.0));
+        assert_eq!(objects[1]["language"], json!(null));
+        assert_eq!(objects[1]["confidence_values"], json!([]));
+    }
+
+    #[test]
+    fn assert_documents_are_written_with_confidence_values() {
+        let document = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            document.path(),
+            "languages are awesome\n\nlanguages are great\n",
+        )
+        .unwrap();
+        let source = document.path().display().to_string();
+        let mut output = Output {
+            writer: vec![],
+            format: OutputFormat::Tsv,
+            with_confidence_values: true,
+        };
+
+        detect_languages_of_documents(&detector(), std::slice::from_ref(&source), &mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output.writer).unwrap();
+        assert!(output.starts_with(&format!("{}\tEnglish\ten\teng\tEnglish:", source)));
+        assert_eq!(output.lines().count(), 1);
+    }
+
+    #[test]
+    fn assert_missing_files_are_reported() {
+        assert_eq!(
+            resolve_inputs(vec!["does-not-exist-*.txt"].into_iter()),
+            Err("'does-not-exist-*.txt' does not match any file".to_string())
+        );
+        assert_eq!(
+            resolve_inputs(vec!["-"].into_iter()),
+            Ok(vec!["-".to_string()])
+        );
+    }
+}
@@ -0,0 +1,288 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The `lingua` command-line tool detecting the languages of files and standard input.
+//! Its subcommands `train`, `merge` and `make-testdata` create and update language model
+//! files and create test data files.
+//!
+//! Languages are detected by the subcommand `detect` or without any subcommand. If the first
+//! input is named like a subcommand, such as a file named `train`, it is taken as that
+//! subcommand unless an option comes first, so such inputs are best given after `detect`.
+//!
+//! It is only built if the `cli` feature is enabled:
+//!
+//! ```text
+//! cargo install lingua --features cli
+//! ```
+
+mod detect;
+#[cfg(feature = "server")]
+mod serve;
+mod train;
+
+use clap::{crate_version, App, AppSettings, Arg, ArgMatches, SubCommand};
+use lingua::{IsoCode639_1, IsoCode639_3, Language, LanguageDetector, LanguageDetectorBuilder};
+use std::process;
+use std::str::FromStr;
+
+fn main() {
+    let matches = create_app().get_matches();
+
+    if let Err(message) = run(&matches) {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        ("train", Some(sub_matches)) => train::run_train(sub_matches),
+        ("merge", Some(sub_matches)) => train::run_merge(sub_matches),
+        ("make-testdata", Some(sub_matches)) => train::run_make_testdata(sub_matches),
+        ("detect", Some(sub_matches)) => {
+            let detector = build_detector(sub_matches)?;
+            detect::run(&detector, sub_matches)
+        }
+        #[cfg(feature = "server")]
+        ("serve", Some(sub_matches)) => {
+            let detector = build_detector(sub_matches)?;
+            serve::run(detector, sub_matches)
+        }
+        _ => {
+            let detector = build_detector(matches)?;
+            detect::run(&detector, matches)
+        }
+    }
+}
+
+fn create_app() -> App<'static, 'static> {
+    App::new("lingua")
+        .version(crate_version!())
+        .about("Detects the languages of files and standard input")
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .subcommand(
+            SubCommand::with_name("detect")
+                .about(
+                    "Detects the languages of files and standard input, \
+                     even of files named like a subcommand",
+                )
+                .args(&create_detection_args()),
+        )
+        .subcommand(train::create_train_subcommand())
+        .subcommand(train::create_merge_subcommand())
+        .subcommand(train::create_make_testdata_subcommand())
+        .subcommands(create_server_subcommand())
+        .args(&create_detection_args())
+}
+
+/// Creates the arguments of language detection, shared by the subcommand `detect`
+/// and the app itself.
+fn create_detection_args() -> Vec<Arg<'static, 'static>> {
+    let mut args = vec![
+        Arg::with_name("inputs")
+            .value_name("INPUT")
+            .multiple(true)
+            .help(
+                "Files or glob patterns to read from. \
+                 If none is given or the input is '-', standard input is read.",
+            ),
+        Arg::with_name("lines")
+            .short("l")
+            .long("lines")
+            .help("Detects the language of each line instead of each input as a whole"),
+        Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .value_name("FORMAT")
+            .possible_values(&["tsv", "json"])
+            .default_value("tsv")
+            .help("Prints tab-separated values or one JSON object per line"),
+        Arg::with_name("confidence")
+            .short("c")
+            .long("confidence")
+            .help("Prints the confidence values of all possible languages"),
+    ];
+    args.extend(create_detector_args());
+    args
+}
+
+/// Creates the arguments configuring the language detector,
+/// shared by language detection and the HTTP server.
+fn create_detector_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("languages")
+            .short("L")
+            .long("languages")
+            .value_name("LANGUAGES")
+            .multiple(true)
+            .require_delimiter(true)
+            .conflicts_with("exclude")
+            .help(
+                "Considers only the given languages, \
+                 specified by name or ISO 639-1 or ISO 639-3 code",
+            ),
+        Arg::with_name("exclude")
+            .short("x")
+            .long("exclude")
+            .value_name("LANGUAGES")
+            .multiple(true)
+            .require_delimiter(true)
+            .help(
+                "Considers all languages except the given ones, \
+                 specified by name or ISO 639-1 or ISO 639-3 code",
+            ),
+        Arg::with_name("min-relative-distance")
+            .short("d")
+            .long("min-relative-distance")
+            .value_name("DISTANCE")
+            .help("Sets the minimum relative distance between 0.0 and 0.99"),
+        Arg::with_name("preload")
+            .short("p")
+            .long("preload")
+            .help("Loads all language models before detecting instead of on demand"),
+        Arg::with_name("low-accuracy")
+            .long("low-accuracy")
+            .help("Uses trigram models only which is faster but less accurate for short texts"),
+    ]
+}
+
+#[cfg(feature = "server")]
+fn create_server_subcommand() -> Option<App<'static, 'static>> {
+    Some(serve::create_serve_subcommand().args(&create_detector_args()))
+}
+
+#[cfg(not(feature = "server"))]
+fn create_server_subcommand() -> Option<App<'static, 'static>> {
+    None
+}
+
+fn build_detector(matches: &ArgMatches) -> Result<LanguageDetector, String> {
+    let mut builder = if let Some(values) = matches.values_of("languages") {
+        let languages = parse_languages(values)?;
+        LanguageDetectorBuilder::try_from_languages(&languages).map_err(|err| err.to_string())?
+    } else if let Some(values) = matches.values_of("exclude") {
+        let languages = parse_languages(values)?;
+        LanguageDetectorBuilder::try_from_all_languages_without(&languages)
+            .map_err(|err| err.to_string())?
+    } else {
+        LanguageDetectorBuilder::from_all_languages()
+    };
+
+    if let Some(value) = matches.value_of("min-relative-distance") {
+        let distance = f64::from_str(value)
+            .map_err(|_| format!("'{}' is not a valid minimum relative distance", value))?;
+        builder
+            .try_with_minimum_relative_distance(distance)
+            .map_err(|err| err.to_string())?;
+    }
+
+    if matches.is_present("low-accuracy") {
+        builder.with_low_accuracy_mode();
+    }
+
+    if matches.is_present("preload") {
+        builder.with_preloaded_language_models();
+    }
+
+    builder.try_build().map_err(|err| err.to_string())
+}
+
+fn parse_languages<'a, I: Iterator<Item = &'a str>>(values: I) -> Result<Vec<Language>, String> {
+    values.map(parse_language).collect()
+}
+
+/// Parses a language given by its name or by its ISO 639-1 or ISO 639-3 code.
+pub(crate) fn parse_language(value: &str) -> Result<Language, String> {
+    let value = value.trim();
+
+    if value.len() == 2 {
+        if let Ok(iso_code) = IsoCode639_1::from_str(value) {
+            return Ok(Language::from_iso_code_639_1(&iso_code));
+        }
+    } else if value.len() == 3 {
+        if let Ok(iso_code) = IsoCode639_3::from_str(value) {
+            return Ok(Language::from_iso_code_639_3(&iso_code));
+        }
+    }
+
+    Language::from_str(value).map_err(|_| format!("'{}' is not a supported language", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingua::Language::*;
+
+    #[test]
+    fn assert_languages_are_parsed_correctly() {
+        assert_eq!(parse_language("German"), Ok(German));
+        assert_eq!(parse_language("german"), Ok(German));
+        assert_eq!(parse_language("de"), Ok(German));
+        assert_eq!(parse_language("DEU"), Ok(German));
+        assert_eq!(
+            parse_language("klingon"),
+            Err("'klingon' is not a supported language".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_inputs_named_like_subcommands_are_detected_with_detect_subcommand() {
+        let matches = create_app().get_matches_from(vec!["lingua", "detect", "train", "serve"]);
+        let (name, sub_matches) = matches.subcommand();
+        assert_eq!(name, "detect");
+        assert_eq!(
+            sub_matches
+                .unwrap()
+                .values_of("inputs")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec!["train", "serve"]
+        );
+
+        let matches = create_app().get_matches_from(vec!["lingua", "--lines", "notes.txt"]);
+        assert_eq!(matches.subcommand_name(), None);
+        assert_eq!(
+            matches.values_of("inputs").unwrap().collect::<Vec<_>>(),
+            vec!["notes.txt"]
+        );
+
+        assert!(create_app()
+            .get_matches_from_safe(vec!["lingua", "train"])
+            .is_err());
+    }
+
+    #[test]
+    fn assert_detector_is_built_from_arguments() {
+        let matches = create_app().get_matches_from(vec![
+            "lingua",
+            "--languages",
+            "en,de,fra",
+            "--min-relative-distance",
+            "0.25",
+        ]);
+        assert!(build_detector(&matches).is_ok());
+
+        let matches = create_app().get_matches_from(vec!["lingua", "--languages", "en"]);
+        assert!(build_detector(&matches).is_err());
+
+        let matches =
+            create_app().get_matches_from(vec!["lingua", "--min-relative-distance", "high"]);
+        assert_eq!(
+            build_detector(&matches).err(),
+            Some("'high' is not a valid minimum relative distance".to_string())
+        );
+    }
+}
@@ -0,0 +1,334 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use lingua::{Language, LanguageDetector};
+use serde_json::{json, Value};
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+const DEFAULT_THREAD_COUNT: &str = "4";
+const MAXIMUM_BODY_SIZE: usize = 1024 * 1024;
+
+pub(crate) fn create_serve_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("serve")
+        .about("Serves language detection as a JSON API over HTTP")
+        .arg(
+            Arg::with_name("address")
+                .short("a")
+                .long("address")
+                .value_name("ADDRESS")
+                .default_value(DEFAULT_ADDRESS)
+                .help("The address and port to listen on"),
+        )
+        .arg(
+            Arg::with_name("threads")
+                .short("t")
+                .long("threads")
+                .value_name("COUNT")
+                .default_value(DEFAULT_THREAD_COUNT)
+                .help("The number of threads handling requests"),
+        )
+}
+
+pub(crate) fn run(detector: LanguageDetector, matches: &ArgMatches) -> Result<(), String> {
+    let address = matches.value_of("address").unwrap();
+    let thread_count = match usize::from_str(matches.value_of("threads").unwrap()) {
+        Ok(thread_count) if thread_count > 0 => thread_count,
+        _ => return Err("the number of threads must be a positive number".to_string()),
+    };
+    let server = Arc::new(Server::http(address).map_err(|err| err.to_string())?);
+    let detector = Arc::new(detector);
+
+    eprintln!("Listening on http://{}", server.server_addr());
+
+    let workers = (0..thread_count)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let detector = Arc::clone(&detector);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle_request(&detector, request);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for worker in workers {
+        worker
+            .join()
+            .map_err(|_| "a request handling thread panicked".to_string())?;
+    }
+
+    Ok(())
+}
+
+fn handle_request(detector: &LanguageDetector, mut request: Request) {
+    let (status_code, value) = match read_body(request.as_reader(), MAXIMUM_BODY_SIZE) {
+        Ok(body) => route(detector, request.method(), request.url(), &body),
+        Err(response) => response,
+    };
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status_code)
+        .with_header(content_type);
+
+    if let Err(err) = request.respond(response) {
+        eprintln!("error: response could not be sent: {}", err);
+    }
+}
+
+/// Reads a UTF-8 encoded request body of at most `maximum_size` bytes. Larger bodies are
+/// rejected with status code 413 without reading more than one byte beyond the limit.
+fn read_body<R: Read>(reader: R, maximum_size: usize) -> Result<String, (u16, Value)> {
+    let mut bytes = vec![];
+    reader
+        .take(maximum_size as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| {
+            (
+                400,
+                error(&format!("request body could not be read: {}", err)),
+            )
+        })?;
+
+    if bytes.len() > maximum_size {
+        return Err((
+            413,
+            error(&format!(
+                "request body must not be larger than {} bytes",
+                maximum_size
+            )),
+        ));
+    }
+
+    String::from_utf8(bytes).map_err(|_| (400, error("request body is not valid UTF-8")))
+}
+
+/// Computes the status code and JSON response for a request.
+fn route(detector: &LanguageDetector, method: &Method, url: &str, body: &str) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or_default();
+
+    match (method, path) {
+        (Method::Post, "/detect") => handle_texts(body, |texts| {
+            detector
+                .detect_languages_of(texts)
+                .into_iter()
+                .map(|language| detection_to_json(language.as_ref()))
+                .collect()
+        }),
+        (Method::Post, "/confidence") => handle_texts(body, |texts| {
+            detector
+                .compute_language_confidence_values_of_batch(texts)
+                .into_iter()
+                .map(|confidence_values| confidence_values_to_json(&confidence_values))
+                .collect()
+        }),
+        (Method::Get, "/languages") => {
+            let languages = detector
+                .languages()
+                .iter()
+                .map(language_to_json)
+                .collect::<Vec<_>>();
+            (200, json!({ "languages": languages }))
+        }
+        (Method::Get, "/health") => {
+            let mut memory_usage = detector.memory_usage().into_iter().collect::<Vec<_>>();
+            memory_usage.sort();
+            let loaded_models = memory_usage
+                .into_iter()
+                .map(|(language, bytes)| {
+                    let mut value = language_to_json(&language);
+                    value["memory_usage"] = json!(bytes);
+                    value
+                })
+                .collect::<Vec<_>>();
+            (
+                200,
+                json!({ "status": "ok", "loaded_models": loaded_models }),
+            )
+        }
+        (_, "/detect") | (_, "/confidence") => (405, error("method must be POST")),
+        (_, "/languages") | (_, "/health") => (405, error("method must be GET")),
+        _ => (404, error(&format!("'{}' does not exist", path))),
+    }
+}
+
+/// Parses a body of the form `{"text": "..."}` or `{"texts": ["...", ...]}`
+/// and applies the given function to the texts. A single text yields a single
+/// result, several texts yield an array of results.
+fn handle_texts<F: Fn(&[String]) -> Vec<Value>>(body: &str, compute: F) -> (u16, Value) {
+    let request = match serde_json::from_str::<Value>(body) {
+        Ok(request) => request,
+        Err(err) => {
+            return (
+                400,
+                error(&format!("request body is not valid JSON: {}", err)),
+            )
+        }
+    };
+
+    if let Some(text) = request["text"].as_str() {
+        let mut results = compute(&[text.to_string()]);
+        return (200, results.remove(0));
+    }
+
+    if let Some(values) = request["texts"].as_array() {
+        let texts = values
+            .iter()
+            .map(|value| value.as_str().map(|text| text.to_string()))
+            .collect::<Option<Vec<_>>>();
+
+        return match texts {
+            Some(texts) => (200, json!({ "results": compute(&texts) })),
+            None => (400, error("'texts' must be an array of strings")),
+        };
+    }
+
+    (
+        400,
+        error("request body must contain either 'text' or 'texts'"),
+    )
+}
+
+/// Returns the name of a language as it appears in all responses.
+fn language_name(language: &Language) -> String {
+    format!("{:?}", language)
+}
+
+fn language_to_json(language: &Language) -> Value {
+    json!({
+        "language": language_name(language),
+        "iso_code_639_1": language.checked_iso_code_639_1().map(|it| it.to_string()),
+        "iso_code_639_3": language.checked_iso_code_639_3().map(|it| it.to_string()),
+    })
+}
+
+fn detection_to_json(language: Option<&Language>) -> Value {
+    match language {
+        Some(language) => language_to_json(language),
+        None => json!({
+            "language": null,
+            "iso_code_639_1": null,
+            "iso_code_639_3": null,
+        }),
+    }
+}
+
+fn confidence_values_to_json(confidence_values: &[(Language, f64)]) -> Value {
+    let confidence_values = confidence_values
+        .iter()
+        .map(|(language, confidence)| {
+            let mut value = language_to_json(language);
+            value["confidence"] = json!(confidence);
+            value
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "confidence_values": confidence_values })
+}
+
+fn error(message: &str) -> Value {
+    json!({ "error": message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingua::Language::*;
+    use lingua::LanguageDetectorBuilder;
+
+    fn detector() -> LanguageDetector {
+        LanguageDetectorBuilder::from_languages(&[English, German]).build()
+    }
+
+    #[test]
+    fn assert_language_of_single_text_is_detected() {
+        let (status_code, value) = route(
+            &detector(),
+            &Method::Post,
+            "/detect",
+            r#"{"text": "languages are awesome"}"#,
+        );
+
+        assert_eq!(status_code, 200);
+        assert_eq!(
+            value,
+            json!({ "language": "English", "iso_code_639_1": "en", "iso_code_639_3": "eng" })
+        );
+    }
+
+    #[test]
+    fn assert_confidence_values_of_batch_are_computed() {
+        let (status_code, value) = route(
+            &detector(),
+            &Method::Post,
+            "/confidence",
+            r#"{"texts": ["languages are awesome", ""]}"#,
+        );
+
+        assert_eq!(status_code, 200);
+        assert_eq!(value["results"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            value["results"][0]["confidence_values"][0]["language"],
+            json!("English")
+        );
+        assert_eq!(
+            value["results"][0]["confidence_values"][0]["confidence"],
+            json!(1.0)
+        );
+        assert_eq!(value["results"][1], json!({ "confidence_values": [] }));
+    }
+
+    #[test]
+    fn assert_languages_and_health_are_reported() {
+        let detector = detector();
+
+        let (status_code, value) = route(&detector, &Method::Get, "/languages", "");
+        assert_eq!(status_code, 200);
+        assert_eq!(value["languages"][0]["language"], json!("English"));
+        assert_eq!(value["languages"][1]["iso_code_639_1"], json!("de"));
+
+        let (status_code, value) = route(&detector, &Method::Get, "/health", "");
+        assert_eq!(status_code, 200);
+        assert_eq!(value["status"], json!("ok"));
+    }
+
+    #[test]
+    fn assert_invalid_requests_are_rejected() {
+        let detector = detector();
+
+        assert_eq!(route(&detector, &Method::Post, "/detect", "{").0, 400);
+        assert_eq!(route(&detector, &Method::Post, "/detect", "{}").0, 400);
+        assert_eq!(
+            route(&detector, &Method::Post, "/detect", r#"{"texts": [1]}"#).0,
+            400
+        );
+        assert_eq!(route(&detector, &Method::Get, "/detect", "").0, 405);
+        assert_eq!(route(&detector, &Method::Get, "/unknown", "").0, 404);
+    }
+
+    #[test]
+    fn assert_request_bodies_larger_than_maximum_size_are_rejected() {
+        assert_eq!(read_body(&b"{}"[..], 2), Ok("{}".to_string()));
+        assert_eq!(read_body(&b"{ }"[..], 2).unwrap_err().0, 413);
+        assert_eq!(read_body(&[0xff, 0xfe][..], 2).unwrap_err().0, 400);
+    }
+}
@@ -0,0 +1,507 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::parse_language;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use lingua::{
+    LanguageModelFilesWriter, TestDataFilesWriter, TrainedLanguageModels, TrainingOptions,
+};
+use regex::Regex;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
+
+const DEFAULT_CHARACTER_CLASS: &str = "\\p{L}";
+const DEFAULT_MAXIMUM_TEST_DATA_LINES: &str = "1000";
+const PROGRESS_INTERVAL_LINES: u64 = 1_000_000;
+
+const LANGUAGE_MODEL_FILE_NAMES: [&str; 5] = [
+    "unigrams.json.zip",
+    "bigrams.json.zip",
+    "trigrams.json.zip",
+    "quadrigrams.json.zip",
+    "fivegrams.json.zip",
+];
+
+const TEST_DATA_FILE_NAMES: [&str; 3] = ["sentences.txt", "single-words.txt", "word-pairs.txt"];
+
+pub(crate) fn create_train_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("train")
+        .about("Creates language model files from text files")
+        .arg(
+            input_arg("The UTF-8 encoded text files to create the language models from")
+                .multiple(true),
+        )
+        .arg(output_arg())
+        .arg(language_arg())
+        .arg(char_class_arg())
+        .arg(
+            Arg::with_name("max-lines")
+                .short("m")
+                .long("max-lines")
+                .value_name("COUNT")
+                .help("Uses at most the given number of non-empty lines of the input files"),
+        )
+        .arg(
+            Arg::with_name("min-count")
+                .long("min-count")
+                .value_name("COUNT")
+                .help("Removes ngrams occurring less often than the given count from the models"),
+        )
+        .arg(
+            Arg::with_name("memory-budget")
+                .long("memory-budget")
+                .value_name("MEGABYTES")
+                .help(
+                    "Spills the ngram counts to the temporary directory \
+                     whenever they exceed the given amount of memory",
+                ),
+        )
+        .arg(
+            Arg::with_name("absolute-frequencies")
+                .short("a")
+                .long("absolute-frequencies")
+                .help(
+                    "Stores the absolute frequencies of all ngrams as well \
+                     so that more text can be merged into the models later on",
+                ),
+        )
+}
+
+pub(crate) fn create_merge_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("merge")
+        .about("Merges additional text files into existing language model files")
+        .arg(input_arg("The UTF-8 encoded text files to add to the language models").multiple(true))
+        .arg(
+            Arg::with_name("models")
+                .short("M")
+                .long("models")
+                .value_name("DIRECTORY")
+                .required(true)
+                .help(
+                    "The directory containing language model files \
+                     created with '--absolute-frequencies'",
+                ),
+        )
+        .arg(language_arg())
+        .arg(char_class_arg())
+}
+
+pub(crate) fn create_make_testdata_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("make-testdata")
+        .about("Creates test data files for accuracy reports from a text file")
+        .arg(input_arg(
+            "The UTF-8 encoded text file to create the test data from",
+        ))
+        .arg(output_arg())
+        .arg(char_class_arg())
+        .arg(
+            Arg::with_name("max-lines")
+                .short("m")
+                .long("max-lines")
+                .value_name("COUNT")
+                .default_value(DEFAULT_MAXIMUM_TEST_DATA_LINES)
+                .help("The maximum number of lines of each test data file"),
+        )
+}
+
+pub(crate) fn run_train(matches: &ArgMatches) -> Result<(), String> {
+    let language = parse_language(matches.value_of("language").unwrap())?;
+    let input_file_paths = matches
+        .values_of("input")
+        .unwrap()
+        .map(resolve_input_file_path)
+        .collect::<Result<Vec<_>, _>>()?;
+    let output_directory_path = resolve_output_directory_path(matches.value_of("output").unwrap())?;
+    let char_class = resolve_character_class(matches.value_of("char-class").unwrap())?;
+    let maximum_lines = parse_maximum_lines(matches.value_of("max-lines"))?;
+    let options = parse_training_options(matches)?;
+    let now = Instant::now();
+
+    let mut read_error = None;
+    let models = match maximum_lines {
+        Some(maximum_lines) => {
+            eprintln!(
+                "Creating {:?} language models from at most {} lines of {} input files...",
+                language,
+                maximum_lines,
+                input_file_paths.len()
+            );
+            let lines = read_lines(&input_file_paths)
+                .map_while(|line| line.map_err(|err| read_error = Some(err)).ok())
+                .take(maximum_lines as usize);
+            LanguageModelFilesWriter::create_language_models_with_options(
+                lines,
+                &language,
+                &char_class,
+                &options,
+            )
+        }
+        None => {
+            eprintln!(
+                "Creating {:?} language models from {} input files...",
+                language,
+                input_file_paths.len()
+            );
+            LanguageModelFilesWriter::create_language_models_from_files_with_options(
+                &input_file_paths
+                    .iter()
+                    .map(|path| path.as_path())
+                    .collect::<Vec<_>>(),
+                &language,
+                &char_class,
+                &options,
+            )
+        }
+    }
+    .map_err(|err| err.to_string())?;
+
+    if let Some(err) = read_error {
+        return Err(err);
+    }
+
+    if matches.is_present("absolute-frequencies") {
+        LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+            &models,
+            &output_directory_path,
+        )
+    } else {
+        LanguageModelFilesWriter::write_language_model_files(&models, &output_directory_path)
+    }
+    .map_err(|err| err.to_string())?;
+
+    eprintln!(
+        "Language models written to {} in {:.2?}",
+        output_directory_path.display(),
+        now.elapsed()
+    );
+
+    print_ngram_counts(&models);
+
+    Ok(())
+}
+
+pub(crate) fn run_merge(matches: &ArgMatches) -> Result<(), String> {
+    let language = parse_language(matches.value_of("language").unwrap())?;
+    let input_file_paths = matches
+        .values_of("input")
+        .unwrap()
+        .map(resolve_input_file_path)
+        .collect::<Result<Vec<_>, _>>()?;
+    let model_directory_path = resolve_input_file_path(matches.value_of("models").unwrap())?;
+    let char_class = resolve_character_class(matches.value_of("char-class").unwrap())?;
+    let now = Instant::now();
+
+    eprintln!(
+        "Merging {} input files into the language models in {}...",
+        input_file_paths.len(),
+        model_directory_path.display()
+    );
+
+    let models = LanguageModelFilesWriter::merge_into_language_model_files(
+        &input_file_paths
+            .iter()
+            .map(|path| path.as_path())
+            .collect::<Vec<_>>(),
+        &model_directory_path,
+        &language,
+        &char_class,
+    )
+    .map_err(|err| err.to_string())?;
+
+    eprintln!("Language models updated in {:.2?}", now.elapsed());
+
+    print_ngram_counts(&models);
+
+    Ok(())
+}
+
+pub(crate) fn run_make_testdata(matches: &ArgMatches) -> Result<(), String> {
+    let input_file_path = resolve_input_file_path(matches.value_of("input").unwrap())?;
+    let output_directory_path = resolve_output_directory_path(matches.value_of("output").unwrap())?;
+    let char_class = resolve_character_class(matches.value_of("char-class").unwrap())?;
+    let maximum_lines = parse_maximum_lines(matches.value_of("max-lines"))?.unwrap();
+    let now = Instant::now();
+
+    eprintln!("Creating test data from {}...", input_file_path.display());
+
+    TestDataFilesWriter::create_and_write_test_data_files(
+        &input_file_path,
+        &output_directory_path,
+        &char_class,
+        maximum_lines,
+    )
+    .map_err(|err| err.to_string())?;
+
+    eprintln!(
+        "Test data written to {} in {:.2?}",
+        output_directory_path.display(),
+        now.elapsed()
+    );
+
+    for file_name in TEST_DATA_FILE_NAMES.iter() {
+        let file_path = output_directory_path.join(file_name);
+        let line_count =
+            count_lines(&file_path).map_err(|err| format!("{}: {}", file_path.display(), err))?;
+        println!("{}\t{} lines", file_name, line_count);
+    }
+
+    Ok(())
+}
+
+fn print_ngram_counts(models: &TrainedLanguageModels) {
+    for (i, file_name) in LANGUAGE_MODEL_FILE_NAMES.iter().enumerate() {
+        println!("{}\t{} ngrams", file_name, models.ngram_count(i + 1));
+    }
+}
+
+fn print_progress(file_path: &Path, line_count: u64) {
+    eprintln!("{}: {} lines read", file_path.display(), line_count);
+}
+
+fn input_arg(help: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name("input")
+        .value_name("INPUT")
+        .required(true)
+        .help(help)
+}
+
+fn output_arg() -> Arg<'static, 'static> {
+    Arg::with_name("output")
+        .short("o")
+        .long("output")
+        .value_name("DIRECTORY")
+        .default_value(".")
+        .help("The directory to write the files to, created if it does not exist")
+}
+
+fn language_arg() -> Arg<'static, 'static> {
+    Arg::with_name("language")
+        .short("L")
+        .long("language")
+        .value_name("LANGUAGE")
+        .required(true)
+        .help("The language given by name or ISO 639-1 or ISO 639-3 code")
+}
+
+fn char_class_arg() -> Arg<'static, 'static> {
+    Arg::with_name("char-class")
+        .short("c")
+        .long("char-class")
+        .value_name("CLASS")
+        .default_value(DEFAULT_CHARACTER_CLASS)
+        .help(
+            "A regex character class such as '\\p{L}' or an alphabet name such as 'Cyrillic' \
+             to restrict the characters to",
+        )
+}
+
+fn resolve_input_file_path(value: &str) -> Result<PathBuf, String> {
+    fs::canonicalize(value).map_err(|err| format!("{}: {}", value, err))
+}
+
+fn resolve_output_directory_path(value: &str) -> Result<PathBuf, String> {
+    fs::create_dir_all(value)
+        .and_then(|_| fs::canonicalize(value))
+        .map_err(|err| format!("{}: {}", value, err))
+}
+
+/// Turns an alphabet name such as `Latin` into the character class `\p{Latin}`.
+/// Any other value is assumed to be a character class already.
+pub(crate) fn resolve_character_class(value: &str) -> Result<String, String> {
+    let is_name = !value.is_empty()
+        && value
+            .chars()
+            .all(|character| character.is_ascii_alphabetic() || character == '_');
+
+    if is_name {
+        let char_class = format!("\\p{{{}}}", value);
+        if Regex::new(&char_class).is_ok() {
+            return Ok(char_class);
+        }
+    }
+
+    match Regex::new(&format!("[{}]", value)) {
+        Ok(_) => Ok(value.to_string()),
+        Err(_) => Err(format!(
+            "'{}' is neither a valid character class nor an alphabet name",
+            value
+        )),
+    }
+}
+
+fn parse_maximum_lines(value: Option<&str>) -> Result<Option<u32>, String> {
+    match value {
+        Some(value) => match u32::from_str(value) {
+            Ok(maximum_lines) if maximum_lines > 0 => Ok(Some(maximum_lines)),
+            _ => Err(format!("'{}' is not a valid maximum line count", value)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse_training_options(matches: &ArgMatches) -> Result<TrainingOptions, String> {
+    let mut options =
+        TrainingOptions::new().with_progress_callback(PROGRESS_INTERVAL_LINES, print_progress);
+
+    if let Some(value) = matches.value_of("min-count") {
+        let minimum_count = u32::from_str(value)
+            .map_err(|_| format!("'{}' is not a valid minimum count", value))?;
+        options = options.with_minimum_count(minimum_count);
+    }
+
+    if let Some(value) = matches.value_of("memory-budget") {
+        let bytes = usize::from_str(value)
+            .ok()
+            .and_then(|megabytes| megabytes.checked_mul(1024 * 1024))
+            .ok_or_else(|| format!("'{}' is not a valid memory budget", value))?;
+        options = options.with_memory_budget(bytes);
+    }
+
+    Ok(options)
+}
+
+/// Reads the non-empty lines of the input files lazily, in the order of the files,
+/// so that only the lines which are actually consumed are read.
+fn read_lines(input_file_paths: &[PathBuf]) -> impl Iterator<Item = Result<String, String>> + '_ {
+    input_file_paths
+        .iter()
+        .flat_map(|input_file_path| {
+            let describe = move |err: io::Error| format!("{}: {}", input_file_path.display(), err);
+            let lines: Box<dyn Iterator<Item = Result<String, String>>> =
+                match File::open(input_file_path) {
+                    Ok(file) => Box::new(
+                        BufReader::new(file)
+                            .lines()
+                            .map(move |line| line.map_err(describe)),
+                    ),
+                    Err(err) => Box::new(iter::once(Err(describe(err)))),
+                };
+            lines
+        })
+        .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+}
+
+fn count_lines(file_path: &Path) -> io::Result<usize> {
+    let reader = BufReader::new(File::open(file_path)?);
+    let mut line_count = 0;
+
+    for line in reader.lines() {
+        if !line?.trim().is_empty() {
+            line_count += 1;
+        }
+    }
+
+    Ok(line_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_character_classes_are_resolved_correctly() {
+        assert_eq!(
+            resolve_character_class("Cyrillic"),
+            Ok("\\p{Cyrillic}".to_string())
+        );
+        assert_eq!(resolve_character_class("\\p{L}"), Ok("\\p{L}".to_string()));
+        assert_eq!(resolve_character_class("a-z"), Ok("a-z".to_string()));
+        assert_eq!(
+            resolve_character_class("\\p{Klingon}"),
+            Err(
+                "'\\p{Klingon}' is neither a valid character class nor an alphabet name"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn assert_training_options_are_parsed_correctly() {
+        let matches = create_train_subcommand().get_matches_from(vec![
+            "train",
+            "corpus.txt",
+            "--language",
+            "en",
+            "--min-count",
+            "many",
+        ]);
+        assert_eq!(
+            parse_training_options(&matches).err(),
+            Some("'many' is not a valid minimum count".to_string())
+        );
+
+        let matches = create_train_subcommand().get_matches_from(vec![
+            "train",
+            "corpus.txt",
+            "--language",
+            "en",
+            "--min-count",
+            "2",
+            "--memory-budget",
+            "512",
+        ]);
+        assert!(parse_training_options(&matches).is_ok());
+
+        let memory_budget = (usize::MAX / 1024).to_string();
+        let matches = create_train_subcommand().get_matches_from(vec![
+            "train",
+            "corpus.txt",
+            "--language",
+            "en",
+            "--memory-budget",
+            &memory_budget,
+        ]);
+        assert_eq!(
+            parse_training_options(&matches).err(),
+            Some(format!("'{}' is not a valid memory budget", memory_budget))
+        );
+    }
+
+    #[test]
+    fn assert_non_empty_lines_are_read_lazily() {
+        let directory = tempfile::tempdir().unwrap();
+        let first_file_path = directory.path().join("first.txt");
+        let second_file_path = directory.path().join("second.txt");
+        let missing_file_path = directory.path().join("missing.txt");
+        fs::write(&first_file_path, "first line\n\n  \nsecond line\n").unwrap();
+        fs::write(&second_file_path, "third line\n").unwrap();
+
+        let input_file_paths = vec![first_file_path, second_file_path, missing_file_path];
+        let lines = read_lines(&input_file_paths).take(3).collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                Ok("first line".to_string()),
+                Ok("second line".to_string()),
+                Ok("third line".to_string())
+            ]
+        );
+        assert!(read_lines(&input_file_paths).nth(3).unwrap().is_err());
+    }
+
+    #[test]
+    fn assert_maximum_lines_are_parsed_correctly() {
+        assert_eq!(parse_maximum_lines(None), Ok(None));
+        assert_eq!(parse_maximum_lines(Some("50")), Ok(Some(50)));
+        assert!(parse_maximum_lines(Some("0")).is_err());
+        assert!(parse_maximum_lines(Some("many")).is_err());
+    }
+}
@@ -14,18 +14,35 @@
  * limitations under the License.
  */
 
-use crate::detector::LanguageDetector;
+use crate::calibration::DEFAULT_CALIBRATION_TEMPERATURE;
+use crate::custom::CustomLanguage;
+use crate::detector::{LanguageDetector, NgramLengths};
+use crate::error::LinguaError;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 use crate::language::Language;
-use std::collections::HashSet;
-
-const MISSING_LANGUAGE_MESSAGE: &str = "LanguageDetector needs at least 2 languages to choose from";
+use crate::prior::collect_weights;
+use crate::rejection::{collect_thresholds, TextLength};
+use crate::smoothing::Smoothing;
+use crate::store::ModelStore;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// This struct configures and creates an instance of [LanguageDetector].
 pub struct LanguageDetectorBuilder {
     languages: HashSet<Language>,
     minimum_relative_distance: f64,
     is_every_language_model_preloaded: bool,
+    model_directory: Option<PathBuf>,
+    model_store: Option<Arc<ModelStore>>,
+    memory_budget: Option<usize>,
+    early_stopping_margin: Option<f64>,
+    calibration_temperature: f64,
+    smoothing: Smoothing,
+    ngram_lengths: NgramLengths,
+    prior_weights: HashMap<Language, f64>,
+    rejection_thresholds: HashMap<(Language, TextLength), f64>,
 }
 
 impl LanguageDetectorBuilder {
@@ -68,55 +85,99 @@ impl LanguageDetectorBuilder {
     /// with all built-in languages except those specified in `languages`.
     ///
     /// ⚠ Panics if less than two `languages` are used to build the
-    /// `LanguageDetector`.
+    /// `LanguageDetector`. Use [try_from_all_languages_without] to get an error instead.
+    ///
+    /// [try_from_all_languages_without]: LanguageDetectorBuilder::try_from_all_languages_without
     pub fn from_all_languages_without(languages: &[Language]) -> Self {
+        Self::try_from_all_languages_without(languages).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with all built-in languages except those specified in `languages`.
+    ///
+    /// Returns [LinguaError::NotEnoughLanguages] if less than two `languages`
+    /// are used to build the `LanguageDetector`.
+    pub fn try_from_all_languages_without(languages: &[Language]) -> Result<Self, LinguaError> {
         let mut languages_to_load = Language::all();
         languages_to_load.retain(|it| !languages.contains(it));
         if languages_to_load.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
+            return Err(LinguaError::NotEnoughLanguages);
         }
-        Self::from(languages_to_load)
+        Ok(Self::from(languages_to_load))
     }
 
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the specified `languages`.
     ///
     /// ⚠ Panics if less than two `languages` are specified.
+    /// Use [try_from_languages] to get an error instead.
+    ///
+    /// [try_from_languages]: LanguageDetectorBuilder::try_from_languages
     pub fn from_languages(languages: &[Language]) -> Self {
+        Self::try_from_languages(languages).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with the specified `languages`.
+    ///
+    /// Returns [LinguaError::NotEnoughLanguages] if less than two `languages` are specified.
+    pub fn try_from_languages(languages: &[Language]) -> Result<Self, LinguaError> {
         if languages.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
+            return Err(LinguaError::NotEnoughLanguages);
         }
-        Self::from(languages.iter().cloned().collect())
+        Ok(Self::from(languages.iter().cloned().collect()))
     }
 
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the languages specified by the respective ISO 639-1 codes.
     ///
     /// ⚠ Panics if less than two `iso_codes` are specified.
+    /// Use [try_from_iso_codes_639_1] to get an error instead.
+    ///
+    /// [try_from_iso_codes_639_1]: LanguageDetectorBuilder::try_from_iso_codes_639_1
     pub fn from_iso_codes_639_1(iso_codes: &[IsoCode639_1]) -> Self {
+        Self::try_from_iso_codes_639_1(iso_codes).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with the languages specified by the respective ISO 639-1 codes.
+    ///
+    /// Returns [LinguaError::NotEnoughLanguages] if less than two `iso_codes` are specified.
+    pub fn try_from_iso_codes_639_1(iso_codes: &[IsoCode639_1]) -> Result<Self, LinguaError> {
         if iso_codes.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
+            return Err(LinguaError::NotEnoughLanguages);
         }
         let languages = iso_codes
             .iter()
             .map(|it| Language::from_iso_code_639_1(it))
             .collect::<HashSet<_>>();
-        Self::from(languages)
+        Ok(Self::from(languages))
     }
 
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the languages specified by the respective ISO 639-3 codes.
     ///
     /// ⚠ Panics if less than two `iso_codes` are specified.
+    /// Use [try_from_iso_codes_639_3] to get an error instead.
+    ///
+    /// [try_from_iso_codes_639_3]: LanguageDetectorBuilder::try_from_iso_codes_639_3
     pub fn from_iso_codes_639_3(iso_codes: &[IsoCode639_3]) -> Self {
+        Self::try_from_iso_codes_639_3(iso_codes).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with the languages specified by the respective ISO 639-3 codes.
+    ///
+    /// Returns [LinguaError::NotEnoughLanguages] if less than two `iso_codes` are specified.
+    pub fn try_from_iso_codes_639_3(iso_codes: &[IsoCode639_3]) -> Result<Self, LinguaError> {
         if iso_codes.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
+            return Err(LinguaError::NotEnoughLanguages);
         }
         let languages = iso_codes
             .iter()
             .map(|it| Language::from_iso_code_639_3(it))
             .collect::<HashSet<_>>();
-        Self::from(languages)
+        Ok(Self::from(languages))
     }
 
     /// Sets the desired value for the minimum relative distance measure.
@@ -139,11 +200,341 @@ impl LanguageDetectorBuilder {
     /// where language detection is not reliably possible.
     ///
     /// ⚠ Panics if `distance` is smaller than 0.0 or greater than 0.99.
+    /// Use [try_with_minimum_relative_distance] to get an error instead.
+    ///
+    /// [try_with_minimum_relative_distance]: LanguageDetectorBuilder::try_with_minimum_relative_distance
     pub fn with_minimum_relative_distance(&mut self, distance: f64) -> &mut Self {
+        self.try_with_minimum_relative_distance(distance)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the desired value for the minimum relative distance measure.
+    ///
+    /// See [with_minimum_relative_distance] for details.
+    ///
+    /// Returns [LinguaError::InvalidMinimumRelativeDistance] if `distance` is smaller
+    /// than 0.0 or greater than 0.99.
+    ///
+    /// [with_minimum_relative_distance]: LanguageDetectorBuilder::with_minimum_relative_distance
+    pub fn try_with_minimum_relative_distance(
+        &mut self,
+        distance: f64,
+    ) -> Result<&mut Self, LinguaError> {
         if !(0.0..=0.99).contains(&distance) {
-            panic!("minimum relative distance must lie in between 0.0 and 0.99");
+            return Err(LinguaError::InvalidMinimumRelativeDistance(distance));
         }
         self.minimum_relative_distance = distance;
+        Ok(self)
+    }
+
+    /// Sets the margin by which the most likely language must lead the runner-up to stop
+    /// reading input early when detecting the language of a reader, such as with
+    /// [LanguageDetector::detect_language_of_reader]. The margin is the difference between
+    /// the confidence values of the most likely language, which is always 1.0, and of the
+    /// second most likely language.
+    ///
+    /// By default, the entire input is read. Large documents are often written in a single
+    /// language, so their language can be reliably detected from their beginning already.
+    /// The margin is only checked once the long-text threshold set with
+    /// [with_long_text_threshold] has been reached, which is 120 characters by default.
+    ///
+    /// ⚠ Panics if `margin` is not greater than 0.0 or greater than 1.0.
+    /// Use [try_with_early_stopping_margin] to get an error instead.
+    ///
+    /// [try_with_early_stopping_margin]: LanguageDetectorBuilder::try_with_early_stopping_margin
+    /// [with_long_text_threshold]: LanguageDetectorBuilder::with_long_text_threshold
+    pub fn with_early_stopping_margin(&mut self, margin: f64) -> &mut Self {
+        self.try_with_early_stopping_margin(margin)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the margin by which the most likely language must lead the runner-up to stop
+    /// reading input early.
+    ///
+    /// See [with_early_stopping_margin] for details.
+    ///
+    /// Returns [LinguaError::InvalidEarlyStoppingMargin] if `margin` is not greater than 0.0
+    /// or greater than 1.0.
+    ///
+    /// [with_early_stopping_margin]: LanguageDetectorBuilder::with_early_stopping_margin
+    pub fn try_with_early_stopping_margin(
+        &mut self,
+        margin: f64,
+    ) -> Result<&mut Self, LinguaError> {
+        if !(margin > 0.0 && margin <= 1.0) {
+            return Err(LinguaError::InvalidEarlyStoppingMargin(margin));
+        }
+        self.early_stopping_margin = Some(margin);
+        Ok(self)
+    }
+
+    /// Sets the temperature by which the length-normalized log-likelihoods of the languages
+    /// are divided before computing calibrated probabilities with
+    /// [LanguageDetector::compute_calibrated_probabilities]. Lower temperatures make the
+    /// probabilities more decisive, higher temperatures flatten them.
+    ///
+    /// The default temperature has been fitted on the test data of all built-in languages.
+    /// A temperature better suited for specific data can be obtained with
+    /// [LanguageDetector::fit_calibration_temperature].
+    ///
+    /// ⚠ Panics if `temperature` is not a finite number greater than 0.0.
+    /// Use [try_with_calibration_temperature] to get an error instead.
+    ///
+    /// [try_with_calibration_temperature]: LanguageDetectorBuilder::try_with_calibration_temperature
+    pub fn with_calibration_temperature(&mut self, temperature: f64) -> &mut Self {
+        self.try_with_calibration_temperature(temperature)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the temperature used for computing calibrated probabilities.
+    ///
+    /// See [with_calibration_temperature] for details.
+    ///
+    /// Returns [LinguaError::InvalidCalibrationTemperature] if `temperature` is not a finite
+    /// number greater than 0.0.
+    ///
+    /// [with_calibration_temperature]: LanguageDetectorBuilder::with_calibration_temperature
+    pub fn try_with_calibration_temperature(
+        &mut self,
+        temperature: f64,
+    ) -> Result<&mut Self, LinguaError> {
+        if !(temperature.is_finite() && temperature > 0.0) {
+            return Err(LinguaError::InvalidCalibrationTemperature(temperature));
+        }
+        self.calibration_temperature = temperature;
+        Ok(self)
+    }
+
+    /// Sets the strategy for computing the probabilities of ngrams which are unknown
+    /// to a language model. See [Smoothing] for the available strategies.
+    ///
+    /// By default, unknown ngrams are backed off to their longest known lower-order ngram
+    /// and ignored if no such ngram exists. This favors languages with smaller language
+    /// models. The other strategies assign a probability to every ngram instead.
+    ///
+    /// ⚠ Panics if the parameter of `smoothing` does not lie in between 0.0 exclusively
+    /// and 1.0 inclusively. Use [try_with_smoothing] to get an error instead.
+    ///
+    /// [try_with_smoothing]: LanguageDetectorBuilder::try_with_smoothing
+    pub fn with_smoothing(&mut self, smoothing: Smoothing) -> &mut Self {
+        self.try_with_smoothing(smoothing)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the strategy for computing the probabilities of ngrams which are unknown
+    /// to a language model.
+    ///
+    /// See [with_smoothing] for details.
+    ///
+    /// Returns [LinguaError::InvalidSmoothingParameter] if the parameter of `smoothing`
+    /// does not lie in between 0.0 exclusively and 1.0 inclusively.
+    ///
+    /// [with_smoothing]: LanguageDetectorBuilder::with_smoothing
+    pub fn try_with_smoothing(&mut self, smoothing: Smoothing) -> Result<&mut Self, LinguaError> {
+        smoothing.validate()?;
+        self.smoothing = smoothing;
+        Ok(self)
+    }
+
+    /// Sets the prior weights of languages, reflecting how often texts are written in them.
+    ///
+    /// By default, all languages are considered equally likely before looking at a text.
+    /// If most texts are written in a few languages, weighting them makes the detection of
+    /// short texts such as single words or word pairs more accurate. The weights are relative:
+    /// Languages without a weight are weighted with 1.0, so a weight of 3.0 makes a language
+    /// three times as likely as each of them. The logarithm of the resulting prior probability
+    /// is added to the summed up log-probabilities of the ngrams of a language before both are
+    /// divided by the number of unigrams of the text. Its influence therefore decreases with
+    /// the length of a text. The weights do not apply to calibrated probabilities.
+    ///
+    /// Per-call hints can be given with [DetectionOptions::with_hints].
+    ///
+    /// ⚠ Panics if a weight is not a finite number greater than 0.0.
+    /// Use [try_with_prior_weights] to get an error instead.
+    ///
+    /// [DetectionOptions::with_hints]: crate::DetectionOptions::with_hints
+    /// [try_with_prior_weights]: LanguageDetectorBuilder::try_with_prior_weights
+    pub fn with_prior_weights(&mut self, weights: &[(Language, f64)]) -> &mut Self {
+        self.try_with_prior_weights(weights)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the prior weights of languages, reflecting how often texts are written in them.
+    ///
+    /// See [with_prior_weights] for details.
+    ///
+    /// Returns [LinguaError::InvalidPriorWeight] if a weight is not a finite number
+    /// greater than 0.0.
+    ///
+    /// [with_prior_weights]: LanguageDetectorBuilder::with_prior_weights
+    pub fn try_with_prior_weights(
+        &mut self,
+        weights: &[(Language, f64)],
+    ) -> Result<&mut Self, LinguaError> {
+        self.prior_weights = collect_weights(weights)?;
+        Ok(self)
+    }
+
+    /// Sets the rejection thresholds of languages for texts written in languages
+    /// the detector does not know.
+    ///
+    /// The confidence values are relative, so a text in a language the detector has not been
+    /// built from, such as Maltese for a detector of other languages written in Latin script,
+    /// is assigned to the closest known language with high confidence. If a threshold is set
+    /// for the language the ngram models decide on and the [TextLength] of the text, the
+    /// goodness of fit of the text is checked against it additionally. It is the log-likelihood
+    /// of the text in this language per character, as computed by
+    /// [compute_goodness_of_fit]. If it falls below the threshold,
+    /// [DetectionOutcome::Undetermined] with [UndeterminedReason::UnknownLanguage] is returned
+    /// instead of the language. Texts are never rejected for languages and text lengths
+    /// without a threshold.
+    ///
+    /// The thresholds depend on the language models and the ngram lengths used, so they should
+    /// be fitted with [fit_rejection_thresholds] on a detector configured like the new one.
+    ///
+    /// ⚠ Panics if a threshold is not a finite number.
+    /// Use [try_with_rejection_thresholds] to get an error instead.
+    ///
+    /// [compute_goodness_of_fit]: LanguageDetector::compute_goodness_of_fit
+    /// [fit_rejection_thresholds]: LanguageDetector::fit_rejection_thresholds
+    /// [DetectionOutcome::Undetermined]: crate::DetectionOutcome::Undetermined
+    /// [UndeterminedReason::UnknownLanguage]: crate::UndeterminedReason::UnknownLanguage
+    /// [try_with_rejection_thresholds]: LanguageDetectorBuilder::try_with_rejection_thresholds
+    pub fn with_rejection_thresholds(
+        &mut self,
+        thresholds: &[(Language, TextLength, f64)],
+    ) -> &mut Self {
+        self.try_with_rejection_thresholds(thresholds)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the rejection thresholds of languages for texts written in languages
+    /// the detector does not know.
+    ///
+    /// See [with_rejection_thresholds] for details.
+    ///
+    /// Returns [LinguaError::InvalidRejectionThreshold] if a threshold is not a finite number.
+    ///
+    /// [with_rejection_thresholds]: LanguageDetectorBuilder::with_rejection_thresholds
+    pub fn try_with_rejection_thresholds(
+        &mut self,
+        thresholds: &[(Language, TextLength, f64)],
+    ) -> Result<&mut Self, LinguaError> {
+        self.rejection_thresholds = collect_thresholds(thresholds)?;
+        Ok(self)
+    }
+
+    /// Enables the low accuracy mode which scores all texts with trigrams only.
+    ///
+    /// By default, *Lingua* uses all available language models of unigrams up to fivegrams
+    /// for texts shorter than 120 characters. Preloading all of them for many languages
+    /// takes several seconds and gigabytes of memory. In low accuracy mode, only the trigram
+    /// models are used and loaded. This makes the detection faster and needs a fraction
+    /// of the memory. The accuracy for long texts stays high but drops for short texts
+    /// such as single words. Texts with less than three characters cannot be detected
+    /// at all anymore. Unknown trigrams are never backed off to bigrams or unigrams,
+    /// whatever the smoothing strategy.
+    ///
+    /// This is a shortcut for setting the ngram lengths of short and long texts
+    /// to `3..4` with [with_ngram_lengths] and [with_long_text_ngram_lengths].
+    ///
+    /// [with_ngram_lengths]: LanguageDetectorBuilder::with_ngram_lengths
+    /// [with_long_text_ngram_lengths]: LanguageDetectorBuilder::with_long_text_ngram_lengths
+    pub fn with_low_accuracy_mode(&mut self) -> &mut Self {
+        self.ngram_lengths.short_text = 3..4;
+        self.ngram_lengths.long_text = 3..4;
+        self
+    }
+
+    /// Sets the lengths of the ngrams which texts shorter than the long-text threshold
+    /// are scored with.
+    ///
+    /// By default, short texts are scored with all ngrams from unigrams to fivegrams,
+    /// i.e. with the range `1..6`. Fewer ngram lengths speed up the detection and need less
+    /// memory at the expense of accuracy. Only the language models of those ngram lengths
+    /// which are used for either short or long texts are loaded.
+    ///
+    /// ⚠ Panics if `ngram_lengths` is empty or not contained in the range `1..6`.
+    /// Use [try_with_ngram_lengths] to get an error instead.
+    ///
+    /// [try_with_ngram_lengths]: LanguageDetectorBuilder::try_with_ngram_lengths
+    pub fn with_ngram_lengths(&mut self, ngram_lengths: Range<usize>) -> &mut Self {
+        self.try_with_ngram_lengths(ngram_lengths)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the lengths of the ngrams which texts shorter than the long-text threshold
+    /// are scored with.
+    ///
+    /// See [with_ngram_lengths] for details.
+    ///
+    /// Returns [LinguaError::InvalidNgramLengthRange] if `ngram_lengths` is empty
+    /// or not contained in the range `1..6`.
+    ///
+    /// [with_ngram_lengths]: LanguageDetectorBuilder::with_ngram_lengths
+    pub fn try_with_ngram_lengths(
+        &mut self,
+        ngram_lengths: Range<usize>,
+    ) -> Result<&mut Self, LinguaError> {
+        check_ngram_length_range(&ngram_lengths)?;
+        self.ngram_lengths.short_text = ngram_lengths;
+        Ok(self)
+    }
+
+    /// Sets the lengths of the ngrams which texts reaching the long-text threshold
+    /// are scored with.
+    ///
+    /// By default, long texts are scored with trigrams only, i.e. with the range `3..4`,
+    /// as they contain enough ngrams to be classified reliably by them.
+    ///
+    /// ⚠ Panics if `ngram_lengths` is empty or not contained in the range `1..6`.
+    /// Use [try_with_long_text_ngram_lengths] to get an error instead.
+    ///
+    /// [try_with_long_text_ngram_lengths]: LanguageDetectorBuilder::try_with_long_text_ngram_lengths
+    pub fn with_long_text_ngram_lengths(&mut self, ngram_lengths: Range<usize>) -> &mut Self {
+        self.try_with_long_text_ngram_lengths(ngram_lengths)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Sets the lengths of the ngrams which texts reaching the long-text threshold
+    /// are scored with.
+    ///
+    /// See [with_long_text_ngram_lengths] for details.
+    ///
+    /// Returns [LinguaError::InvalidNgramLengthRange] if `ngram_lengths` is empty
+    /// or not contained in the range `1..6`.
+    ///
+    /// [with_long_text_ngram_lengths]: LanguageDetectorBuilder::with_long_text_ngram_lengths
+    pub fn try_with_long_text_ngram_lengths(
+        &mut self,
+        ngram_lengths: Range<usize>,
+    ) -> Result<&mut Self, LinguaError> {
+        check_ngram_length_range(&ngram_lengths)?;
+        self.ngram_lengths.long_text = ngram_lengths;
+        Ok(self)
+    }
+
+    /// Sets the number of characters from which on a text is scored with the ngram lengths
+    /// for long texts instead of those for short texts. Whitespace between words counts
+    /// as a character, punctuation and numbers do not.
+    ///
+    /// The default threshold is 120 characters. A threshold of 0 scores all texts with the
+    /// ngram lengths for long texts, `usize::MAX` scores all texts with those for short texts.
+    pub fn with_long_text_threshold(&mut self, character_count: usize) -> &mut Self {
+        self.ngram_lengths.long_text_threshold = character_count;
+        self
+    }
+
+    /// Adds the given custom languages to the languages of `LanguageDetectorBuilder`.
+    ///
+    /// Custom languages take part in the detection alongside the built-in languages.
+    /// Their language models are always loaded from their own model directories,
+    /// regardless of [with_model_directory].
+    ///
+    /// [with_model_directory]: LanguageDetectorBuilder::with_model_directory
+    pub fn with_custom_languages(&mut self, languages: &[CustomLanguage]) -> &mut Self {
+        self.languages
+            .extend(languages.iter().cloned().map(Language::Custom));
         self
     }
 
@@ -160,12 +551,132 @@ impl LanguageDetectorBuilder {
         self
     }
 
+    /// Configures `LanguageDetectorBuilder` to load the language models from the given
+    /// directory at runtime instead of using the ones compiled into the binary.
+    ///
+    /// The directory is expected to contain a subdirectory for each language, named after
+    /// the language's ISO 639-1 code, which holds the files `unigrams.json.zip`,
+    /// `bigrams.json.zip`, `trigrams.json.zip`, `quadrigrams.json.zip` and `fivegrams.json.zip`
+    /// as written by [LanguageModelFilesWriter](crate::LanguageModelFilesWriter).
+    /// The English models, for instance, are looked up in `<directory>/en/`.
+    /// If a language model file does not exist in the directory, the compiled-in
+    /// language model is used as a fallback. With the feature `external-models`,
+    /// languages may be enabled without compiled-in language models, in which case
+    /// all of their language model files must exist in the directory.
+    ///
+    /// If a model store is set with [with_model_store], the directory needs to be configured
+    /// on the store itself with [ModelStore::from_directory], and building the detector fails
+    /// with [LinguaError::ModelDirectoryWithModelStore] otherwise.
+    ///
+    /// ⚠ Panics if `directory` does not exist or is not a directory.
+    /// Use [try_with_model_directory] to get an error instead.
+    ///
+    /// [try_with_model_directory]: LanguageDetectorBuilder::try_with_model_directory
+    /// [with_model_store]: LanguageDetectorBuilder::with_model_store
+    pub fn with_model_directory<P: AsRef<Path>>(&mut self, directory: P) -> &mut Self {
+        self.try_with_model_directory(directory)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Configures `LanguageDetectorBuilder` to load the language models from the given
+    /// directory at runtime instead of using the ones compiled into the binary.
+    ///
+    /// See [with_model_directory] for details.
+    ///
+    /// Returns [LinguaError::ModelDirectoryNotFound] if `directory` does not exist
+    /// or is not a directory.
+    ///
+    /// [with_model_directory]: LanguageDetectorBuilder::with_model_directory
+    pub fn try_with_model_directory<P: AsRef<Path>>(
+        &mut self,
+        directory: P,
+    ) -> Result<&mut Self, LinguaError> {
+        let directory = directory.as_ref();
+        if !directory.is_dir() {
+            return Err(LinguaError::ModelDirectoryNotFound(directory.to_path_buf()));
+        }
+        self.model_directory = Some(directory.to_path_buf());
+        Ok(self)
+    }
+
+    /// Configures `LanguageDetectorBuilder` to keep the language models in the given
+    /// [ModelStore] instead of a store of the detector's own.
+    ///
+    /// By default, each instance of [LanguageDetector] loads its language models into a store
+    /// of its own which is released as soon as the instance is dropped. Passing the same store
+    /// to several builders makes the detectors built with them share their language models.
+    pub fn with_model_store(&mut self, model_store: Arc<ModelStore>) -> &mut Self {
+        self.model_store = Some(model_store);
+        self
+    }
+
+    /// Limits the approximate amount of memory in bytes that the language models of the
+    /// [LanguageDetector] may occupy. The least recently used language models are unloaded
+    /// whenever the budget is exceeded. See [ModelStore::with_memory_budget] for details.
+    ///
+    /// If a model store is set with [with_model_store], the budget needs to be configured on
+    /// the store itself, and building the detector fails with
+    /// [LinguaError::MemoryBudgetWithModelStore] otherwise.
+    ///
+    /// [with_model_store]: LanguageDetectorBuilder::with_model_store
+    pub fn with_memory_budget(&mut self, bytes: usize) -> &mut Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
     /// Creates and returns the configured instance of [LanguageDetector].
+    ///
+    /// ⚠ Panics if a memory budget or a model directory is set together with a model store,
+    /// if a language model file cannot be found or, if language models are to be preloaded,
+    /// one of them cannot be loaded. Use [try_build] to get an error instead.
+    ///
+    /// [try_build]: LanguageDetectorBuilder::try_build
     pub fn build(&mut self) -> LanguageDetector {
+        self.try_build().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns the configured instance of [LanguageDetector].
+    ///
+    /// Returns [LinguaError::LanguageModelNotFound] if a language model file cannot be found.
+    /// If language models are to be preloaded, [LinguaError::CorruptLanguageModel] is returned
+    /// if one of them cannot be loaded. With lazy loading, only the existence of the files
+    /// is checked. A language model which exists but cannot
+    /// be loaded later on is skipped during detection and not attempted to be loaded again
+    /// until it is unloaded with [LanguageDetector::unload_language_models].
+    ///
+    /// Returns [LinguaError::MemoryBudgetWithModelStore] if a memory budget is set with
+    /// [with_memory_budget] together with a model store set with [with_model_store].
+    ///
+    /// Returns [LinguaError::ModelDirectoryWithModelStore] if a model directory is set with
+    /// [with_model_directory] together with a model store set with [with_model_store].
+    ///
+    /// [with_memory_budget]: LanguageDetectorBuilder::with_memory_budget
+    /// [with_model_directory]: LanguageDetectorBuilder::with_model_directory
+    /// [with_model_store]: LanguageDetectorBuilder::with_model_store
+    pub fn try_build(&mut self) -> Result<LanguageDetector, LinguaError> {
+        if self.model_store.is_some() && self.memory_budget.is_some() {
+            return Err(LinguaError::MemoryBudgetWithModelStore);
+        }
+        if self.model_store.is_some() && self.model_directory.is_some() {
+            return Err(LinguaError::ModelDirectoryWithModelStore);
+        }
         LanguageDetector::from(
             self.languages.clone(),
             self.minimum_relative_distance,
+            self.early_stopping_margin,
+            self.calibration_temperature,
+            self.smoothing,
+            self.ngram_lengths.clone(),
+            self.prior_weights.clone(),
+            self.rejection_thresholds.clone(),
             self.is_every_language_model_preloaded,
+            self.model_store.clone().unwrap_or_else(|| {
+                let model_store = ModelStore::with_source(self.model_directory.clone());
+                Arc::new(match self.memory_budget {
+                    Some(memory_budget) => model_store.with_memory_budget(memory_budget),
+                    None => model_store,
+                })
+            }),
         )
     }
 
@@ -174,13 +685,30 @@ impl LanguageDetectorBuilder {
             languages,
             minimum_relative_distance: 0.0,
             is_every_language_model_preloaded: false,
+            model_directory: None,
+            model_store: None,
+            memory_budget: None,
+            early_stopping_margin: None,
+            calibration_temperature: DEFAULT_CALIBRATION_TEMPERATURE,
+            smoothing: Smoothing::BackOff,
+            ngram_lengths: NgramLengths::default(),
+            prior_weights: hashmap!(),
+            rejection_thresholds: hashmap!(),
         }
     }
 }
 
+fn check_ngram_length_range(ngram_lengths: &Range<usize>) -> Result<(), LinguaError> {
+    if ngram_lengths.is_empty() || ngram_lengths.start < 1 || ngram_lengths.end > 6 {
+        return Err(LinguaError::InvalidNgramLengthRange(ngram_lengths.clone()));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alphabet::Alphabet;
 
     #[test]
     fn assert_detector_can_be_built_from_all_languages() {
@@ -202,6 +730,22 @@ mod tests {
         assert_eq!(builder.minimum_relative_distance, 0.2);
     }
 
+    #[test]
+    fn assert_custom_languages_are_added() {
+        let directory = tempfile::tempdir().unwrap();
+        let scots = CustomLanguage::new("Scots", &[Alphabet::Latin], None, directory.path());
+        let expected_languages = hashset!(
+            Language::English,
+            Language::German,
+            Language::Custom(scots.clone())
+        );
+        let mut builder =
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::German]);
+
+        builder.with_custom_languages(&[scots]);
+        assert_eq!(builder.languages, expected_languages);
+    }
+
     #[test]
     fn assert_detector_can_be_built_from_languages_with_arabic_script() {
         let builder = LanguageDetectorBuilder::from_all_languages_with_arabic_script();
@@ -302,6 +846,203 @@ mod tests {
         LanguageDetectorBuilder::from_iso_codes_639_3(&[IsoCode639_3::DEU]);
     }
 
+    #[test]
+    fn assert_fallible_constructors_return_error_for_too_few_languages() {
+        assert!(matches!(
+            LanguageDetectorBuilder::try_from_languages(&[Language::German]),
+            Err(LinguaError::NotEnoughLanguages)
+        ));
+        assert!(matches!(
+            LanguageDetectorBuilder::try_from_iso_codes_639_1(&[IsoCode639_1::DE]),
+            Err(LinguaError::NotEnoughLanguages)
+        ));
+        assert!(matches!(
+            LanguageDetectorBuilder::try_from_iso_codes_639_3(&[IsoCode639_3::DEU]),
+            Err(LinguaError::NotEnoughLanguages)
+        ));
+
+        let languages = Language::all()
+            .difference(&hashset!(Language::German))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            LanguageDetectorBuilder::try_from_all_languages_without(&languages),
+            Err(LinguaError::NotEnoughLanguages)
+        ));
+    }
+
+    #[test]
+    fn assert_fallible_constructors_succeed_for_enough_languages() {
+        let builder =
+            LanguageDetectorBuilder::try_from_languages(&[Language::German, Language::English])
+                .unwrap();
+
+        assert_eq!(
+            builder.languages,
+            hashset!(Language::German, Language::English)
+        );
+    }
+
+    #[test]
+    fn assert_fallible_minimum_relative_distance_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_minimum_relative_distance(-2.3),
+            Err(LinguaError::InvalidMinimumRelativeDistance(distance)) if distance == -2.3
+        ));
+        assert!(matches!(
+            builder.try_with_minimum_relative_distance(1.7),
+            Err(LinguaError::InvalidMinimumRelativeDistance(_))
+        ));
+        assert_eq!(builder.minimum_relative_distance, 0.0);
+
+        assert!(builder.try_with_minimum_relative_distance(0.2).is_ok());
+        assert_eq!(builder.minimum_relative_distance, 0.2);
+    }
+
+    #[test]
+    fn assert_fallible_early_stopping_margin_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_early_stopping_margin(0.0),
+            Err(LinguaError::InvalidEarlyStoppingMargin(margin)) if margin == 0.0
+        ));
+        assert!(matches!(
+            builder.try_with_early_stopping_margin(1.5),
+            Err(LinguaError::InvalidEarlyStoppingMargin(_))
+        ));
+        assert_eq!(builder.early_stopping_margin, None);
+
+        assert!(builder.try_with_early_stopping_margin(0.3).is_ok());
+        assert_eq!(builder.early_stopping_margin, Some(0.3));
+    }
+
+    #[test]
+    fn assert_fallible_calibration_temperature_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_calibration_temperature(0.0),
+            Err(LinguaError::InvalidCalibrationTemperature(temperature)) if temperature == 0.0
+        ));
+        assert!(matches!(
+            builder.try_with_calibration_temperature(f64::INFINITY),
+            Err(LinguaError::InvalidCalibrationTemperature(_))
+        ));
+        assert_eq!(
+            builder.calibration_temperature,
+            DEFAULT_CALIBRATION_TEMPERATURE
+        );
+
+        assert!(builder.try_with_calibration_temperature(0.5).is_ok());
+        assert_eq!(builder.calibration_temperature, 0.5);
+    }
+
+    #[test]
+    fn assert_fallible_smoothing_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_smoothing(Smoothing::PenalizedBackOff { penalty: 0.0 }),
+            Err(LinguaError::InvalidSmoothingParameter(penalty)) if penalty == 0.0
+        ));
+        assert_eq!(builder.smoothing, Smoothing::BackOff);
+
+        assert!(builder
+            .try_with_smoothing(Smoothing::LinearInterpolation { weight: 0.9 })
+            .is_ok());
+        assert_eq!(
+            builder.smoothing,
+            Smoothing::LinearInterpolation { weight: 0.9 }
+        );
+    }
+
+    #[test]
+    fn assert_fallible_prior_weights_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_prior_weights(&[(Language::English, 0.6), (Language::German, -0.4)]),
+            Err(LinguaError::InvalidPriorWeight(weight)) if weight == -0.4
+        ));
+        assert!(builder.prior_weights.is_empty());
+
+        assert!(builder
+            .try_with_prior_weights(&[(Language::English, 4.5)])
+            .is_ok());
+        assert_eq!(builder.prior_weights, hashmap!(Language::English => 4.5));
+    }
+
+    #[test]
+    fn assert_fallible_rejection_thresholds_setter_returns_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_rejection_thresholds(&[(
+                Language::English,
+                TextLength::ShortText,
+                f64::NEG_INFINITY
+            )]),
+            Err(LinguaError::InvalidRejectionThreshold(_))
+        ));
+        assert!(builder.rejection_thresholds.is_empty());
+
+        assert!(builder
+            .try_with_rejection_thresholds(&[(Language::English, TextLength::ShortText, -7.25)])
+            .is_ok());
+        assert_eq!(
+            builder.rejection_thresholds,
+            hashmap!((Language::English, TextLength::ShortText) => -7.25)
+        );
+    }
+
+    #[test]
+    fn assert_ngram_lengths_can_be_configured() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.ngram_lengths, NgramLengths::default());
+
+        builder
+            .with_ngram_lengths(2..4)
+            .with_long_text_ngram_lengths(3..5)
+            .with_long_text_threshold(60);
+
+        assert_eq!(builder.ngram_lengths.short_text, 2..4);
+        assert_eq!(builder.ngram_lengths.long_text, 3..5);
+        assert_eq!(builder.ngram_lengths.long_text_threshold, 60);
+    }
+
+    #[test]
+    fn assert_low_accuracy_mode_uses_trigrams_only() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder.with_low_accuracy_mode();
+
+        assert_eq!(builder.ngram_lengths.short_text, 3..4);
+        assert_eq!(builder.ngram_lengths.long_text, 3..4);
+        assert_eq!(builder.ngram_lengths.all(), vec![3]);
+    }
+
+    #[test]
+    fn assert_fallible_ngram_length_setters_return_error() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+
+        assert!(matches!(
+            builder.try_with_ngram_lengths(3..3),
+            Err(LinguaError::InvalidNgramLengthRange(_))
+        ));
+        assert!(matches!(
+            builder.try_with_ngram_lengths(0..3),
+            Err(LinguaError::InvalidNgramLengthRange(_))
+        ));
+        assert!(matches!(
+            builder.try_with_long_text_ngram_lengths(4..7),
+            Err(LinguaError::InvalidNgramLengthRange(range)) if range == (4..7)
+        ));
+        assert_eq!(builder.ngram_lengths, NgramLengths::default());
+    }
+
     #[test]
     #[should_panic(expected = "minimum relative distance must lie in between 0.0 and 0.99")]
     fn assert_detector_cannot_be_built_from_too_small_minimum_relative_distance() {
@@ -313,4 +1054,45 @@ mod tests {
     fn assert_detector_cannot_be_built_from_too_large_minimum_relative_distance() {
         LanguageDetectorBuilder::from_all_languages().with_minimum_relative_distance(1.7);
     }
+
+    #[test]
+    fn assert_detector_cannot_be_built_from_missing_model_directory() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        let result = builder.try_with_model_directory("/some/missing/directory");
+
+        assert!(matches!(
+            result,
+            Err(LinguaError::ModelDirectoryNotFound(_))
+        ));
+        assert_eq!(builder.model_directory, None);
+    }
+
+    #[test]
+    fn assert_memory_budget_cannot_be_combined_with_model_store() {
+        let result =
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::German])
+                .with_model_store(Arc::new(ModelStore::new()))
+                .with_memory_budget(1024)
+                .try_build();
+
+        assert!(matches!(
+            result,
+            Err(LinguaError::MemoryBudgetWithModelStore)
+        ));
+    }
+
+    #[test]
+    fn assert_model_directory_cannot_be_combined_with_model_store() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let result =
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::German])
+                .with_model_store(Arc::new(ModelStore::new()))
+                .with_model_directory(model_directory.path())
+                .try_build();
+
+        assert!(matches!(
+            result,
+            Err(LinguaError::ModelDirectoryWithModelStore)
+        ));
+    }
 }
@@ -0,0 +1,258 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use crate::smoothing::UNKNOWN_NGRAM_FREQUENCY;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// The temperature fitted on all test data of all built-in languages by running
+/// `cargo run --release --example calibration` without a line limit. Its output is
+/// kept in `accuracy-reports/calibration.txt`.
+pub(crate) const DEFAULT_CALIBRATION_TEMPERATURE: f64 = 0.1519;
+
+const MINIMUM_TEMPERATURE: f64 = 1e-4;
+const MAXIMUM_TEMPERATURE: f64 = 1e2;
+const GOLDEN_SECTION_ITERATIONS: usize = 100;
+
+/// Applies the softmax function to the given length-normalized log-likelihoods divided by
+/// the given temperature. The probabilities are sorted in descending order.
+pub(crate) fn compute_calibrated_probabilities(
+    log_likelihoods: HashMap<Language, f64>,
+    temperature: f64,
+) -> Vec<(Language, f64)> {
+    if log_likelihoods.is_empty() {
+        return vec![];
+    }
+
+    let highest_log_likelihood = log_likelihoods
+        .values()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let exponentials = log_likelihoods
+        .into_iter()
+        .map(|(language, log_likelihood)| {
+            let exponential = ((log_likelihood - highest_log_likelihood) / temperature).exp();
+            (language, exponential)
+        })
+        .collect_vec();
+
+    let total: f64 = exponentials
+        .iter()
+        .map(|(_, exponential)| exponential)
+        .sum();
+
+    exponentials
+        .into_iter()
+        .map(|(language, exponential)| (language, exponential / total))
+        .sorted_by(
+            |(first_language, first_probability), (second_language, second_probability)| {
+                let sorted_by_probability =
+                    second_probability.partial_cmp(first_probability).unwrap();
+                let sorted_by_language = first_language.partial_cmp(second_language).unwrap();
+
+                sorted_by_probability.then(sorted_by_language)
+            },
+        )
+        .collect_vec()
+}
+
+/// Finds the temperature minimizing the mean negative log-likelihood of the expected
+/// languages by golden-section search on the logarithm of the temperature.
+///
+/// If the expected language of a sample has not been considered possible at all, it is
+/// counted with the lowest log-likelihood possible, as if none of the text's ngrams were
+/// known to it, so that the sample adds the maximal loss instead of being left out.
+/// Samples with a single possible language do not depend on the temperature and are
+/// ignored. If no sample remains, the default temperature is returned.
+pub(crate) fn fit_temperature(samples: &[(HashMap<Language, f64>, Language)]) -> f64 {
+    let informative_samples = samples
+        .iter()
+        .filter(|(log_likelihoods, _)| log_likelihoods.len() > 1)
+        .map(|(log_likelihoods, expected_language)| {
+            let mut log_likelihoods = log_likelihoods.clone();
+            if !log_likelihoods.contains_key(expected_language) {
+                let lowest_log_likelihood = log_likelihoods
+                    .values()
+                    .cloned()
+                    .fold(UNKNOWN_NGRAM_FREQUENCY.ln(), f64::min);
+                log_likelihoods.insert(expected_language.clone(), lowest_log_likelihood);
+            }
+            (log_likelihoods, expected_language.clone())
+        })
+        .collect_vec();
+
+    if informative_samples.is_empty() {
+        return DEFAULT_CALIBRATION_TEMPERATURE;
+    }
+
+    let inverse_golden_ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut lower_bound = MINIMUM_TEMPERATURE.ln();
+    let mut upper_bound = MAXIMUM_TEMPERATURE.ln();
+
+    for _ in 0..GOLDEN_SECTION_ITERATIONS {
+        let distance = inverse_golden_ratio * (upper_bound - lower_bound);
+        let first_point = upper_bound - distance;
+        let second_point = lower_bound + distance;
+
+        if compute_mean_negative_log_likelihood(&informative_samples, first_point.exp())
+            < compute_mean_negative_log_likelihood(&informative_samples, second_point.exp())
+        {
+            upper_bound = second_point;
+        } else {
+            lower_bound = first_point;
+        }
+    }
+
+    ((lower_bound + upper_bound) / 2.0).exp()
+}
+
+fn compute_mean_negative_log_likelihood(
+    samples: &[(HashMap<Language, f64>, Language)],
+    temperature: f64,
+) -> f64 {
+    let total: f64 = samples
+        .iter()
+        .map(|(log_likelihoods, expected_language)| {
+            let scaled_log_likelihoods = log_likelihoods
+                .values()
+                .map(|log_likelihood| log_likelihood / temperature)
+                .collect_vec();
+            let highest_scaled_log_likelihood = scaled_log_likelihoods
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max);
+            let log_sum_of_exponentials = highest_scaled_log_likelihood
+                + scaled_log_likelihoods
+                    .iter()
+                    .map(|it| (it - highest_scaled_log_likelihood).exp())
+                    .sum::<f64>()
+                    .ln();
+
+            log_sum_of_exponentials - log_likelihoods[expected_language] / temperature
+        })
+        .sum();
+
+    total / samples.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn assert_calibrated_probabilities_sum_up_to_one() {
+        let probabilities = compute_calibrated_probabilities(
+            hashmap!(English => -1.2, German => -1.5, French => -2.0),
+            0.5,
+        );
+
+        assert_eq!(
+            probabilities
+                .iter()
+                .map(|(language, _)| language.clone())
+                .collect_vec(),
+            vec![English, German, French]
+        );
+        assert!(approx_eq!(
+            f64,
+            probabilities
+                .iter()
+                .map(|(_, probability)| probability)
+                .sum(),
+            1.0,
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            probabilities[0].1 / probabilities[1].1,
+            (0.3_f64 / 0.5).exp(),
+            epsilon = 1e-12
+        ));
+    }
+
+    #[test]
+    fn assert_single_language_gets_probability_one() {
+        assert_eq!(
+            compute_calibrated_probabilities(hashmap!(German => 0.0), 0.5),
+            vec![(German, 1.0)]
+        );
+        assert_eq!(compute_calibrated_probabilities(hashmap!(), 0.5), vec![]);
+    }
+
+    #[test]
+    fn assert_lower_temperature_sharpens_probabilities() {
+        let log_likelihoods = hashmap!(English => -1.2, German => -1.5);
+        let sharp_probabilities = compute_calibrated_probabilities(log_likelihoods.clone(), 0.1);
+        let flat_probabilities = compute_calibrated_probabilities(log_likelihoods, 10.0);
+
+        assert!(sharp_probabilities[0].1 > 0.95);
+        assert!(flat_probabilities[0].1 < 0.55);
+    }
+
+    #[test]
+    fn assert_temperature_is_fitted_correctly() {
+        // The expected language leads by 1.0 in nine out of ten samples
+        // and trails by 1.0 in the remaining one, so the optimal temperature
+        // satisfies sigmoid(1 / temperature) = 0.9.
+        let mut samples = vec![];
+        for _ in 0..9 {
+            samples.push((hashmap!(English => -1.0, German => -2.0), English));
+        }
+        samples.push((hashmap!(English => -1.0, German => -2.0), German));
+
+        let temperature = fit_temperature(&samples);
+
+        assert!(
+            approx_eq!(f64, temperature, 1.0 / 9f64.ln(), epsilon = 1e-6),
+            "expected temperature {}, got {}",
+            1.0 / 9f64.ln(),
+            temperature
+        );
+    }
+
+    #[test]
+    fn assert_default_temperature_is_returned_without_informative_samples() {
+        let samples = vec![
+            (hashmap!(English => 0.0), English),
+            (hashmap!(English => 0.0), French),
+            (hashmap!(), French),
+        ];
+        assert_eq!(fit_temperature(&samples), DEFAULT_CALIBRATION_TEMPERATURE);
+    }
+
+    #[test]
+    fn assert_samples_without_expected_language_raise_temperature() {
+        let mut samples = vec![];
+        for _ in 0..9 {
+            samples.push((hashmap!(English => -1.0, German => -2.0), English));
+        }
+        let temperature = fit_temperature(&samples);
+
+        samples.push((hashmap!(English => -1.0, German => -2.0), French));
+        let temperature_with_missing_language = fit_temperature(&samples);
+
+        assert!(
+            temperature_with_missing_language > temperature,
+            "expected temperature above {}, got {}",
+            temperature,
+            temperature_with_missing_language
+        );
+    }
+}
@@ -0,0 +1,299 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use crate::error::LinguaError;
+use crate::language::is_built_in_language_name;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// This struct describes a language which is not part of the built-in languages,
+/// such as a dialect or an internal language for which a corpus is available.
+///
+/// Its language models are created with [LanguageModelFilesWriter] and loaded
+/// from a directory of their own. Wrapped in [Language::Custom], a custom language can be
+/// passed to [LanguageDetectorBuilder] like any built-in language and takes part in the
+/// detection alongside the built-in languages.
+///
+/// Two custom languages are equal if they have the same name and the same model directory,
+/// so that languages of the same name with different language models are never mixed up.
+/// Serializing a custom language only retains its name, so a deserialized custom language
+/// has neither alphabets nor a model directory and is not equal to the original one.
+/// This is why [LanguageModelFilesWriter::merge_into_language_model_files] takes the language
+/// of the language model files as an argument instead of reading it from the files.
+///
+/// [Language::Custom]: crate::Language::Custom
+/// [LanguageModelFilesWriter]: crate::LanguageModelFilesWriter
+/// [LanguageModelFilesWriter::merge_into_language_model_files]: crate::LanguageModelFilesWriter::merge_into_language_model_files
+/// [LanguageDetectorBuilder]: crate::LanguageDetectorBuilder
+#[derive(Clone)]
+pub struct CustomLanguage {
+    definition: Arc<CustomLanguageDefinition>,
+}
+
+#[derive(Default)]
+struct CustomLanguageDefinition {
+    name: String,
+    alphabets: HashSet<Alphabet>,
+    unique_characters: Option<String>,
+    model_directory: PathBuf,
+}
+
+impl CustomLanguage {
+    /// Creates and returns a custom language.
+    ///
+    /// `name`: The name identifying the language.
+    ///
+    /// `alphabets`: The alphabets the language is written in. They are used by the rule-based
+    /// engine to decide which languages are possible for a given text.
+    ///
+    /// `unique_characters`: Characters which are used by this language only among all
+    /// languages of a detector. A word containing one of them is attributed to this language.
+    ///
+    /// `model_directory`: The directory containing the files `unigrams.json.zip`,
+    /// `bigrams.json.zip`, `trigrams.json.zip`, `quadrigrams.json.zip` and `fivegrams.json.zip`
+    /// as written by [LanguageModelFilesWriter] or their binary counterparts.
+    ///
+    /// ⚠ Panics if the name is empty or the name of a built-in language, even one which is
+    /// left out as Cargo feature, if no alphabet is given or if the model directory does
+    /// not exist.
+    /// Use [try_new] to get an error instead.
+    ///
+    /// [LanguageModelFilesWriter]: crate::LanguageModelFilesWriter
+    /// [try_new]: CustomLanguage::try_new
+    pub fn new<P: AsRef<Path>>(
+        name: &str,
+        alphabets: &[Alphabet],
+        unique_characters: Option<&str>,
+        model_directory: P,
+    ) -> Self {
+        Self::try_new(name, alphabets, unique_characters, model_directory)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns a custom language.
+    ///
+    /// See [new] for details.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - the name is empty or the name of a built-in language, even one which is left out
+    ///   as Cargo feature
+    /// - no alphabet is given
+    /// - the model directory does not exist or is not a directory
+    ///
+    /// [new]: CustomLanguage::new
+    pub fn try_new<P: AsRef<Path>>(
+        name: &str,
+        alphabets: &[Alphabet],
+        unique_characters: Option<&str>,
+        model_directory: P,
+    ) -> Result<Self, LinguaError> {
+        let name = name.trim();
+        if name.is_empty() || is_built_in_language_name(name) {
+            return Err(LinguaError::InvalidCustomLanguageName(name.to_string()));
+        }
+        if alphabets.is_empty() {
+            return Err(LinguaError::CustomLanguageWithoutAlphabets(
+                name.to_string(),
+            ));
+        }
+        let model_directory = model_directory.as_ref();
+        if !model_directory.is_dir() {
+            return Err(LinguaError::ModelDirectoryNotFound(
+                model_directory.to_path_buf(),
+            ));
+        }
+
+        Ok(Self {
+            definition: Arc::new(CustomLanguageDefinition {
+                name: name.to_string(),
+                alphabets: alphabets.iter().cloned().collect(),
+                unique_characters: unique_characters
+                    .filter(|characters| !characters.is_empty())
+                    .map(|characters| characters.to_string()),
+                model_directory: model_directory.to_path_buf(),
+            }),
+        })
+    }
+
+    /// Returns the name of this custom language.
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub(crate) fn alphabets(&self) -> &HashSet<Alphabet> {
+        &self.definition.alphabets
+    }
+
+    pub(crate) fn unique_characters(&self) -> Option<&str> {
+        self.definition.unique_characters.as_deref()
+    }
+
+    pub(crate) fn model_directory(&self) -> &Path {
+        &self.definition.model_directory
+    }
+
+    fn from_name(name: String) -> Self {
+        Self {
+            definition: Arc::new(CustomLanguageDefinition {
+                name,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl Debug for CustomLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl PartialEq for CustomLanguage {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name() && self.model_directory() == other.model_directory()
+    }
+}
+
+impl Eq for CustomLanguage {}
+
+impl Hash for CustomLanguage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+        self.model_directory().hash(state);
+    }
+}
+
+impl PartialOrd for CustomLanguage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CustomLanguage {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name()
+            .cmp(other.name())
+            .then_with(|| self.model_directory().cmp(other.model_directory()))
+    }
+}
+
+impl Serialize for CustomLanguage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomLanguage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+    use tempfile::tempdir;
+
+    #[test]
+    fn assert_custom_language_is_created_correctly() {
+        let directory = tempdir().unwrap();
+        let language = CustomLanguage::new(
+            "Luxembourgish",
+            &[Alphabet::Latin],
+            Some("Ëë"),
+            directory.path(),
+        );
+
+        assert_eq!(language.name(), "Luxembourgish");
+        assert_eq!(language.alphabets(), &hashset!(Alphabet::Latin));
+        assert_eq!(language.unique_characters(), Some("Ëë"));
+        assert_eq!(language.model_directory(), directory.path());
+        assert_eq!(format!("{:?}", language), "Luxembourgish");
+    }
+
+    #[test]
+    fn assert_invalid_custom_languages_are_rejected() {
+        let directory = tempdir().unwrap();
+        let missing_directory = directory.path().join("missing");
+
+        assert!(matches!(
+            CustomLanguage::try_new(" ", &[Alphabet::Latin], None, directory.path()),
+            Err(LinguaError::InvalidCustomLanguageName(_))
+        ));
+        assert!(matches!(
+            CustomLanguage::try_new("english", &[Alphabet::Latin], None, directory.path()),
+            Err(LinguaError::InvalidCustomLanguageName(_))
+        ));
+        assert!(matches!(
+            CustomLanguage::try_new("Scots", &[], None, directory.path()),
+            Err(LinguaError::CustomLanguageWithoutAlphabets(_))
+        ));
+        assert!(matches!(
+            CustomLanguage::try_new("Scots", &[Alphabet::Latin], None, &missing_directory),
+            Err(LinguaError::ModelDirectoryNotFound(path)) if path == missing_directory
+        ));
+    }
+
+    #[test]
+    fn assert_custom_language_is_serialized_by_name() {
+        let directory = tempdir().unwrap();
+        let language = Language::Custom(CustomLanguage::new(
+            "Scots",
+            &[Alphabet::Latin],
+            None,
+            directory.path(),
+        ));
+
+        let serialized = serde_json::to_string(&language).unwrap();
+        assert_eq!(serialized, "{\"CUSTOM\":\"Scots\"}");
+
+        let deserialized = serde_json::from_str::<Language>(&serialized).unwrap();
+        assert!(matches!(&deserialized, Language::Custom(it) if it.name() == "Scots"));
+        assert_ne!(deserialized, language);
+    }
+
+    #[test]
+    fn assert_custom_languages_are_identified_by_name_and_model_directory() {
+        let first_directory = tempdir().unwrap();
+        let second_directory = tempdir().unwrap();
+        let scots = CustomLanguage::new("Scots", &[Alphabet::Latin], None, first_directory.path());
+
+        assert_eq!(
+            CustomLanguage::new("Scots", &[Alphabet::Latin], None, first_directory.path()),
+            scots
+        );
+        assert_ne!(
+            CustomLanguage::new("Scots", &[Alphabet::Latin], None, second_directory.path()),
+            scots
+        );
+        assert_eq!(
+            hashset!(
+                scots.clone(),
+                CustomLanguage::new("Scots", &[Alphabet::Latin], None, second_directory.path())
+            )
+            .len(),
+            2
+        );
+    }
+}
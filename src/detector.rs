@@ -15,1168 +15,3316 @@
  */
 
 use crate::alphabet::Alphabet;
+use crate::calibration::{compute_calibrated_probabilities, fit_temperature};
 use crate::constant::{
     CHARS_TO_LANGUAGES_MAPPING, JAPANESE_CHARACTER_SET, LANGUAGES_SUPPORTING_LOGOGRAMS,
-    MULTIPLE_WHITESPACE, NO_LETTER, NUMBERS, PUNCTUATION,
+    MULTIPLE_WHITESPACE, NO_LETTER, NUMBERS, PUNCTUATION, SENTENCES, TOKENS_WITHOUT_WHITESPACE,
+    TOKENS_WITH_OPTIONAL_WHITESPACE,
+};
+use crate::error::LinguaError;
+use crate::explanation::{
+    sort_counts, DetectionExplanation, LanguageEvidence, RuleDecision, MAXIMUM_LISTED_NGRAMS,
 };
-use crate::json::load_json;
 use crate::language::Language;
-use crate::model::TrainingDataLanguageModel;
-use crate::model::{LanguageModel, TestDataLanguageModel};
+use crate::model::TestDataLanguageModel;
 use crate::ngram::Ngram;
+use crate::options::DetectionOptions;
+use crate::outcome::{DecisionSource, DetectionOutcome, UndeterminedReason};
+use crate::prior::add_log_prior_probabilities;
+use crate::rejection::{compute_goodness_of_fit, fit_threshold, TextLength, TextSize};
+use crate::result::DetectionResult;
+use crate::session::DetectionSession;
+use crate::smoothing::Smoothing;
+use crate::store::ModelStore;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::str::FromStr;
-use std::sync::RwLock;
+use std::io::{self, BufRead};
+use std::ops::Range;
+use std::str::{self, FromStr};
+use std::sync::Arc;
 use strum::IntoEnumIterator;
 
-type BoxedLanguageModel = Box<dyn LanguageModel + Send + Sync>;
-type LazyLanguageModelMap = Lazy<RwLock<HashMap<Language, BoxedLanguageModel>>>;
-type StaticLanguageModelMap = &'static RwLock<HashMap<Language, BoxedLanguageModel>>;
+pub(crate) const DEFAULT_LONG_TEXT_THRESHOLD: usize = 120;
+const MINIMUM_CANDIDATE_WORD_LENGTH: usize = 5;
+const LANGUAGE_SWITCH_PENALTY: f64 = 0.5;
+
+/// The counts collected from the words of a text which the rule-based engine decides on.
+#[derive(Clone, Default)]
+pub(crate) struct WordCounts {
+    pub(crate) word_count: usize,
+    word_languages: HashMap<Option<Language>, u32>,
+    word_alphabets: HashMap<Alphabet, u32>,
+    character_languages: HashMap<Language, u32>,
+}
+
+/// The lengths of the ngrams which texts are scored with, depending on the number
+/// of characters of a text.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NgramLengths {
+    pub(crate) short_text: Range<usize>,
+    pub(crate) long_text: Range<usize>,
+    pub(crate) long_text_threshold: usize,
+}
+
+impl NgramLengths {
+    /// Returns the ngram lengths for a text of the given number of characters.
+    /// Ngram lengths exceeding the number of characters are left out.
+    pub(crate) fn for_character_count(&self, character_count: usize) -> Range<usize> {
+        let range = if character_count >= self.long_text_threshold {
+            self.long_text.clone()
+        } else {
+            self.short_text.clone()
+        };
+        range.start..range.end.min(character_count + 1)
+    }
+
+    /// Returns all ngram lengths used for either short or long texts, sorted in ascending order.
+    pub(crate) fn all(&self) -> Vec<usize> {
+        (1..6)
+            .filter(|&ngram_length| self.contains(ngram_length))
+            .collect()
+    }
+
+    /// Returns whether ngrams of the given length are used for either short or long texts.
+    pub(crate) fn contains(&self, ngram_length: usize) -> bool {
+        self.short_text.contains(&ngram_length) || self.long_text.contains(&ngram_length)
+    }
+}
+
+impl Default for NgramLengths {
+    fn default() -> Self {
+        Self {
+            short_text: 1..6,
+            long_text: 3..4,
+            long_text_threshold: DEFAULT_LONG_TEXT_THRESHOLD,
+        }
+    }
+}
 
-static UNIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static BIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static TRIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static QUADRIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static FIVEGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
+enum PreparedText {
+    Decided(Language),
+    Undetermined(UndeterminedReason),
+    Scorable(
+        Vec<(usize, TestDataLanguageModel)>,
+        HashSet<Language>,
+        TextSize,
+    ),
+}
 
 /// This struct detects the language of given input text.
 pub struct LanguageDetector {
-    languages: HashSet<Language>,
+    pub(crate) languages: HashSet<Language>,
     minimum_relative_distance: f64,
+    early_stopping_margin: Option<f64>,
+    calibration_temperature: f64,
+    smoothing: Smoothing,
+    pub(crate) ngram_lengths: NgramLengths,
+    prior_weights: HashMap<Language, f64>,
+    rejection_thresholds: HashMap<(Language, TextLength), f64>,
     languages_with_unique_characters: HashSet<Language>,
     one_language_alphabets: HashMap<Alphabet, Language>,
-    unigram_language_models: StaticLanguageModelMap,
-    bigram_language_models: StaticLanguageModelMap,
-    trigram_language_models: StaticLanguageModelMap,
-    quadrigram_language_models: StaticLanguageModelMap,
-    fivegram_language_models: StaticLanguageModelMap,
+    pub(crate) model_store: Arc<ModelStore>,
 }
 
 impl LanguageDetector {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from(
         languages: HashSet<Language>,
         minimum_relative_distance: f64,
+        early_stopping_margin: Option<f64>,
+        calibration_temperature: f64,
+        smoothing: Smoothing,
+        ngram_lengths: NgramLengths,
+        prior_weights: HashMap<Language, f64>,
+        rejection_thresholds: HashMap<(Language, TextLength), f64>,
         is_every_language_model_preloaded: bool,
-    ) -> Self {
+        model_store: Arc<ModelStore>,
+    ) -> Result<Self, LinguaError> {
         let languages_with_unique_characters = languages
             .iter()
             .filter(|it| it.unique_characters().is_some())
             .cloned()
             .collect();
+        let custom_language_alphabets = languages
+            .iter()
+            .filter(|it| matches!(it, Language::Custom(_)))
+            .flat_map(|it| it.alphabets())
+            .collect::<HashSet<_>>();
         let one_language_alphabets = Alphabet::all_supporting_single_language()
             .into_iter()
-            .filter(|(_, language)| languages.contains(language))
+            .filter(|(alphabet, language)| {
+                languages.contains(language) && !custom_language_alphabets.contains(alphabet)
+            })
             .collect();
 
         let mut detector = Self {
             languages: languages.clone(),
             minimum_relative_distance,
+            early_stopping_margin,
+            calibration_temperature,
+            smoothing,
+            ngram_lengths,
+            prior_weights,
+            rejection_thresholds,
             languages_with_unique_characters,
             one_language_alphabets,
-            unigram_language_models: &UNIGRAM_MODELS,
-            bigram_language_models: &BIGRAM_MODELS,
-            trigram_language_models: &TRIGRAM_MODELS,
-            quadrigram_language_models: &QUADRIGRAM_MODELS,
-            fivegram_language_models: &FIVEGRAM_MODELS,
+            model_store,
         };
 
         if is_every_language_model_preloaded {
-            detector.preload_language_models(&languages);
+            detector.preload_language_models(&languages)?;
+        } else {
+            detector.check_language_models(&languages)?;
         }
 
-        detector
+        Ok(detector)
     }
 
-    fn preload_language_models(&mut self, languages: &HashSet<Language>) {
-        languages.par_iter().for_each(|language| {
-            self.load_language_models(self.unigram_language_models, language, 1);
-            self.load_language_models(self.bigram_language_models, language, 2);
-            self.load_language_models(self.trigram_language_models, language, 3);
-            self.load_language_models(self.quadrigram_language_models, language, 4);
-            self.load_language_models(self.fivegram_language_models, language, 5);
-        });
+    fn preload_language_models(
+        &mut self,
+        languages: &HashSet<Language>,
+    ) -> Result<(), LinguaError> {
+        let ngram_lengths = self.ngram_lengths.all();
+
+        languages.par_iter().try_for_each(|language| {
+            ngram_lengths.iter().try_for_each(|&ngram_length| {
+                self.model_store
+                    .load_language_models(language, ngram_length)
+            })
+        })
     }
 
-    /// Detects the language of given input text.
-    /// If the language cannot be reliably detected, `None` is returned.
-    pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
-        let confidence_values = self.compute_language_confidence_values(text);
+    /// Checks that the language models to be loaded lazily exist, so that missing
+    /// language model files are reported when building the detector already.
+    fn check_language_models(&self, languages: &HashSet<Language>) -> Result<(), LinguaError> {
+        let ngram_lengths = self.ngram_lengths.all();
 
-        if confidence_values.is_empty() {
-            return None;
-        }
+        languages.iter().try_for_each(|language| {
+            ngram_lengths.iter().try_for_each(|&ngram_length| {
+                self.model_store
+                    .check_language_model(language, ngram_length)
+            })
+        })
+    }
 
-        let (most_likely_language, most_likely_language_probability) =
-            &confidence_values.first().unwrap();
+    /// Returns the languages this detector decides between, sorted by name.
+    pub fn languages(&self) -> Vec<Language> {
+        self.languages.iter().cloned().sorted().collect_vec()
+    }
 
-        if confidence_values.len() == 1 {
-            return Some(most_likely_language.clone());
-        }
+    /// Unloads all language models of the given languages.
+    ///
+    /// The language models are loaded again on demand. As the language models are kept in
+    /// the detector's [ModelStore], this affects all detectors sharing the same store.
+    pub fn unload_language_models(&self, languages: &[Language]) {
+        self.model_store.unload_language_models(languages);
+    }
 
-        let (_, second_most_likely_language_probability) = &confidence_values.get(1).unwrap();
+    /// Unloads the language models of the given ngram lengths for all languages.
+    /// Unigram models have length 1, fivegram models have length 5.
+    ///
+    /// The language models are loaded again on demand. As the language models are kept in
+    /// the detector's [ModelStore], this affects all detectors sharing the same store.
+    ///
+    /// ⚠ Panics if any of the ngram lengths is not in range 1..6.
+    pub fn unload_language_models_of_ngram_lengths(&self, ngram_lengths: &[usize]) {
+        self.model_store
+            .unload_language_models_of_ngram_lengths(ngram_lengths);
+    }
 
-        if (most_likely_language_probability - second_most_likely_language_probability).abs()
-            < f64::EPSILON
-        {
-            return None;
-        }
+    /// Returns the approximate amount of memory in bytes occupied by the currently
+    /// loaded language models of each language.
+    pub fn memory_usage(&self) -> HashMap<Language, usize> {
+        self.model_store.memory_usage()
+    }
 
-        if (most_likely_language_probability - second_most_likely_language_probability)
-            < self.minimum_relative_distance
-        {
-            return None;
-        }
+    /// Detects the language of given input text.
+    /// If the language cannot be reliably detected, `None` is returned.
+    pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
+        self.detect_language_outcome_of(text).language()
+    }
 
-        Some(most_likely_language.clone())
+    /// Detects the language of given input text and describes the outcome.
+    ///
+    /// This method works like [detect_language_of] but tells apart why no language has been
+    /// detected: A [DetectionOutcome] is either a detected language, together with whether
+    /// the rule-based engine or the language models have decided on it, a set of ambiguous
+    /// candidates whose confidence values are closer than the minimum relative distance,
+    /// or the reason why the language cannot be determined at all. This includes texts
+    /// rejected by the thresholds set with [with_rejection_thresholds].
+    ///
+    /// [detect_language_of]: LanguageDetector::detect_language_of
+    /// [with_rejection_thresholds]: crate::LanguageDetectorBuilder::with_rejection_thresholds
+    pub fn detect_language_outcome_of<T: Into<String>>(&self, text: T) -> DetectionOutcome {
+        self.detect_language_outcome_with_options(text, &DetectionOptions::new())
     }
 
-    /// Computes confidence values for each language considered possible for the given input text.
+    /// Detects the language of given input text among the given languages only.
+    /// If the language cannot be reliably detected, `None` is returned.
     ///
-    /// A vector of all possible languages is returned, sorted by their confidence value in
-    /// descending order. The values that this method computes are part of a **relative**
-    /// confidence metric, not of an absolute one. Each value is a number between 0.0 and 1.0.
-    /// The most likely language is always returned with value 1.0. All other languages get values
-    /// assigned which are lower than 1.0, denoting how less likely those languages are in
-    /// comparison to the most likely language.
+    /// Languages which the detector has not been built from are ignored. In contrast to
+    /// building a new detector for the given languages, the language models already loaded
+    /// are reused. See [detect_language_with_options] for overriding further settings.
     ///
-    /// The vector returned by this method does not necessarily contain all languages which the
-    /// calling instance of `LanguageDetector` was built from. If the rule-based engine decides
-    /// that a specific language is truly impossible, then it will not be part of the returned
-    /// vector. Likewise, if no ngram probabilities can be found within the detector's languages
-    /// for the given input text, the returned vector will be empty. The confidence value for
-    /// each language not being part of the returned vector is assumed to be 0.0.
-    pub fn compute_language_confidence_values<T: Into<String>>(
+    /// [detect_language_with_options]: LanguageDetector::detect_language_with_options
+    pub fn detect_language_among<T: Into<String>>(
         &self,
         text: T,
-    ) -> Vec<(Language, f64)> {
-        let mut values = vec![];
-        let cleaned_up_text = self.clean_up_input_text(text.into());
+        languages: &[Language],
+    ) -> Option<Language> {
+        self.detect_language_with_options(text, &DetectionOptions::new().with_languages(languages))
+    }
 
-        if cleaned_up_text.is_empty() || NO_LETTER.is_match(&cleaned_up_text) {
-            return values;
-        }
+    /// Detects the language of given input text with the settings of the detector
+    /// overridden by the given [DetectionOptions] for this call only.
+    /// If the language cannot be reliably detected, `None` is returned.
+    pub fn detect_language_with_options<T: Into<String>>(
+        &self,
+        text: T,
+        options: &DetectionOptions,
+    ) -> Option<Language> {
+        self.detect_language_outcome_with_options(text, options)
+            .language()
+    }
 
-        let words = self.split_text_into_words(&cleaned_up_text);
-        let language_detected_by_rules = self.detect_language_with_rules(&words);
+    /// Detects the language of given input text with the settings of the detector
+    /// overridden by the given [DetectionOptions] and describes the outcome.
+    ///
+    /// See [detect_language_outcome_of] for details.
+    ///
+    /// [detect_language_outcome_of]: LanguageDetector::detect_language_outcome_of
+    pub fn detect_language_outcome_with_options<T: Into<String>>(
+        &self,
+        text: T,
+        options: &DetectionOptions,
+    ) -> DetectionOutcome {
+        let languages = options.resolve_languages(&self.languages);
 
-        if let Some(language) = language_detected_by_rules {
-            values.push((language, 1.0));
-            return values;
+        if languages.is_empty() {
+            return DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::NoLanguages,
+            };
         }
 
-        let filtered_languages = self.filter_languages_by_rules(words);
-
-        if filtered_languages.len() == 1 {
-            let filtered_language = filtered_languages.into_iter().next().unwrap();
-            values.push((filtered_language, 1.0));
-            return values;
+        match self.prepare_text(text.into(), &languages) {
+            PreparedText::Decided(language) => DetectionOutcome::Detected {
+                language,
+                source: DecisionSource::Rules,
+            },
+            PreparedText::Undetermined(reason) => DetectionOutcome::Undetermined { reason },
+            PreparedText::Scorable(test_data_models, filtered_languages, text_size) => {
+                let _detection = self.model_store.begin_detection();
+                let confidence_values = self.compute_confidence_values_of_test_data_models(
+                    &test_data_models,
+                    filtered_languages,
+                    options.hints(),
+                    true,
+                    &|language: &Language, ngram: &Ngram| {
+                        self.look_up_smoothed_ngram_probability(language, ngram)
+                    },
+                );
+                self.decide_outcome_of_confidence_values(
+                    &confidence_values,
+                    &test_data_models,
+                    text_size,
+                    options,
+                )
+            }
         }
+    }
 
-        let character_count = cleaned_up_text.chars().count();
-        let ngram_length_range = if character_count >= 120 {
-            3..4usize
-        } else {
-            1..6usize
+    /// Decides on the outcome of the language models for the given confidence values and
+    /// rejects the languages whose language models the ngrams fit worse than their rejection
+    /// thresholds. A detected language which is rejected makes the language unknown. Rejected
+    /// candidates of an ambiguous outcome are left out, and the language is unknown only if
+    /// all candidates are rejected.
+    fn decide_outcome_of_confidence_values(
+        &self,
+        confidence_values: &[(Language, f64)],
+        test_data_models: &[(usize, TestDataLanguageModel)],
+        text_size: TextSize,
+        options: &DetectionOptions,
+    ) -> DetectionOutcome {
+        let outcome = self.detect_outcome_from_confidence_values(
+            confidence_values,
+            options.resolve_minimum_relative_distance(self.minimum_relative_distance),
+        );
+        let unknown_language = DetectionOutcome::Undetermined {
+            reason: UndeterminedReason::UnknownLanguage,
         };
+        match outcome {
+            DetectionOutcome::Detected { ref language, .. }
+                if self.is_rejected(language, text_size, test_data_models) =>
+            {
+                unknown_language
+            }
+            DetectionOutcome::Ambiguous { candidates } => {
+                let candidates = candidates
+                    .into_iter()
+                    .filter(|language| !self.is_rejected(language, text_size, test_data_models))
+                    .collect_vec();
+                if candidates.is_empty() {
+                    unknown_language
+                } else {
+                    DetectionOutcome::Ambiguous { candidates }
+                }
+            }
+            _ => outcome,
+        }
+    }
 
-        #[allow(clippy::type_complexity)]
-        let all_probabilities_and_unigram_counts: Vec<(
-            HashMap<Language, f64>,
-            Option<HashMap<Language, u32>>,
-        )> = ngram_length_range
-            .into_par_iter()
-            .filter(|i| character_count >= *i)
-            .map(|ngram_length| {
-                self.look_up_language_models(&cleaned_up_text, ngram_length, &filtered_languages)
-            })
-            .collect();
-
-        let all_probabilities = all_probabilities_and_unigram_counts
-            .iter()
-            .map(|(probabilities, _)| probabilities)
-            .collect::<Vec<_>>();
-
-        let unigram_counts = &all_probabilities_and_unigram_counts[0].1;
-
-        let summed_up_probabilities =
-            self.sum_up_probabilities(all_probabilities, unigram_counts, filtered_languages);
+    /// Attempts to detect multiple languages in mixed-language text.
+    ///
+    /// This feature is experimental and under continuous development.
+    ///
+    /// A vector of [DetectionResult] is returned containing an entry for each contiguous
+    /// single-language text section as identified by the library. Each entry consists
+    /// of the identified language, a start index and an end index. The indices denote
+    /// the substring that has been identified as a contiguous single-language text section.
+    /// The indices are byte offsets into the original input text, so the sections can be
+    /// retrieved by slicing the input text with them.
+    ///
+    /// The candidate languages are collected by running the usual detection on the entire
+    /// text, on every sentence and on every word having at least five characters. Afterwards, each word is
+    /// assigned one of these candidate languages and adjacent words of the same language
+    /// are merged into sections.
+    pub fn detect_multiple_languages_of<T: Into<String>>(&self, text: T) -> Vec<DetectionResult> {
+        let text_str = text.into();
+        let token_matches = TOKENS_WITH_OPTIONAL_WHITESPACE
+            .find_iter(&text_str)
+            .collect_vec();
 
-        if summed_up_probabilities.is_empty() {
-            return values;
+        if token_matches.is_empty() {
+            return vec![];
         }
 
-        let highest_probability = self.get_highest_probability(&summed_up_probabilities);
-
-        self.compute_confidence_values(summed_up_probabilities, highest_probability)
-    }
+        let mut candidate_languages = hashset!();
 
-    fn clean_up_input_text(&self, text: String) -> String {
-        let trimmed = text.trim().to_lowercase();
-        let without_punctuation = PUNCTUATION.replace_all(&trimmed, "");
-        let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
-        let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&without_numbers, " ");
-        normalized_whitespace.to_string()
-    }
+        if let Some(language) = self.detect_language_of(text_str.clone()) {
+            candidate_languages.insert(language);
+        }
 
-    fn split_text_into_words(&self, text: &str) -> Vec<String> {
-        let mut normalized_text_builder = vec![];
-        for chr in text.chars() {
-            normalized_text_builder.push(chr.to_string());
-            if self.is_logogram(chr) {
-                normalized_text_builder.push(' '.to_string());
+        for sentence in SENTENCES.find_iter(&text_str) {
+            if let Some(language) = self.detect_language_of(sentence.as_str()) {
+                candidate_languages.insert(language);
             }
         }
-        let normalized_text: String = normalized_text_builder.join("");
-        if normalized_text.contains(' ') {
-            normalized_text
-                .split(' ')
-                .filter(|it| !it.is_empty())
-                .map(|it| it.to_string())
-                .collect_vec()
-        } else {
-            vec![normalized_text]
+
+        for word in TOKENS_WITHOUT_WHITESPACE.find_iter(&text_str) {
+            if word.as_str().chars().count() >= MINIMUM_CANDIDATE_WORD_LENGTH {
+                if let Some(language) = self.detect_language_of(word.as_str()) {
+                    candidate_languages.insert(language);
+                }
+            }
         }
-    }
 
-    fn is_logogram(&self, chr: char) -> bool {
-        if chr.is_whitespace() {
-            false
-        } else {
-            LANGUAGES_SUPPORTING_LOGOGRAMS
-                .iter()
-                .flat_map(|it| it.alphabets())
-                .any(|it| it.matches(&chr.to_string()))
+        if candidate_languages.is_empty() {
+            return vec![];
         }
-    }
 
-    fn detect_language_with_rules(&self, words: &[String]) -> Option<Language> {
-        let mut total_language_counts = HashMap::<Option<Language>, u32>::new();
-        let half_word_count = (words.len() as f64) * 0.5;
+        if candidate_languages.len() == 1 {
+            let language = candidate_languages.into_iter().next().unwrap();
+            return vec![DetectionResult::new(
+                0,
+                text_str.len(),
+                token_matches.len(),
+                language,
+            )];
+        }
 
-        for word in words {
-            let mut word_language_counts = HashMap::<Language, u32>::new();
-
-            for character in word.chars() {
-                let mut is_match = false;
-                let mut buffer = [0; 4];
-                let char_str = character.encode_utf8(&mut buffer);
-
-                for (alphabet, language) in self.one_language_alphabets.iter() {
-                    if alphabet.matches(char_str) {
-                        self.increment_counter(&mut word_language_counts, language.clone());
-                        is_match = true;
-                        break;
-                    }
-                }
+        let token_languages = self.assign_languages_to_tokens(
+            &token_matches
+                .iter()
+                .map(|token| token.as_str())
+                .collect_vec(),
+            candidate_languages,
+        );
 
-                if !is_match {
-                    if cfg!(feature = "chinese") && Alphabet::Han.matches(char_str) {
-                        self.increment_counter(
-                            &mut word_language_counts,
-                            Language::from_str("Chinese").unwrap(),
-                        );
-                    } else if cfg!(feature = "japanese")
-                        && JAPANESE_CHARACTER_SET.is_match(char_str)
-                    {
-                        self.increment_counter(
-                            &mut word_language_counts,
-                            Language::from_str("Japanese").unwrap(),
-                        );
-                    } else if Alphabet::Latin.matches(char_str)
-                        || Alphabet::Cyrillic.matches(char_str)
-                        || Alphabet::Devanagari.matches(char_str)
-                    {
-                        self.languages_with_unique_characters
-                            .iter()
-                            .filter(|it| it.unique_characters().unwrap().contains(character))
-                            .for_each(|it| {
-                                self.increment_counter(&mut word_language_counts, it.clone())
-                            });
-                    }
-                }
-            }
+        let mut sections: Vec<DetectionResult> = vec![];
 
-            if word_language_counts.is_empty() {
-                self.increment_counter(&mut total_language_counts, None);
-            } else if word_language_counts.len() == 1 {
-                let counted_languages = word_language_counts.keys().collect_vec();
-                let language = *counted_languages.first().unwrap();
-                if self.languages.contains(language) {
-                    self.increment_counter(&mut total_language_counts, Some(language.clone()));
-                } else {
-                    self.increment_counter(&mut total_language_counts, None);
+        for (token, language) in token_matches.iter().zip(token_languages) {
+            match sections.last_mut() {
+                Some(section) if section.language == language => {
+                    section.end_index = token.end();
+                    section.word_count += 1;
                 }
-            } else if cfg!(feature = "chinese")
-                && cfg!(feature = "japanese")
-                && word_language_counts.contains_key(&Language::from_str("Chinese").unwrap())
-                && word_language_counts.contains_key(&Language::from_str("Japanese").unwrap())
-            {
-                self.increment_counter(
-                    &mut total_language_counts,
-                    Some(Language::from_str("Japanese").unwrap()),
-                );
-            } else {
-                let sorted_word_language_counts = word_language_counts
-                    .into_iter()
-                    .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
-                    .collect_vec();
-                let (most_frequent_language, first_count) = &sorted_word_language_counts[0];
-                let (_, second_count) = &sorted_word_language_counts[1];
-
-                if first_count > second_count && self.languages.contains(most_frequent_language) {
-                    self.increment_counter(
-                        &mut total_language_counts,
-                        Some(most_frequent_language.clone()),
-                    );
-                } else {
-                    self.increment_counter(&mut total_language_counts, None);
+                Some(section) => {
+                    let start_index = section.end_index;
+                    sections.push(DetectionResult::new(start_index, token.end(), 1, language));
                 }
+                None => sections.push(DetectionResult::new(0, token.end(), 1, language)),
             }
         }
 
-        let unknown_language_count = *total_language_counts.get(&None).or(Some(&0)).unwrap() as f64;
-
-        if unknown_language_count < half_word_count {
-            total_language_counts.remove(&None);
-        }
-
-        if total_language_counts.is_empty() {
-            return None;
-        }
-
-        if total_language_counts.len() == 1 {
-            return total_language_counts.iter().next().unwrap().0.clone();
+        if let Some(last_section) = sections.last_mut() {
+            last_section.end_index = text_str.len();
         }
 
-        if total_language_counts.len() == 2
-            && cfg!(feature = "chinese")
-            && cfg!(feature = "japanese")
-            && total_language_counts.contains_key(&Some(Language::from_str("Chinese").unwrap()))
-            && total_language_counts.contains_key(&Some(Language::from_str("Japanese").unwrap()))
-        {
-            return Some(Language::from_str("Japanese").unwrap());
-        }
+        sections
+    }
 
-        let sorted_total_language_counts = total_language_counts
-            .into_iter()
-            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+    /// Assigns one of the candidate languages to each token such that the sum of the tokens'
+    /// confidence values is maximized while every change of language between adjacent tokens
+    /// is penalized. This is the Viterbi algorithm applied to a hidden Markov model whose
+    /// states are the candidate languages.
+    fn assign_languages_to_tokens(
+        &self,
+        tokens: &[&str],
+        candidate_languages: HashSet<Language>,
+    ) -> Vec<Language> {
+        let languages = candidate_languages.iter().cloned().sorted().collect_vec();
+        let token_scores = tokens
+            .iter()
+            .map(|token| {
+                let confidence_values = self.compute_language_confidence_values_for_languages(
+                    token.to_string(),
+                    &candidate_languages,
+                    &hashmap!(),
+                );
+                languages
+                    .iter()
+                    .map(|language| {
+                        confidence_values
+                            .iter()
+                            .find(|(it, _)| it == language)
+                            .map_or(0.0, |(_, value)| *value)
+                    })
+                    .collect_vec()
+            })
             .collect_vec();
-        let (most_frequent_language, first_count) = sorted_total_language_counts[0].clone();
-        let (_, second_count) = sorted_total_language_counts[1];
 
-        if first_count == second_count {
-            return None;
-        }
+        let mut scores = token_scores[0].clone();
+        let mut backpointers = vec![];
 
-        most_frequent_language
-    }
+        for current_token_scores in token_scores.iter().skip(1) {
+            let mut next_scores = vec![];
+            let mut current_backpointers = vec![];
 
-    fn filter_languages_by_rules(&self, words: Vec<String>) -> HashSet<Language> {
-        let mut detected_alphabets = HashMap::<Alphabet, u32>::new();
-        let half_word_count = (words.len() as f64) * 0.5;
+            for (i, token_score) in current_token_scores.iter().enumerate() {
+                let mut best_previous = i;
+                let mut best_score = scores[i];
 
-        for word in words.iter() {
-            for alphabet in Alphabet::iter() {
-                if alphabet.matches(word) {
-                    self.increment_counter(&mut detected_alphabets, alphabet);
-                    break;
+                for (j, &score) in scores.iter().enumerate() {
+                    if j != i && score - LANGUAGE_SWITCH_PENALTY > best_score {
+                        best_previous = j;
+                        best_score = score - LANGUAGE_SWITCH_PENALTY;
+                    }
                 }
+
+                next_scores.push(best_score + token_score);
+                current_backpointers.push(best_previous);
             }
-        }
 
-        if detected_alphabets.is_empty() {
-            return self.languages.clone();
+            scores = next_scores;
+            backpointers.push(current_backpointers);
         }
 
-        if detected_alphabets.len() > 1 {
-            let mut distinct_alphabets = hashset!();
-            for count in detected_alphabets.values() {
-                distinct_alphabets.insert(count);
-            }
-            if distinct_alphabets.len() == 1 {
-                return self.languages.clone();
+        let mut best_last = 0;
+        for (i, &score) in scores.iter().enumerate() {
+            if score > scores[best_last] {
+                best_last = i;
             }
         }
 
-        let most_frequent_alphabet = detected_alphabets
-            .into_iter()
-            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
-            .next()
-            .unwrap()
-            .0;
-
-        let filtered_languages = self
-            .languages
-            .iter()
-            .cloned()
-            .filter(|it| it.alphabets().contains(&most_frequent_alphabet))
-            .collect::<HashSet<_>>();
+        let mut path = vec![best_last];
+        for current_backpointers in backpointers.iter().rev() {
+            path.push(current_backpointers[*path.last().unwrap()]);
+        }
 
-        let mut language_counts = HashMap::<&Language, u32>::new();
+        path.into_iter()
+            .rev()
+            .map(|i| languages[i].clone())
+            .collect_vec()
+    }
 
-        for word in words.iter() {
-            for (characters, languages) in CHARS_TO_LANGUAGES_MAPPING.iter() {
-                for character in characters.chars() {
-                    if word.contains(character) {
-                        for language in languages.iter() {
-                            self.increment_counter(&mut language_counts, language);
-                        }
-                    }
+    /// Decides on the outcome of the language models for the given confidence values. All
+    /// languages closer to the most likely one than the minimum relative distance are
+    /// ambiguous candidates.
+    fn detect_outcome_from_confidence_values(
+        &self,
+        confidence_values: &[(Language, f64)],
+        minimum_relative_distance: f64,
+    ) -> DetectionOutcome {
+        let highest_value = match confidence_values.first() {
+            Some((_, value)) => *value,
+            None => {
+                return DetectionOutcome::Undetermined {
+                    reason: UndeterminedReason::UnknownNgrams,
                 }
             }
-        }
+        };
 
-        let languages_subset = language_counts
-            .into_iter()
-            .filter(|(_, count)| (*count as f64) >= half_word_count)
-            .map(|(language, _)| language)
-            .collect::<HashSet<_>>();
+        let mut candidates = confidence_values
+            .iter()
+            .take_while(|(_, value)| {
+                let distance = highest_value - value;
+                distance < f64::EPSILON || distance < minimum_relative_distance
+            })
+            .map(|(language, _)| language.clone())
+            .collect_vec();
 
-        if !languages_subset.is_empty() {
-            filtered_languages
-                .into_iter()
-                .filter(|it| languages_subset.contains(&it))
-                .collect::<HashSet<_>>()
+        if candidates.len() == 1 {
+            DetectionOutcome::Detected {
+                language: candidates.remove(0),
+                source: DecisionSource::Ngrams,
+            }
         } else {
-            filtered_languages
+            DetectionOutcome::Ambiguous { candidates }
         }
     }
 
-    fn look_up_language_models(
+    pub(crate) fn detect_language_from_confidence_values(
         &self,
-        text: &str,
-        ngram_length: usize,
-        filtered_languages: &HashSet<Language>,
-    ) -> (HashMap<Language, f64>, Option<HashMap<Language, u32>>) {
-        let test_data_model = TestDataLanguageModel::from(text, ngram_length);
-        let probabilities =
-            self.compute_language_probabilities(&test_data_model, filtered_languages);
-        let unigram_counts = if ngram_length == 1 {
-            let languages = probabilities.keys().collect_vec();
-            let intersected_languages = if !languages.is_empty() {
-                filtered_languages
-                    .iter()
-                    .cloned()
-                    .filter(|it| languages.contains(&it))
-                    .collect()
-            } else {
-                filtered_languages.clone()
-            };
-            Some(self.count_unigrams(&test_data_model, &intersected_languages))
-        } else {
-            None
-        };
-
-        (probabilities, unigram_counts)
+        confidence_values: &[(Language, f64)],
+    ) -> Option<Language> {
+        self.detect_outcome_from_confidence_values(
+            confidence_values,
+            self.minimum_relative_distance,
+        )
+        .language()
     }
 
-    fn compute_language_probabilities(
+    /// Computes confidence values for each language considered possible for the given input text.
+    ///
+    /// A vector of all possible languages is returned, sorted by their confidence value in
+    /// descending order. The values that this method computes are part of a **relative**
+    /// confidence metric, not of an absolute one. Each value is a number between 0.0 and 1.0.
+    /// The most likely language is always returned with value 1.0. All other languages get values
+    /// assigned which are lower than 1.0, denoting how less likely those languages are in
+    /// comparison to the most likely language.
+    ///
+    /// The vector returned by this method does not necessarily contain all languages which the
+    /// calling instance of `LanguageDetector` was built from. If the rule-based engine decides
+    /// that a specific language is truly impossible, then it will not be part of the returned
+    /// vector. Likewise, if no ngram probabilities can be found within the detector's languages
+    /// for the given input text, the returned vector will be empty. The confidence value for
+    /// each language not being part of the returned vector is assumed to be 0.0.
+    pub fn compute_language_confidence_values<T: Into<String>>(
         &self,
-        model: &TestDataLanguageModel,
-        filtered_languages: &HashSet<Language>,
-    ) -> HashMap<Language, f64> {
-        let mut probabilities = hashmap!();
-        for language in filtered_languages.iter() {
-            let sum = self.compute_sum_of_ngram_probabilities(language, &model.ngrams);
-            if sum < 0.0 {
-                probabilities.insert(language.clone(), sum);
-            }
-        }
-        probabilities
+        text: T,
+    ) -> Vec<(Language, f64)> {
+        self.compute_language_confidence_values_for_languages(
+            text.into(),
+            &self.languages,
+            &hashmap!(),
+        )
     }
 
-    fn get_highest_probability(&self, probabilities: &HashMap<Language, f64>) -> f64 {
-        probabilities
-            .iter()
-            .map(|(_, &probability)| probability)
-            .sorted_by(|&first, &second| second.partial_cmp(&first).unwrap())
-            .next()
-            .unwrap()
+    /// Computes confidence values for each language considered possible for the given input
+    /// text among the languages allowed by the given [DetectionOptions].
+    ///
+    /// See [compute_language_confidence_values] for details.
+    ///
+    /// [compute_language_confidence_values]: LanguageDetector::compute_language_confidence_values
+    pub fn compute_language_confidence_values_with_options<T: Into<String>>(
+        &self,
+        text: T,
+        options: &DetectionOptions,
+    ) -> Vec<(Language, f64)> {
+        self.compute_language_confidence_values_for_languages(
+            text.into(),
+            &options.resolve_languages(&self.languages),
+            options.hints(),
+        )
     }
 
-    fn compute_confidence_values(
+    /// Computes calibrated probabilities for each language considered possible for the given
+    /// input text.
+    ///
+    /// In contrast to [compute_language_confidence_values], which relates all languages to the
+    /// most likely one, this method returns posterior probabilities summing up to 1.0. They are
+    /// computed by applying the softmax function to the log-likelihoods of the languages,
+    /// normalized by the length of the text and divided by the calibration temperature set with
+    /// [with_calibration_temperature]. The probabilities can therefore be compared and
+    /// thresholded across texts of different length.
+    ///
+    /// The probabilities are computed from the language models alone. Neither the prior weights
+    /// set with [with_prior_weights] nor any hints are taken into account, so that the
+    /// calibration temperature does not depend on them. If prior weights are set, the most
+    /// probable language may therefore differ from the one returned by [detect_language_of].
+    /// Without prior weights, the languages are ordered in the same way as by
+    /// [compute_language_confidence_values].
+    ///
+    /// A language which the rule-based engine decides on is returned with probability 1.0.
+    /// If no ngram probabilities can be found within the detector's languages for the given
+    /// input text, the returned vector will be empty.
+    ///
+    /// [compute_language_confidence_values]: LanguageDetector::compute_language_confidence_values
+    /// [detect_language_of]: LanguageDetector::detect_language_of
+    /// [with_calibration_temperature]: crate::LanguageDetectorBuilder::with_calibration_temperature
+    /// [with_prior_weights]: crate::LanguageDetectorBuilder::with_prior_weights
+    pub fn compute_calibrated_probabilities<T: Into<String>>(
         &self,
-        probabilities: HashMap<Language, f64>,
-        highest_probability: f64,
+        text: T,
     ) -> Vec<(Language, f64)> {
-        probabilities
-            .into_iter()
-            .map(|(language, probability)| (language, highest_probability / probability))
-            .sorted_by(
-                |(first_language, first_probability), (second_language, second_probability)| {
-                    let sorted_by_probability =
-                        second_probability.partial_cmp(first_probability).unwrap();
-                    let sorted_by_language = first_language.partial_cmp(second_language).unwrap();
+        let log_likelihoods = self.compute_normalized_log_likelihoods(text.into());
+        compute_calibrated_probabilities(log_likelihoods, self.calibration_temperature)
+    }
 
-                    sorted_by_probability.then(sorted_by_language)
-                },
-            )
-            .collect_vec()
+    /// Fits the calibration temperature used by [compute_calibrated_probabilities] on the
+    /// given samples, each consisting of the expected language and a text written in it.
+    ///
+    /// The returned temperature minimizes the negative log-likelihood of the expected languages
+    /// and can be passed to [with_calibration_temperature] when building a new detector.
+    /// Samples whose expected language is not considered possible are taken into account
+    /// with the lowest log-likelihood possible, as if none of their ngrams were known to it.
+    /// The samples should resemble the texts to be classified later on, both in length and in
+    /// the languages they are written in. The `calibration` example shows how to fit the
+    /// temperature on the test data bundled with the language models.
+    ///
+    /// [compute_calibrated_probabilities]: LanguageDetector::compute_calibrated_probabilities
+    /// [with_calibration_temperature]: crate::LanguageDetectorBuilder::with_calibration_temperature
+    pub fn fit_calibration_temperature<T: AsRef<str> + Sync>(
+        &self,
+        samples: &[(Language, T)],
+    ) -> f64 {
+        let log_likelihoods = samples
+            .par_iter()
+            .map(|(language, text)| {
+                (
+                    self.compute_normalized_log_likelihoods(text.as_ref().to_string()),
+                    language.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        fit_temperature(&log_likelihoods)
     }
 
-    fn compute_sum_of_ngram_probabilities(
+    /// Computes how well the given input text fits the language model of the given language.
+    ///
+    /// The goodness of fit is the log-likelihood of the text in the language per character of
+    /// the text. The log-likelihood is the sum of the log-probabilities of the text's ngrams of
+    /// all lengths the text is scored with, smoothed like for detecting the language. Ngrams
+    /// the detector ignores because not even their unigrams are known to the language are
+    /// assumed to have a probability of 1e-7 instead. In contrast to the confidence values, it
+    /// is an **absolute** metric: Texts written in the language get higher values than texts
+    /// written in other languages. As each distinct ngram is scored once, the values depend on
+    /// the length of the text, so only compare texts of the same [TextLength].
+    ///
+    /// `None` is returned if the text contains no letters, if it is shorter than the shortest
+    /// ngrams or if the detector has not been built from the given language.
+    pub fn compute_goodness_of_fit<T: Into<String>>(
         &self,
+        text: T,
         language: &Language,
-        ngrams: &HashSet<Ngram>,
-    ) -> f64 {
-        let mut sum = 0.0;
-        for ngram in ngrams.iter() {
-            for elem in ngram.range_of_lower_order_ngrams() {
-                let probability = self.look_up_ngram_probability(language, &elem);
+    ) -> Option<f64> {
+        if !self.languages.contains(language) {
+            return None;
+        }
 
-                if probability > 0.0 {
-                    sum += probability.ln();
-                    break;
-                }
-            }
+        let cleaned_up_text = self.clean_up_input_text(text.into());
+
+        if cleaned_up_text.is_empty() || NO_LETTER.is_match(&cleaned_up_text) {
+            return None;
         }
-        sum
-    }
 
-    fn look_up_ngram_probability(&self, language: &Language, ngram: &Ngram) -> f64 {
-        let ngram_length = ngram.value.chars().count();
-        let language_models = match ngram_length {
-            5 => self.fivegram_language_models,
-            4 => self.quadrigram_language_models,
-            3 => self.trigram_language_models,
-            2 => self.bigram_language_models,
-            1 => self.unigram_language_models,
-            0 => panic!("zerogram detected"),
-            _ => panic!(
-                "unsupported ngram length detected: {}",
-                ngram.value.chars().count()
-            ),
-        };
+        let test_data_models = self.create_test_data_models(&cleaned_up_text);
 
-        self.load_language_models(language_models, language, ngram_length);
+        if test_data_models.is_empty() {
+            return None;
+        }
 
-        language_models
-            .read()
-            .unwrap()
-            .get(language)
-            .unwrap()
-            .get_relative_frequency(ngram)
+        let text_size = TextSize::from_counts(
+            self.split_text_into_words(&cleaned_up_text).len(),
+            cleaned_up_text.chars().count(),
+            self.ngram_lengths.long_text_threshold,
+        );
+
+        let _detection = self.model_store.begin_detection();
+
+        Some(self.compute_goodness_of_fit_of_test_data_models(
+            language,
+            &test_data_models,
+            text_size,
+        ))
     }
 
-    fn count_unigrams(
+    /// Fits the rejection thresholds used to detect texts written in unknown languages on the
+    /// given samples, each consisting of the expected language and a text written in it.
+    ///
+    /// The samples are grouped by language and [TextLength]. For each group of `n` samples, the
+    /// threshold is the goodness of fit of the sample at index
+    /// `min(floor(n * false_rejection_rate), n - 1)` in ascending order. As only texts fitting
+    /// worse than the threshold are rejected, at most the given fraction of the samples, such as
+    /// 0.01 for one percent, would be rejected, and never all of them. A rate of 0.0 rejects none
+    /// of the samples, rates outside of [0.0, 1.0] are clamped to this range. Only samples which the
+    /// rule-based engine does not decide on are taken into account, as only those are checked
+    /// against the thresholds. Languages and lengths without such samples get no threshold. The
+    /// returned thresholds are sorted by language and length and can be passed to
+    /// [with_rejection_thresholds] when building a new detector. The `calibration` example shows
+    /// how to fit them on the test data bundled with the language models.
+    ///
+    /// [with_rejection_thresholds]: crate::LanguageDetectorBuilder::with_rejection_thresholds
+    pub fn fit_rejection_thresholds<T: AsRef<str> + Sync>(
         &self,
-        unigram_model: &TestDataLanguageModel,
-        filtered_languages: &HashSet<Language>,
-    ) -> HashMap<Language, u32> {
-        let mut unigram_counts = HashMap::new();
-        for language in filtered_languages.iter() {
-            for unigram in unigram_model.ngrams.iter() {
-                if self.look_up_ngram_probability(language, unigram) > 0.0 {
-                    self.increment_counter(&mut unigram_counts, language.clone());
+        samples: &[(Language, T)],
+        false_rejection_rate: f64,
+    ) -> Vec<(Language, TextLength, f64)> {
+        let _detection = self.model_store.begin_detection();
+
+        let scores = samples
+            .par_iter()
+            .filter(|(language, _)| self.languages.contains(language))
+            .filter_map(|(language, text)| {
+                match self.prepare_text(text.as_ref().to_string(), &self.languages) {
+                    PreparedText::Scorable(test_data_models, _, text_size) => Some((
+                        (language.clone(), text_size.length),
+                        self.compute_goodness_of_fit_of_test_data_models(
+                            language,
+                            &test_data_models,
+                            text_size,
+                        ),
+                    )),
+                    _ => None,
                 }
+            })
+            .collect::<Vec<_>>();
+
+        scores
+            .into_iter()
+            .into_group_map()
+            .into_iter()
+            .filter_map(|((language, text_length), scores)| {
+                fit_threshold(scores, false_rejection_rate)
+                    .map(|threshold| (language, text_length, threshold))
+            })
+            .sorted_by(
+                |(first_language, first_length, _), (second_language, second_length, _)| {
+                    first_language
+                        .cmp(second_language)
+                        .then_with(|| first_length.cmp(second_length))
+                },
+            )
+            .collect()
+    }
+
+    /// Computes the summed up ngram probabilities of each language, normalized by the number
+    /// of ngram lengths if they are normalized by the unigram counts already, or by the
+    /// number of trigrams for long texts. No prior probabilities are added.
+    fn compute_normalized_log_likelihoods(&self, text: String) -> HashMap<Language, f64> {
+        match self.prepare_text(text, &self.languages) {
+            PreparedText::Decided(language) => hashmap!(language => 0.0),
+            PreparedText::Undetermined(_) => hashmap!(),
+            PreparedText::Scorable(test_data_models, filtered_languages, _) => {
+                let _detection = self.model_store.begin_detection();
+
+                let normalizer = if test_data_models[0].0 == 1 {
+                    test_data_models.len()
+                } else {
+                    test_data_models
+                        .iter()
+                        .map(|(_, test_data_model)| test_data_model.ngrams.len())
+                        .sum::<usize>()
+                        .max(1)
+                };
+
+                self.sum_up_probabilities_of_test_data_models(
+                    &test_data_models,
+                    filtered_languages,
+                    None,
+                    true,
+                    &|language: &Language, ngram: &Ngram| {
+                        self.look_up_smoothed_ngram_probability(language, ngram)
+                    },
+                )
+                .into_iter()
+                .map(|(language, sum)| (language, sum / normalizer as f64))
+                .collect()
             }
         }
-        unigram_counts
     }
 
-    fn sum_up_probabilities(
+    /// Detects the languages of all given input texts.
+    ///
+    /// This is the batch counterpart of [detect_language_of]. For each text, the detected
+    /// language is returned at the same index, or `None` if the language cannot be reliably
+    /// detected. The texts are processed in parallel and each distinct ngram is looked up
+    /// only once per language for the whole batch which makes this method considerably
+    /// faster than calling [detect_language_of] in a loop.
+    ///
+    /// [detect_language_of]: LanguageDetector::detect_language_of
+    pub fn detect_languages_of<T: AsRef<str> + Sync>(&self, texts: &[T]) -> Vec<Option<Language>> {
+        self.detect_languages_with_confidence_values_of(texts)
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect()
+    }
+
+    /// Detects the languages of all given input texts together with the confidence values
+    /// they are derived from.
+    ///
+    /// This combines [detect_languages_of] and [compute_language_confidence_values_of_batch]
+    /// but scores each text only once. For each text, the detected language or `None` is
+    /// returned at the same index together with its confidence values.
+    ///
+    /// [detect_languages_of]: LanguageDetector::detect_languages_of
+    /// [compute_language_confidence_values_of_batch]: LanguageDetector::compute_language_confidence_values_of_batch
+    #[allow(clippy::type_complexity)]
+    pub fn detect_languages_with_confidence_values_of<T: AsRef<str> + Sync>(
         &self,
-        probabilities: Vec<&HashMap<Language, f64>>,
-        unigram_counts: &Option<HashMap<Language, u32>>,
-        filtered_languages: HashSet<Language>,
-    ) -> HashMap<Language, f64> {
-        let mut summed_up_probabilities = hashmap!();
-        for language in filtered_languages.iter() {
-            let mut sum = probabilities
-                .iter()
-                .map(|it| match it.get(language) {
-                    Some(probability) => *probability,
-                    None => 0.0,
-                })
-                .sum();
+        texts: &[T],
+    ) -> Vec<(Option<Language>, Vec<(Language, f64)>)> {
+        let prepared_texts = self.prepare_texts(texts);
 
-            if let Some(counts) = unigram_counts {
-                if counts.contains_key(language) {
-                    sum /= *counts.get(language).unwrap() as f64;
-                }
-            }
+        self.compute_confidence_values_of_prepared_texts(&prepared_texts)
+            .into_par_iter()
+            .zip(prepared_texts.par_iter())
+            .map(|(confidence_values, prepared_text)| {
+                let language = self
+                    .detect_language_from_confidence_values(&confidence_values)
+                    .filter(|language| match prepared_text {
+                        PreparedText::Scorable(test_data_models, _, text_size) => {
+                            !self.is_rejected(language, *text_size, test_data_models)
+                        }
+                        _ => true,
+                    });
+                (language, confidence_values)
+            })
+            .collect()
+    }
 
-            if sum != 0.0 {
-                summed_up_probabilities.insert(language.clone(), sum);
-            }
-        }
+    /// Computes confidence values for each language considered possible for all given
+    /// input texts.
+    ///
+    /// This is the batch counterpart of [compute_language_confidence_values]. For each text,
+    /// the confidence values are returned at the same index. The texts are processed in
+    /// parallel and each distinct ngram is looked up only once per language for the whole
+    /// batch.
+    ///
+    /// [compute_language_confidence_values]: LanguageDetector::compute_language_confidence_values
+    pub fn compute_language_confidence_values_of_batch<T: AsRef<str> + Sync>(
+        &self,
+        texts: &[T],
+    ) -> Vec<Vec<(Language, f64)>> {
+        let prepared_texts = self.prepare_texts(texts);
+        self.compute_confidence_values_of_prepared_texts(&prepared_texts)
+    }
 
-        summed_up_probabilities
+    fn prepare_texts<T: AsRef<str> + Sync>(&self, texts: &[T]) -> Vec<PreparedText> {
+        texts
+            .par_iter()
+            .map(|text| self.prepare_text(text.as_ref().to_string(), &self.languages))
+            .collect()
     }
 
-    fn load_language_models(
+    fn compute_confidence_values_of_prepared_texts(
         &self,
-        language_models: StaticLanguageModelMap,
-        language: &Language,
-        ngram_length: usize,
-    ) {
-        let models = language_models.read().unwrap();
-        if !models.contains_key(language) {
-            std::mem::drop(models);
-            let mut models = language_models.write().unwrap();
-            let json = load_json(language.clone(), ngram_length).unwrap();
-            models.insert(
-                language.clone(),
-                Box::new(TrainingDataLanguageModel::from_json(&json)),
-            );
-        }
+        prepared_texts: &[PreparedText],
+    ) -> Vec<Vec<(Language, f64)>> {
+        let _detection = self.model_store.begin_detection();
+
+        let ngram_probabilities = self.look_up_distinct_ngram_probabilities(prepared_texts);
+        let lookup = |language: &Language, ngram: &Ngram| {
+            ngram_probabilities
+                .get(language)
+                .and_then(|probabilities| probabilities.get(ngram))
+                .copied()
+                .unwrap_or(0.0)
+        };
+
+        prepared_texts
+            .par_iter()
+            .map(|prepared_text| match prepared_text {
+                PreparedText::Decided(language) => vec![(language.clone(), 1.0)],
+                PreparedText::Undetermined(_) => vec![],
+                PreparedText::Scorable(test_data_models, filtered_languages, _) => self
+                    .compute_confidence_values_of_test_data_models(
+                        test_data_models,
+                        filtered_languages.clone(),
+                        &hashmap!(),
+                        false,
+                        &lookup,
+                    ),
+            })
+            .collect()
     }
 
-    fn increment_counter<T: Eq + Hash>(&self, counts: &mut HashMap<T, u32>, key: T) {
-        let counter = counts.entry(key).or_insert(0);
-        *counter += 1;
+    /// Explains how the language of the given input text is detected.
+    ///
+    /// The returned [DetectionExplanation] traces the steps which [detect_language_outcome_of]
+    /// takes: the cleaned text and its words, the alphabets and characters found in them,
+    /// the decision of the rule-based engine and the languages remaining possible afterwards.
+    /// If the rules do not decide on a language, the evidence of the ngram models is listed
+    /// for every remaining language, broken down by ngram length and including the ngrams
+    /// with the highest probabilities and the ngrams unknown to the language. The explanation
+    /// ends with the same [DetectionOutcome] as returned by [detect_language_outcome_of].
+    ///
+    /// Explaining a text is slower than detecting its language,
+    /// so this method is meant for debugging wrong detections.
+    ///
+    /// [detect_language_outcome_of]: LanguageDetector::detect_language_outcome_of
+    pub fn explain<T: Into<String>>(&self, text: T) -> DetectionExplanation {
+        self.explain_with_options(text, &DetectionOptions::new())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::language::Language::*;
-    use crate::model::MockLanguageModel;
-    use crate::LanguageDetectorBuilder;
-    use float_cmp::approx_eq;
-    use once_cell::sync::OnceCell;
-    use rstest::*;
+    /// Explains how the language of the given input text is detected with the settings
+    /// of the detector overridden by the given [DetectionOptions].
+    ///
+    /// See [explain] for details. The explanation ends with the same [DetectionOutcome]
+    /// as returned by [detect_language_outcome_with_options].
+    ///
+    /// [explain]: LanguageDetector::explain
+    /// [detect_language_outcome_with_options]: LanguageDetector::detect_language_outcome_with_options
+    pub fn explain_with_options<T: Into<String>>(
+        &self,
+        text: T,
+        options: &DetectionOptions,
+    ) -> DetectionExplanation {
+        let languages = options.resolve_languages(&self.languages);
+        let mut explanation = DetectionExplanation::new();
+
+        let outcome = if languages.is_empty() {
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::NoLanguages,
+            }
+        } else {
+            match self.prepare_and_explain_text(text.into(), &languages, Some(&mut explanation)) {
+                PreparedText::Decided(language) => {
+                    explanation.confidence_values = vec![(language.clone(), 1.0)];
+                    DetectionOutcome::Detected {
+                        language,
+                        source: DecisionSource::Rules,
+                    }
+                }
+                PreparedText::Undetermined(reason) => DetectionOutcome::Undetermined { reason },
+                PreparedText::Scorable(test_data_models, filtered_languages, text_size) => {
+                    let _detection = self.model_store.begin_detection();
+                    let lookup = |language: &Language, ngram: &Ngram| {
+                        self.look_up_smoothed_ngram_probability(language, ngram)
+                    };
+                    let summed_up_probabilities = self.sum_up_probabilities_of_test_data_models(
+                        &test_data_models,
+                        filtered_languages,
+                        Some(options.hints()),
+                        true,
+                        &lookup,
+                    );
 
-    // ##############################
-    // MOCKS
-    // ##############################
+                    explanation.language_evidence = explanation
+                        .remaining_languages
+                        .iter()
+                        .map(|language| {
+                            self.collect_language_evidence(
+                                language,
+                                &test_data_models,
+                                text_size,
+                                *summed_up_probabilities.get(language).unwrap_or(&0.0),
+                            )
+                        })
+                        .collect();
+                    explanation.confidence_values = self
+                        .compute_confidence_values_of_summed_up_probabilities(
+                            summed_up_probabilities,
+                        );
 
-    fn create_training_model_mock(data: HashMap<&'static str, f64>) -> BoxedLanguageModel {
-        let mut mock = MockLanguageModel::new();
-        for (ngram, probability) in data {
-            mock.expect_get_relative_frequency()
-                .withf(move |n| n == &Ngram::new(ngram))
-                .return_const(probability);
-        }
-        Box::new(mock)
+                    self.decide_outcome_of_confidence_values(
+                        &explanation.confidence_values,
+                        &test_data_models,
+                        text_size,
+                        options,
+                    )
+                }
+            }
+        };
+
+        explanation.language = outcome.language();
+        explanation.outcome = outcome;
+        explanation
     }
 
-    // ##############################
-    // LANGUAGE MODELS FOR ENGLISH
-    // ##############################
+    /// Collects the log-probabilities of the given ngrams in the given language,
+    /// together with the ngrams contributing most and the ngrams unknown to the language.
+    fn collect_language_evidence(
+        &self,
+        language: &Language,
+        test_data_models: &[(usize, TestDataLanguageModel)],
+        text_size: TextSize,
+        total_log_probability: f64,
+    ) -> LanguageEvidence {
+        let mut log_probabilities = vec![];
+        let mut unigram_count = 0;
+        let mut contributions = vec![];
+        let mut missing_ngrams = vec![];
+
+        for (ngram_length, test_data_model) in test_data_models.iter() {
+            let mut sum = 0.0;
+            for ngram in test_data_model.ngrams.iter() {
+                let probability = self.look_up_smoothed_ngram_probability(language, ngram);
+                if probability > 0.0 {
+                    sum += probability.ln();
+                    contributions.push((ngram.value.clone(), probability.ln()));
+                    if *ngram_length == 1 {
+                        unigram_count += 1;
+                    }
+                }
+                if self.look_up_ngram_probability(language, ngram) == 0.0 {
+                    missing_ngrams.push(ngram.value.clone());
+                }
+            }
+            log_probabilities.push((*ngram_length, sum));
+        }
 
-    #[fixture]
-    fn unigram_language_model_for_english() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "a" => 0.01,
-            "l" => 0.02,
-            "t" => 0.03,
-            "e" => 0.04,
-            "r" => 0.05,
-            // unknown unigrams
-            "w" => 0.0
-        ))
+        let missing_ngram_count = missing_ngrams.len();
+
+        LanguageEvidence {
+            language: language.clone(),
+            log_probabilities,
+            unigram_count,
+            total_log_probability,
+            goodness_of_fit: self.compute_goodness_of_fit_of_test_data_models(
+                language,
+                test_data_models,
+                text_size,
+            ),
+            rejection_threshold: self.rejection_threshold(language, text_size.length),
+            top_ngrams: contributions
+                .into_iter()
+                .sorted_by(|(first_ngram, first_value), (second_ngram, second_value)| {
+                    second_value
+                        .partial_cmp(first_value)
+                        .unwrap()
+                        .then_with(|| first_ngram.cmp(second_ngram))
+                })
+                .take(MAXIMUM_LISTED_NGRAMS)
+                .collect(),
+            missing_ngrams: missing_ngrams
+                .into_iter()
+                .sorted_by(|first, second| {
+                    second
+                        .chars()
+                        .count()
+                        .cmp(&first.chars().count())
+                        .then_with(|| first.cmp(second))
+                })
+                .take(MAXIMUM_LISTED_NGRAMS)
+                .collect(),
+            missing_ngram_count,
+        }
     }
 
-    #[fixture]
-    fn bigram_language_model_for_english() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "al" => 0.11,
-            "lt" => 0.12,
-            "te" => 0.13,
-            "er" => 0.14,
-            // unknown bigrams
-            "aq" => 0.0,
-            "wx" => 0.0
-        ))
+    /// Starts a [DetectionSession] to detect the language of text arriving in pieces.
+    ///
+    /// Instead of detecting the language of the accumulated text from scratch whenever
+    /// a new piece arrives, the session scores each piece only once.
+    pub fn start_session(&self) -> DetectionSession<'_> {
+        DetectionSession::new(self)
     }
 
-    #[fixture]
-    fn trigram_language_model_for_english() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "alt" => 0.19,
-            "lte" => 0.2,
-            "ter" => 0.21,
-            // unknown trigrams
-            "aqu" => 0.0,
-            "tez" => 0.0,
-            "wxy" => 0.0
-        ))
+    /// Detects the language of the text read from the given reader.
+    /// If the language cannot be reliably detected, `None` is returned.
+    ///
+    /// This is the streaming counterpart of [detect_language_of] for large documents.
+    /// See [compute_language_confidence_values_of_reader] for details. The language is
+    /// rejected by the thresholds set with [with_rejection_thresholds] like for texts in memory.
+    /// If reading stops early, only the text read so far is checked against them.
+    ///
+    /// [detect_language_of]: LanguageDetector::detect_language_of
+    /// [compute_language_confidence_values_of_reader]: LanguageDetector::compute_language_confidence_values_of_reader
+    /// [with_rejection_thresholds]: crate::LanguageDetectorBuilder::with_rejection_thresholds
+    pub fn detect_language_of_reader<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> Result<Option<Language>, LinguaError> {
+        self.detect_language_with_confidence_values_of_reader(reader)
+            .map(|(language, _)| language)
     }
 
-    #[fixture]
-    fn quadrigram_language_model_for_english() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "alte" => 0.25,
-            "lter" => 0.26,
-            // unknown quadrigrams
-            "aqua" => 0.0,
-            "wxyz" => 0.0
-        ))
+    /// Detects the language of the text read from the given reader together with the
+    /// confidence values it is derived from.
+    ///
+    /// This combines [detect_language_of_reader] and
+    /// [compute_language_confidence_values_of_reader] but reads and scores the text only once.
+    ///
+    /// [detect_language_of_reader]: LanguageDetector::detect_language_of_reader
+    /// [compute_language_confidence_values_of_reader]: LanguageDetector::compute_language_confidence_values_of_reader
+    #[allow(clippy::type_complexity)]
+    pub fn detect_language_with_confidence_values_of_reader<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> Result<(Option<Language>, Vec<(Language, f64)>), LinguaError> {
+        Ok(self
+            .feed_session_from_reader(reader)?
+            .current_language_with_confidence_values())
     }
 
-    #[fixture]
-    fn fivegram_language_model_for_english() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "alter" => 0.29,
-            // unknown fivegrams
-            "aquas" => 0.0
-        ))
+    /// Computes confidence values for each language considered possible for the text read
+    /// from the given reader.
+    ///
+    /// This is the streaming counterpart of [compute_language_confidence_values] for large
+    /// documents. Instead of holding the entire text in memory, the input is processed piece
+    /// by piece while the ngram probabilities are summed up incrementally. If an early stopping
+    /// margin is set with [with_early_stopping_margin], reading stops as soon as the most likely
+    /// language leads the runner-up by this margin.
+    ///
+    /// The margin is checked each time the content of the reader's buffer has been processed,
+    /// once the complete words read so far contain at least as many characters as the long-text
+    /// threshold set with [with_long_text_threshold]. Reading stops with the buffer after which
+    /// the margin is reached, so the buffer capacity of the reader determines how much text is
+    /// read beyond this point. The memory needed grows with the number of distinct ngrams read,
+    /// as described for [DetectionSession].
+    ///
+    /// Returns [LinguaError::Io] if the reader fails or the input is not valid UTF-8.
+    ///
+    /// [compute_language_confidence_values]: LanguageDetector::compute_language_confidence_values
+    /// [with_early_stopping_margin]: crate::LanguageDetectorBuilder::with_early_stopping_margin
+    /// [with_long_text_threshold]: crate::LanguageDetectorBuilder::with_long_text_threshold
+    pub fn compute_language_confidence_values_of_reader<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<(Language, f64)>, LinguaError> {
+        Ok(self
+            .feed_session_from_reader(reader)?
+            .current_confidence_values())
     }
 
-    // ##############################
-    // LANGUAGE MODELS FOR GERMAN
-    // ##############################
+    /// Feeds the text read from the given reader to a new [DetectionSession]. If an early
+    /// stopping margin is set, it is checked after each buffer once the long-text threshold
+    /// is reached, and reading stops as soon as the margin is reached.
+    fn feed_session_from_reader<R: BufRead>(
+        &self,
+        mut reader: R,
+    ) -> Result<DetectionSession<'_>, LinguaError> {
+        let mut session = DetectionSession::new(self);
+        let mut undecoded_bytes = vec![];
+
+        loop {
+            let buffer = match reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
 
-    #[fixture]
-    fn unigram_language_model_for_german() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "a" => 0.06,
-            "l" => 0.07,
-            "t" => 0.08,
-            "e" => 0.09,
-            "r" => 0.1,
-            // unknown unigrams
-            "w" => 0.0
-        ))
-    }
+            if buffer.is_empty() {
+                break;
+            }
 
-    #[fixture]
-    fn bigram_language_model_for_german() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "al" => 0.15,
-            "lt" => 0.16,
-            "te" => 0.17,
-            "er" => 0.18,
-            // unknown bigrams
-            "wx" => 0.0
-        ))
-    }
+            undecoded_bytes.extend_from_slice(buffer);
+            let buffer_length = buffer.len();
+            reader.consume(buffer_length);
 
-    #[fixture]
-    fn trigram_language_model_for_german() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "alt" => 0.22,
-            "lte" => 0.23,
-            "ter" => 0.24,
-            // unknown trigrams
-            "wxy" => 0.0
-        ))
-    }
+            let valid_length = match str::from_utf8(&undecoded_bytes) {
+                Ok(text) => text.len(),
+                Err(error) if error.error_len().is_none() => error.valid_up_to(),
+                Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error).into()),
+            };
 
-    #[fixture]
-    fn quadrigram_language_model_for_german() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!(
-            "alte" => 0.27,
-            "lter" => 0.28,
-            // unknown quadrigrams
-            "wxyz" => 0.0
-        ))
-    }
+            session.feed(str::from_utf8(&undecoded_bytes[..valid_length]).unwrap());
+            undecoded_bytes.drain(..valid_length);
 
-    #[fixture]
-    fn fivegram_language_model_for_german() -> BoxedLanguageModel {
-        create_training_model_mock(hashmap!("alter" => 0.3))
-    }
+            if let Some(margin) = self.early_stopping_margin {
+                if session.character_count() >= self.ngram_lengths.long_text_threshold {
+                    let confidence_values = session.current_confidence_values();
+                    if self.is_early_stopping_margin_reached(&confidence_values, margin) {
+                        return Ok(session);
+                    }
+                }
+            }
+        }
 
-    // ##############################
-    // NGRAM MODELS
-    // ##############################
+        if let Err(error) = str::from_utf8(&undecoded_bytes) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error).into());
+        }
 
-    #[fixture]
-    fn unigram_language_models(
-        unigram_language_model_for_english: BoxedLanguageModel,
-        unigram_language_model_for_german: BoxedLanguageModel,
-    ) -> StaticLanguageModelMap {
-        static UNIGRAM_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        UNIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => unigram_language_model_for_english,
-                German => unigram_language_model_for_german
-            ))
-        })
+        Ok(session)
     }
 
-    #[fixture]
-    fn bigram_language_models(
-        bigram_language_model_for_english: BoxedLanguageModel,
-        bigram_language_model_for_german: BoxedLanguageModel,
-    ) -> StaticLanguageModelMap {
-        static BIGRAM_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        BIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => bigram_language_model_for_english,
-                German => bigram_language_model_for_german
-            ))
-        })
+    fn is_early_stopping_margin_reached(
+        &self,
+        confidence_values: &[(Language, f64)],
+        margin: f64,
+    ) -> bool {
+        match confidence_values {
+            [] => false,
+            [_] => true,
+            [(_, first_value), (_, second_value), ..] => first_value - second_value >= margin,
+        }
     }
 
-    #[fixture]
-    fn trigram_language_models(
-        trigram_language_model_for_english: BoxedLanguageModel,
-        trigram_language_model_for_german: BoxedLanguageModel,
-    ) -> StaticLanguageModelMap {
-        static TRIGRAM_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        TRIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => trigram_language_model_for_english,
-                German => trigram_language_model_for_german
-            ))
-        })
-    }
+    /// Looks up the smoothed probabilities of all distinct ngrams occurring in the given
+    /// texts for the languages still considered possible for the respective text. Every
+    /// distinct ngram is looked up only once per language.
+    fn look_up_distinct_ngram_probabilities<'a>(
+        &self,
+        prepared_texts: &'a [PreparedText],
+    ) -> HashMap<Language, HashMap<&'a Ngram, f64>> {
+        let languages = prepared_texts
+            .iter()
+            .filter_map(|prepared_text| match prepared_text {
+                PreparedText::Scorable(_, filtered_languages, _) => Some(filtered_languages),
+                _ => None,
+            })
+            .flatten()
+            .cloned()
+            .collect::<HashSet<_>>();
 
-    #[fixture]
-    fn quadrigram_language_models(
-        quadrigram_language_model_for_english: BoxedLanguageModel,
-        quadrigram_language_model_for_german: BoxedLanguageModel,
-    ) -> StaticLanguageModelMap {
-        static QUADRIGRAM_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        QUADRIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => quadrigram_language_model_for_english,
-                German => quadrigram_language_model_for_german
-            ))
-        })
+        languages
+            .into_par_iter()
+            .map(|language| {
+                let mut probabilities = HashMap::new();
+                for prepared_text in prepared_texts.iter() {
+                    if let PreparedText::Scorable(test_data_models, filtered_languages, _) =
+                        prepared_text
+                    {
+                        if !filtered_languages.contains(&language) {
+                            continue;
+                        }
+                        for (_, test_data_model) in test_data_models.iter() {
+                            for ngram in test_data_model.ngrams.iter() {
+                                probabilities.entry(ngram).or_insert_with(|| {
+                                    self.look_up_smoothed_ngram_probability(&language, ngram)
+                                });
+                            }
+                        }
+                    }
+                }
+                (language, probabilities)
+            })
+            .collect()
     }
 
-    #[fixture]
-    fn fivegram_language_models(
-        fivegram_language_model_for_english: BoxedLanguageModel,
-        fivegram_language_model_for_german: BoxedLanguageModel,
-    ) -> StaticLanguageModelMap {
-        static FIVEGRAM_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        FIVEGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => fivegram_language_model_for_english,
-                German => fivegram_language_model_for_german
-            ))
-        })
+    fn compute_language_confidence_values_for_languages(
+        &self,
+        text: String,
+        languages: &HashSet<Language>,
+        hints: &HashMap<Language, f64>,
+    ) -> Vec<(Language, f64)> {
+        match self.prepare_text(text, languages) {
+            PreparedText::Decided(language) => vec![(language, 1.0)],
+            PreparedText::Undetermined(_) => vec![],
+            PreparedText::Scorable(test_data_models, filtered_languages, _) => {
+                let _detection = self.model_store.begin_detection();
+                self.compute_confidence_values_of_test_data_models(
+                    &test_data_models,
+                    filtered_languages,
+                    hints,
+                    true,
+                    &|language: &Language, ngram: &Ngram| {
+                        self.look_up_smoothed_ngram_probability(language, ngram)
+                    },
+                )
+            }
+        }
     }
 
-    // ##############################
-    // EMPTY NGRAM MODELS
-    // ##############################
-
-    #[fixture]
-    fn empty_language_models() -> StaticLanguageModelMap {
-        static EMPTY_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, BoxedLanguageModel>>> =
-            OnceCell::new();
-        EMPTY_MODELS_FIXTURE.get_or_init(|| RwLock::new(hashmap!()))
+    /// Applies the rule-based engine to the given text. If the rules cannot decide on
+    /// a single language, the ngrams of the text are extracted for the statistical models.
+    fn prepare_text(&self, text: String, languages: &HashSet<Language>) -> PreparedText {
+        self.prepare_and_explain_text(text, languages, None)
     }
 
-    // ##############################
-    // TEST DATA MODELS
-    // ##############################
+    /// Prepares the given text like [prepare_text] and records every step in the given
+    /// explanation, if any.
+    ///
+    /// [prepare_text]: LanguageDetector::prepare_text
+    fn prepare_and_explain_text(
+        &self,
+        text: String,
+        languages: &HashSet<Language>,
+        mut explanation: Option<&mut DetectionExplanation>,
+    ) -> PreparedText {
+        if text.trim().is_empty() {
+            return PreparedText::Undetermined(UndeterminedReason::EmptyText);
+        }
 
-    #[fixture(strs=hashset!())]
-    fn test_data_model(strs: HashSet<&'static str>) -> TestDataLanguageModel {
-        let ngrams = strs
-            .iter()
-            .map(|&it| Ngram::new(it))
-            .collect::<HashSet<_>>();
+        let cleaned_up_text = self.clean_up_input_text(text);
 
-        TestDataLanguageModel { ngrams }
-    }
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.cleaned_text = cleaned_up_text.clone();
+        }
 
-    // ##############################
-    // DETECTORS
-    // ##############################
+        if cleaned_up_text.is_empty() || NO_LETTER.is_match(&cleaned_up_text) {
+            return PreparedText::Undetermined(UndeterminedReason::NoLetters);
+        }
 
-    #[fixture]
-    fn detector_for_english_and_german(
-        unigram_language_models: StaticLanguageModelMap,
-        bigram_language_models: StaticLanguageModelMap,
-        trigram_language_models: StaticLanguageModelMap,
-        quadrigram_language_models: StaticLanguageModelMap,
-        fivegram_language_models: StaticLanguageModelMap,
-    ) -> LanguageDetector {
-        LanguageDetector {
-            languages: hashset!(English, German),
-            minimum_relative_distance: 0.0,
-            languages_with_unique_characters: hashset!(),
-            one_language_alphabets: hashmap!(),
-            unigram_language_models,
-            bigram_language_models,
-            trigram_language_models,
-            quadrigram_language_models,
-            fivegram_language_models,
+        let words = self.split_text_into_words(&cleaned_up_text);
+        let word_counts = self.count_words(&words, languages);
+        let language_detected_by_rules = self.detect_language_with_rules(&word_counts);
+
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.words = words;
+            explanation.alphabets = sort_counts(word_counts.word_alphabets.clone());
+            explanation.word_languages = sort_counts(word_counts.word_languages.clone());
+            explanation.character_languages = sort_counts(word_counts.character_languages.clone());
         }
-    }
 
-    #[fixture]
-    fn detector_for_all_languages(
-        empty_language_models: StaticLanguageModelMap,
-    ) -> LanguageDetector {
-        let languages = Language::all();
-        let languages_with_unique_characters = languages
-            .iter()
-            .filter(|it| it.unique_characters().is_some())
-            .cloned()
-            .collect();
+        if let Some(language) = language_detected_by_rules {
+            if let Some(explanation) = explanation.as_mut() {
+                explanation.rule = RuleDecision::WordRules {
+                    language: language.clone(),
+                };
+                explanation.remaining_languages = vec![language.clone()];
+            }
+            return PreparedText::Decided(language);
+        }
 
-        let one_language_alphabets = Alphabet::all_supporting_single_language()
-            .into_iter()
-            .filter(|(_, language)| languages.contains(language))
-            .collect();
+        let filtered_languages = self.filter_languages_by_rules(&word_counts, languages);
 
-        LanguageDetector {
-            languages,
-            minimum_relative_distance: 0.0,
-            languages_with_unique_characters,
-            one_language_alphabets,
-            unigram_language_models: empty_language_models,
-            bigram_language_models: empty_language_models,
-            trigram_language_models: empty_language_models,
-            quadrigram_language_models: empty_language_models,
-            fivegram_language_models: empty_language_models,
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.rule = RuleDecision::Undecided;
+            explanation.remaining_languages = filtered_languages.iter().cloned().sorted().collect();
         }
-    }
 
-    // ##############################
-    // TESTS
-    // ##############################
+        if filtered_languages.is_empty() {
+            return PreparedText::Undetermined(UndeterminedReason::UnsupportedScript);
+        }
 
-    #[rstest]
-    fn assert_text_is_cleaned_up_properly(detector_for_all_languages: LanguageDetector) {
-        let text = "Weltweit    gibt es ungefähr 6.000 Sprachen,
-        wobei laut Schätzungen zufolge ungefähr 90  Prozent davon
-        am Ende dieses Jahrhunderts verdrängt sein werden.";
+        if filtered_languages.len() == 1 {
+            let filtered_language = filtered_languages.into_iter().next().unwrap();
+            if let Some(explanation) = explanation.as_mut() {
+                explanation.rule = RuleDecision::SingleRemainingLanguage {
+                    language: filtered_language.clone(),
+                };
+            }
+            return PreparedText::Decided(filtered_language);
+        }
 
-        let expected_cleaned_text =
-            "weltweit gibt es ungefähr sprachen wobei laut schätzungen zufolge ungefähr \
-            prozent davon am ende dieses jahrhunderts verdrängt sein werden";
+        let test_data_models = self.create_test_data_models(&cleaned_up_text);
 
-        assert_eq!(
-            detector_for_all_languages.clean_up_input_text(text.to_string()),
-            expected_cleaned_text
+        if test_data_models.is_empty() {
+            return PreparedText::Undetermined(UndeterminedReason::TooShort);
+        }
+
+        let text_size = TextSize::from_counts(
+            word_counts.word_count,
+            cleaned_up_text.chars().count(),
+            self.ngram_lengths.long_text_threshold,
         );
+
+        PreparedText::Scorable(test_data_models, filtered_languages, text_size)
     }
 
-    #[rstest]
-    fn assert_text_is_split_into_words_correctly(detector_for_all_languages: LanguageDetector) {
-        assert_eq!(
-            detector_for_all_languages.split_text_into_words("this is a sentence"),
-            vec!["this", "is", "a", "sentence"]
+    /// Extracts the ngrams of the given cleaned up text for all ngram lengths
+    /// the text is scored with.
+    fn create_test_data_models(
+        &self,
+        cleaned_up_text: &str,
+    ) -> Vec<(usize, TestDataLanguageModel)> {
+        self.ngram_lengths
+            .for_character_count(cleaned_up_text.chars().count())
+            .map(|ngram_length| {
+                (
+                    ngram_length,
+                    TestDataLanguageModel::from(cleaned_up_text, ngram_length),
+                )
+            })
+            .collect()
+    }
+
+    /// Checks whether the given ngrams of a text of the given length fit the language model
+    /// of the given language worse than the rejection threshold of the language for this
+    /// length, if any.
+    pub(crate) fn is_rejected(
+        &self,
+        language: &Language,
+        text_size: TextSize,
+        test_data_models: &[(usize, TestDataLanguageModel)],
+    ) -> bool {
+        match self.rejection_threshold(language, text_size.length) {
+            Some(threshold) => {
+                self.compute_goodness_of_fit_of_test_data_models(
+                    language,
+                    test_data_models,
+                    text_size,
+                ) < threshold
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn rejection_threshold(
+        &self,
+        language: &Language,
+        text_length: TextLength,
+    ) -> Option<f64> {
+        self.rejection_thresholds
+            .get(&(language.clone(), text_length))
+            .copied()
+    }
+
+    fn compute_goodness_of_fit_of_test_data_models(
+        &self,
+        language: &Language,
+        test_data_models: &[(usize, TestDataLanguageModel)],
+        text_size: TextSize,
+    ) -> f64 {
+        compute_goodness_of_fit(test_data_models, text_size.character_count, |ngram| {
+            self.look_up_smoothed_ngram_probability(language, ngram)
+        })
+    }
+
+    fn compute_confidence_values_of_test_data_models<F>(
+        &self,
+        test_data_models: &[(usize, TestDataLanguageModel)],
+        filtered_languages: HashSet<Language>,
+        hints: &HashMap<Language, f64>,
+        is_parallel: bool,
+        lookup: &F,
+    ) -> Vec<(Language, f64)>
+    where
+        F: Fn(&Language, &Ngram) -> f64 + Sync,
+    {
+        let summed_up_probabilities = self.sum_up_probabilities_of_test_data_models(
+            test_data_models,
+            filtered_languages,
+            Some(hints),
+            is_parallel,
+            lookup,
+        );
+        self.compute_confidence_values_of_summed_up_probabilities(summed_up_probabilities)
+    }
+
+    /// Sums up the probabilities of the ngrams of a text, ordered by ascending ngram length.
+    /// `lookup` returns the probability of an ngram in a language, smoothed according to the
+    /// configured strategy if it is unknown. The ngram lengths are processed in parallel if `is_parallel`
+    /// is set. If `hints` are given, the log prior probabilities are added as well.
+    fn sum_up_probabilities_of_test_data_models<F>(
+        &self,
+        test_data_models: &[(usize, TestDataLanguageModel)],
+        filtered_languages: HashSet<Language>,
+        hints: Option<&HashMap<Language, f64>>,
+        is_parallel: bool,
+        lookup: &F,
+    ) -> HashMap<Language, f64>
+    where
+        F: Fn(&Language, &Ngram) -> f64 + Sync,
+    {
+        let look_up_language_models =
+            |(ngram_length, test_data_model): &(usize, TestDataLanguageModel)| {
+                self.look_up_language_models(
+                    test_data_model,
+                    *ngram_length,
+                    &filtered_languages,
+                    lookup,
+                )
+            };
+
+        #[allow(clippy::type_complexity)]
+        let all_probabilities_and_unigram_counts: Vec<(
+            HashMap<Language, f64>,
+            Option<HashMap<Language, u32>>,
+        )> = if is_parallel {
+            test_data_models
+                .par_iter()
+                .map(look_up_language_models)
+                .collect()
+        } else {
+            test_data_models
+                .iter()
+                .map(look_up_language_models)
+                .collect()
+        };
+
+        let all_probabilities = all_probabilities_and_unigram_counts
+            .iter()
+            .map(|(probabilities, _)| probabilities)
+            .collect::<Vec<_>>();
+
+        let unigram_counts = &all_probabilities_and_unigram_counts[0].1;
+
+        self.sum_up_probabilities(all_probabilities, unigram_counts, filtered_languages, hints)
+    }
+
+    /// Computes the confidence values from the summed up log-probabilities of the languages.
+    pub(crate) fn compute_confidence_values_of_summed_up_probabilities(
+        &self,
+        summed_up_probabilities: HashMap<Language, f64>,
+    ) -> Vec<(Language, f64)> {
+        if summed_up_probabilities.is_empty() {
+            return vec![];
+        }
+
+        let highest_probability = self.get_highest_probability(&summed_up_probabilities);
+
+        self.compute_confidence_values(summed_up_probabilities, highest_probability)
+    }
+
+    fn clean_up_input_text(&self, text: String) -> String {
+        let trimmed = text.trim().to_lowercase();
+        let without_punctuation = PUNCTUATION.replace_all(&trimmed, "");
+        let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
+        let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&without_numbers, " ");
+        normalized_whitespace.to_string()
+    }
+
+    fn split_text_into_words(&self, text: &str) -> Vec<String> {
+        let mut normalized_text_builder = vec![];
+        for chr in text.chars() {
+            normalized_text_builder.push(chr.to_string());
+            if self.is_logogram(chr) {
+                normalized_text_builder.push(' '.to_string());
+            }
+        }
+        let normalized_text: String = normalized_text_builder.join("");
+        if normalized_text.contains(' ') {
+            normalized_text
+                .split(' ')
+                .filter(|it| !it.is_empty())
+                .map(|it| it.to_string())
+                .collect_vec()
+        } else {
+            vec![normalized_text]
+        }
+    }
+
+    pub(crate) fn is_logogram(&self, chr: char) -> bool {
+        if chr.is_whitespace() {
+            false
+        } else {
+            LANGUAGES_SUPPORTING_LOGOGRAMS
+                .iter()
+                .flat_map(|it| it.alphabets())
+                .any(|it| it.matches(&chr.to_string()))
+        }
+    }
+
+    /// Counts the given words for the rule-based engine.
+    pub(crate) fn count_words(
+        &self,
+        words: &[String],
+        languages: &HashSet<Language>,
+    ) -> WordCounts {
+        let mut word_counts = WordCounts::default();
+        for word in words {
+            self.count_word(word, languages, &mut word_counts);
+        }
+        word_counts
+    }
+
+    /// Adds the given word to the counts which the rule-based engine decides on.
+    pub(crate) fn count_word(
+        &self,
+        word: &str,
+        languages: &HashSet<Language>,
+        word_counts: &mut WordCounts,
+    ) {
+        word_counts.word_count += 1;
+
+        let word_language = self.detect_language_of_word_with_rules(word, languages);
+        self.increment_counter(&mut word_counts.word_languages, word_language);
+
+        if let Some(alphabet) = Alphabet::iter().find(|alphabet| alphabet.matches(word)) {
+            self.increment_counter(&mut word_counts.word_alphabets, alphabet);
+        }
+
+        for (characters, character_languages) in CHARS_TO_LANGUAGES_MAPPING.iter() {
+            for character in characters.chars() {
+                if word.contains(character) {
+                    for language in character_languages.iter() {
+                        self.increment_counter(
+                            &mut word_counts.character_languages,
+                            language.clone(),
+                        );
+                    }
+                }
+            }
+        }
+
+        for language in self
+            .languages_with_unique_characters
+            .iter()
+            .filter(|it| matches!(it, Language::Custom(_)))
+        {
+            for character in language.unique_characters().unwrap().chars() {
+                if word.contains(character) {
+                    self.increment_counter(&mut word_counts.character_languages, language.clone());
+                }
+            }
+        }
+    }
+
+    fn detect_language_of_word_with_rules(
+        &self,
+        word: &str,
+        languages: &HashSet<Language>,
+    ) -> Option<Language> {
+        let mut word_language_counts = HashMap::<Language, u32>::new();
+
+        for character in word.chars() {
+            let mut is_match = false;
+            let mut buffer = [0; 4];
+            let char_str = character.encode_utf8(&mut buffer);
+
+            for (alphabet, language) in self
+                .one_language_alphabets
+                .iter()
+                .filter(|(_, language)| languages.contains(language))
+            {
+                if alphabet.matches(char_str) {
+                    self.increment_counter(&mut word_language_counts, language.clone());
+                    is_match = true;
+                    break;
+                }
+            }
+
+            if !is_match {
+                if cfg!(any(feature = "chinese", feature = "external-models"))
+                    && Alphabet::Han.matches(char_str)
+                {
+                    self.increment_counter(
+                        &mut word_language_counts,
+                        Language::from_str("Chinese").unwrap(),
+                    );
+                } else if cfg!(any(feature = "japanese", feature = "external-models"))
+                    && JAPANESE_CHARACTER_SET.is_match(char_str)
+                {
+                    self.increment_counter(
+                        &mut word_language_counts,
+                        Language::from_str("Japanese").unwrap(),
+                    );
+                } else if Alphabet::Latin.matches(char_str)
+                    || Alphabet::Cyrillic.matches(char_str)
+                    || Alphabet::Devanagari.matches(char_str)
+                {
+                    self.languages_with_unique_characters
+                        .iter()
+                        .filter(|it| languages.contains(it))
+                        .filter(|it| it.unique_characters().unwrap().contains(character))
+                        .for_each(|it| {
+                            self.increment_counter(&mut word_language_counts, it.clone())
+                        });
+                }
+            }
+        }
+
+        if word_language_counts.is_empty() {
+            None
+        } else if word_language_counts.len() == 1 {
+            let counted_languages = word_language_counts.keys().collect_vec();
+            let language = *counted_languages.first().unwrap();
+            if languages.contains(language) {
+                Some(language.clone())
+            } else {
+                None
+            }
+        } else if cfg!(any(feature = "chinese", feature = "external-models"))
+            && cfg!(any(feature = "japanese", feature = "external-models"))
+            && word_language_counts.contains_key(&Language::from_str("Chinese").unwrap())
+            && word_language_counts.contains_key(&Language::from_str("Japanese").unwrap())
+        {
+            Some(Language::from_str("Japanese").unwrap())
+        } else {
+            let sorted_word_language_counts = word_language_counts
+                .into_iter()
+                .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+                .collect_vec();
+            let (most_frequent_language, first_count) = &sorted_word_language_counts[0];
+            let (_, second_count) = &sorted_word_language_counts[1];
+
+            if first_count > second_count && languages.contains(most_frequent_language) {
+                Some(most_frequent_language.clone())
+            } else {
+                None
+            }
+        }
+    }
+
+    pub(crate) fn detect_language_with_rules(&self, word_counts: &WordCounts) -> Option<Language> {
+        let mut total_language_counts = word_counts.word_languages.clone();
+        let half_word_count = (word_counts.word_count as f64) * 0.5;
+
+        let unknown_language_count = *total_language_counts.get(&None).or(Some(&0)).unwrap() as f64;
+
+        if unknown_language_count < half_word_count {
+            total_language_counts.remove(&None);
+        }
+
+        if total_language_counts.is_empty() {
+            return None;
+        }
+
+        if total_language_counts.len() == 1 {
+            return total_language_counts.iter().next().unwrap().0.clone();
+        }
+
+        if total_language_counts.len() == 2
+            && cfg!(any(feature = "chinese", feature = "external-models"))
+            && cfg!(any(feature = "japanese", feature = "external-models"))
+            && total_language_counts.contains_key(&Some(Language::from_str("Chinese").unwrap()))
+            && total_language_counts.contains_key(&Some(Language::from_str("Japanese").unwrap()))
+        {
+            return Some(Language::from_str("Japanese").unwrap());
+        }
+
+        let sorted_total_language_counts = total_language_counts
+            .into_iter()
+            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+            .collect_vec();
+        let (most_frequent_language, first_count) = sorted_total_language_counts[0].clone();
+        let (_, second_count) = sorted_total_language_counts[1];
+
+        if first_count == second_count {
+            return None;
+        }
+
+        most_frequent_language
+    }
+
+    pub(crate) fn filter_languages_by_rules(
+        &self,
+        word_counts: &WordCounts,
+        languages: &HashSet<Language>,
+    ) -> HashSet<Language> {
+        let detected_alphabets = word_counts.word_alphabets.clone();
+        let half_word_count = (word_counts.word_count as f64) * 0.5;
+
+        if detected_alphabets.is_empty() {
+            return languages.clone();
+        }
+
+        if detected_alphabets.len() > 1 {
+            let mut distinct_alphabets = hashset!();
+            for count in detected_alphabets.values() {
+                distinct_alphabets.insert(count);
+            }
+            if distinct_alphabets.len() == 1 {
+                return languages.clone();
+            }
+        }
+
+        let most_frequent_alphabet = detected_alphabets
+            .into_iter()
+            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+            .next()
+            .unwrap()
+            .0;
+
+        let filtered_languages = languages
+            .iter()
+            .cloned()
+            .filter(|it| it.alphabets().contains(&most_frequent_alphabet))
+            .collect::<HashSet<_>>();
+
+        let languages_subset = word_counts
+            .character_languages
+            .iter()
+            .filter(|(_, count)| (**count as f64) >= half_word_count)
+            .map(|(language, _)| language)
+            .collect::<HashSet<_>>();
+
+        if !languages_subset.is_empty() {
+            filtered_languages
+                .into_iter()
+                .filter(|it| languages_subset.contains(&it))
+                .collect::<HashSet<_>>()
+        } else {
+            filtered_languages
+        }
+    }
+
+    fn look_up_language_models<F>(
+        &self,
+        test_data_model: &TestDataLanguageModel,
+        ngram_length: usize,
+        filtered_languages: &HashSet<Language>,
+        lookup: &F,
+    ) -> (HashMap<Language, f64>, Option<HashMap<Language, u32>>)
+    where
+        F: Fn(&Language, &Ngram) -> f64,
+    {
+        let probabilities =
+            self.compute_language_probabilities(test_data_model, filtered_languages, lookup);
+        let unigram_counts = if ngram_length == 1 {
+            let languages = probabilities.keys().collect_vec();
+            let intersected_languages = if !languages.is_empty() {
+                filtered_languages
+                    .iter()
+                    .cloned()
+                    .filter(|it| languages.contains(&it))
+                    .collect()
+            } else {
+                filtered_languages.clone()
+            };
+            Some(self.count_unigrams(test_data_model, &intersected_languages, lookup))
+        } else {
+            None
+        };
+
+        (probabilities, unigram_counts)
+    }
+
+    fn compute_language_probabilities<F>(
+        &self,
+        model: &TestDataLanguageModel,
+        filtered_languages: &HashSet<Language>,
+        lookup: &F,
+    ) -> HashMap<Language, f64>
+    where
+        F: Fn(&Language, &Ngram) -> f64,
+    {
+        let mut probabilities = hashmap!();
+        for language in filtered_languages.iter() {
+            let sum = self.compute_sum_of_ngram_probabilities(language, &model.ngrams, lookup);
+            if sum < 0.0 {
+                probabilities.insert(language.clone(), sum);
+            }
+        }
+        probabilities
+    }
+
+    fn get_highest_probability(&self, probabilities: &HashMap<Language, f64>) -> f64 {
+        probabilities
+            .iter()
+            .map(|(_, &probability)| probability)
+            .sorted_by(|&first, &second| second.partial_cmp(&first).unwrap())
+            .next()
+            .unwrap()
+    }
+
+    fn compute_confidence_values(
+        &self,
+        probabilities: HashMap<Language, f64>,
+        highest_probability: f64,
+    ) -> Vec<(Language, f64)> {
+        probabilities
+            .into_iter()
+            .map(|(language, probability)| (language, highest_probability / probability))
+            .sorted_by(
+                |(first_language, first_probability), (second_language, second_probability)| {
+                    let sorted_by_probability =
+                        second_probability.partial_cmp(first_probability).unwrap();
+                    let sorted_by_language = first_language.partial_cmp(second_language).unwrap();
+
+                    sorted_by_probability.then(sorted_by_language)
+                },
+            )
+            .collect_vec()
+    }
+
+    fn compute_sum_of_ngram_probabilities<F>(
+        &self,
+        language: &Language,
+        ngrams: &HashSet<Ngram>,
+        lookup: &F,
+    ) -> f64
+    where
+        F: Fn(&Language, &Ngram) -> f64,
+    {
+        let mut sum = 0.0;
+        for ngram in ngrams.iter() {
+            let probability = lookup(language, ngram);
+
+            if probability > 0.0 {
+                sum += probability.ln();
+            }
+        }
+        sum
+    }
+
+    /// Returns the probability of the given ngram computed by the configured
+    /// smoothing strategy, by default backing off to its longest known lower-order ngram.
+    pub(crate) fn look_up_smoothed_ngram_probability(
+        &self,
+        language: &Language,
+        ngram: &Ngram,
+    ) -> f64 {
+        self.smoothing.compute_probability(ngram, |elem| {
+            if self.ngram_lengths.contains(elem.value.chars().count()) {
+                self.look_up_ngram_probability(language, elem)
+            } else {
+                0.0
+            }
+        })
+    }
+
+    fn look_up_ngram_probability(&self, language: &Language, ngram: &Ngram) -> f64 {
+        self.model_store.get_relative_frequency(language, ngram)
+    }
+
+    fn count_unigrams<F>(
+        &self,
+        unigram_model: &TestDataLanguageModel,
+        filtered_languages: &HashSet<Language>,
+        lookup: &F,
+    ) -> HashMap<Language, u32>
+    where
+        F: Fn(&Language, &Ngram) -> f64,
+    {
+        let mut unigram_counts = HashMap::new();
+        for language in filtered_languages.iter() {
+            for unigram in unigram_model.ngrams.iter() {
+                if lookup(language, unigram) > 0.0 {
+                    self.increment_counter(&mut unigram_counts, language.clone());
+                }
+            }
+        }
+        unigram_counts
+    }
+
+    /// Sums up the log-probabilities of each language over all ngram lengths. If `hints` are
+    /// given, the log prior probability derived from the prior weights of the detector and
+    /// the hints is added to the sum of each language. Afterwards, the sums are divided by the
+    /// unigram counts, if any, so that the prior is scaled in the same way as the likelihood.
+    pub(crate) fn sum_up_probabilities(
+        &self,
+        probabilities: Vec<&HashMap<Language, f64>>,
+        unigram_counts: &Option<HashMap<Language, u32>>,
+        filtered_languages: HashSet<Language>,
+        hints: Option<&HashMap<Language, f64>>,
+    ) -> HashMap<Language, f64> {
+        let mut summed_up_probabilities = hashmap!();
+        for language in filtered_languages.iter() {
+            let sum: f64 = probabilities
+                .iter()
+                .map(|it| match it.get(language) {
+                    Some(probability) => *probability,
+                    None => 0.0,
+                })
+                .sum();
+
+            if sum != 0.0 {
+                summed_up_probabilities.insert(language.clone(), sum);
+            }
+        }
+
+        if let Some(hints) = hints {
+            summed_up_probabilities =
+                add_log_prior_probabilities(summed_up_probabilities, &self.prior_weights, hints);
+        }
+
+        if let Some(counts) = unigram_counts {
+            for (language, sum) in summed_up_probabilities.iter_mut() {
+                if let Some(count) = counts.get(language) {
+                    *sum /= *count as f64;
+                }
+            }
+        }
+
+        summed_up_probabilities
+    }
+
+    fn increment_counter<T: Eq + Hash>(&self, counts: &mut HashMap<T, u32>, key: T) {
+        let counter = counts.entry(key).or_insert(0);
+        *counter += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::DEFAULT_CALIBRATION_TEMPERATURE;
+    use crate::custom::CustomLanguage;
+    use crate::language::Language::*;
+    use crate::model::{BinaryLanguageModel, MockLanguageModel, TrainingDataLanguageModel};
+    use crate::smoothing::UNKNOWN_NGRAM_FREQUENCY;
+    use crate::store::BoxedLanguageModel;
+    use crate::writer::LanguageModelFilesWriter;
+    use crate::LanguageDetectorBuilder;
+    use float_cmp::approx_eq;
+    use once_cell::sync::OnceCell;
+    use rstest::*;
+    use std::io::Read;
+
+    // ##############################
+    // MOCKS
+    // ##############################
+
+    fn create_training_model_mock(data: HashMap<&'static str, f64>) -> BoxedLanguageModel {
+        let mut mock = MockLanguageModel::new();
+        for (ngram, probability) in data {
+            mock.expect_get_relative_frequency()
+                .withf(move |n| n == &Ngram::new(ngram))
+                .return_const(probability);
+        }
+        Box::new(mock)
+    }
+
+    // ##############################
+    // LANGUAGE MODELS FOR ENGLISH
+    // ##############################
+
+    #[fixture]
+    fn unigram_language_model_for_english() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "a" => 0.01,
+            "l" => 0.02,
+            "t" => 0.03,
+            "e" => 0.04,
+            "r" => 0.05,
+            // unknown unigrams
+            "w" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn bigram_language_model_for_english() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "al" => 0.11,
+            "lt" => 0.12,
+            "te" => 0.13,
+            "er" => 0.14,
+            // unknown bigrams
+            "aq" => 0.0,
+            "wx" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn trigram_language_model_for_english() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "alt" => 0.19,
+            "lte" => 0.2,
+            "ter" => 0.21,
+            // unknown trigrams
+            "aqu" => 0.0,
+            "tez" => 0.0,
+            "wxy" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn quadrigram_language_model_for_english() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "alte" => 0.25,
+            "lter" => 0.26,
+            // unknown quadrigrams
+            "aqua" => 0.0,
+            "wxyz" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn fivegram_language_model_for_english() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "alter" => 0.29,
+            // unknown fivegrams
+            "aquas" => 0.0
+        ))
+    }
+
+    // ##############################
+    // LANGUAGE MODELS FOR GERMAN
+    // ##############################
+
+    #[fixture]
+    fn unigram_language_model_for_german() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "a" => 0.06,
+            "l" => 0.07,
+            "t" => 0.08,
+            "e" => 0.09,
+            "r" => 0.1,
+            // unknown unigrams
+            "w" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn bigram_language_model_for_german() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "al" => 0.15,
+            "lt" => 0.16,
+            "te" => 0.17,
+            "er" => 0.18,
+            // unknown bigrams
+            "wx" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn trigram_language_model_for_german() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "alt" => 0.22,
+            "lte" => 0.23,
+            "ter" => 0.24,
+            // unknown trigrams
+            "wxy" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn quadrigram_language_model_for_german() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!(
+            "alte" => 0.27,
+            "lter" => 0.28,
+            // unknown quadrigrams
+            "wxyz" => 0.0
+        ))
+    }
+
+    #[fixture]
+    fn fivegram_language_model_for_german() -> BoxedLanguageModel {
+        create_training_model_mock(hashmap!("alter" => 0.3))
+    }
+
+    // ##############################
+    // NGRAM MODELS
+    // ##############################
+
+    #[fixture]
+    fn unigram_language_models(
+        unigram_language_model_for_english: BoxedLanguageModel,
+        unigram_language_model_for_german: BoxedLanguageModel,
+    ) -> HashMap<Language, BoxedLanguageModel> {
+        hashmap!(
+            English => unigram_language_model_for_english,
+            German => unigram_language_model_for_german
+        )
+    }
+
+    #[fixture]
+    fn bigram_language_models(
+        bigram_language_model_for_english: BoxedLanguageModel,
+        bigram_language_model_for_german: BoxedLanguageModel,
+    ) -> HashMap<Language, BoxedLanguageModel> {
+        hashmap!(
+            English => bigram_language_model_for_english,
+            German => bigram_language_model_for_german
+        )
+    }
+
+    #[fixture]
+    fn trigram_language_models(
+        trigram_language_model_for_english: BoxedLanguageModel,
+        trigram_language_model_for_german: BoxedLanguageModel,
+    ) -> HashMap<Language, BoxedLanguageModel> {
+        hashmap!(
+            English => trigram_language_model_for_english,
+            German => trigram_language_model_for_german
+        )
+    }
+
+    #[fixture]
+    fn quadrigram_language_models(
+        quadrigram_language_model_for_english: BoxedLanguageModel,
+        quadrigram_language_model_for_german: BoxedLanguageModel,
+    ) -> HashMap<Language, BoxedLanguageModel> {
+        hashmap!(
+            English => quadrigram_language_model_for_english,
+            German => quadrigram_language_model_for_german
+        )
+    }
+
+    #[fixture]
+    fn fivegram_language_models(
+        fivegram_language_model_for_english: BoxedLanguageModel,
+        fivegram_language_model_for_german: BoxedLanguageModel,
+    ) -> HashMap<Language, BoxedLanguageModel> {
+        hashmap!(
+            English => fivegram_language_model_for_english,
+            German => fivegram_language_model_for_german
+        )
+    }
+
+    // ##############################
+    // EMPTY MODEL STORE
+    // ##############################
+
+    #[fixture]
+    fn empty_model_store() -> Arc<ModelStore> {
+        static EMPTY_MODEL_STORE_FIXTURE: OnceCell<Arc<ModelStore>> = OnceCell::new();
+        EMPTY_MODEL_STORE_FIXTURE
+            .get_or_init(|| Arc::new(ModelStore::new()))
+            .clone()
+    }
+
+    // ##############################
+    // TEST DATA MODELS
+    // ##############################
+
+    #[fixture(strs=hashset!())]
+    fn test_data_model(strs: HashSet<&'static str>) -> TestDataLanguageModel {
+        let ngrams = strs
+            .iter()
+            .map(|&it| Ngram::new(it))
+            .collect::<HashSet<_>>();
+
+        TestDataLanguageModel { ngrams }
+    }
+
+    // ##############################
+    // DETECTORS
+    // ##############################
+
+    #[fixture]
+    fn detector_for_english_and_german(
+        unigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        bigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        trigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        quadrigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        fivegram_language_models: HashMap<Language, BoxedLanguageModel>,
+    ) -> LanguageDetector {
+        LanguageDetector {
+            languages: hashset!(English, German),
+            minimum_relative_distance: 0.0,
+            early_stopping_margin: None,
+            calibration_temperature: DEFAULT_CALIBRATION_TEMPERATURE,
+            smoothing: Smoothing::BackOff,
+            ngram_lengths: NgramLengths::default(),
+            prior_weights: hashmap!(),
+            rejection_thresholds: hashmap!(),
+            languages_with_unique_characters: hashset!(),
+            one_language_alphabets: hashmap!(),
+            model_store: Arc::new(ModelStore::from_language_models(
+                unigram_language_models,
+                bigram_language_models,
+                trigram_language_models,
+                quadrigram_language_models,
+                fivegram_language_models,
+            )),
+        }
+    }
+
+    #[fixture]
+    fn detector_for_all_languages(empty_model_store: Arc<ModelStore>) -> LanguageDetector {
+        let languages = Language::all();
+        let languages_with_unique_characters = languages
+            .iter()
+            .filter(|it| it.unique_characters().is_some())
+            .cloned()
+            .collect();
+
+        let one_language_alphabets = Alphabet::all_supporting_single_language()
+            .into_iter()
+            .filter(|(_, language)| languages.contains(language))
+            .collect();
+
+        LanguageDetector {
+            languages,
+            minimum_relative_distance: 0.0,
+            early_stopping_margin: None,
+            calibration_temperature: DEFAULT_CALIBRATION_TEMPERATURE,
+            smoothing: Smoothing::BackOff,
+            ngram_lengths: NgramLengths::default(),
+            prior_weights: hashmap!(),
+            rejection_thresholds: hashmap!(),
+            languages_with_unique_characters,
+            one_language_alphabets,
+            model_store: empty_model_store,
+        }
+    }
+
+    // ##############################
+    // TESTS
+    // ##############################
+
+    #[rstest]
+    fn assert_languages_are_returned_sorted(detector_for_english_and_german: LanguageDetector) {
+        assert_eq!(
+            detector_for_english_and_german.languages(),
+            vec![English, German]
+        );
+    }
+
+    #[rstest]
+    fn assert_text_is_cleaned_up_properly(detector_for_all_languages: LanguageDetector) {
+        let text = "Weltweit    gibt es ungefähr 6.000 Sprachen,
+        wobei laut Schätzungen zufolge ungefähr 90  Prozent davon
+        am Ende dieses Jahrhunderts verdrängt sein werden.";
+
+        let expected_cleaned_text =
+            "weltweit gibt es ungefähr sprachen wobei laut schätzungen zufolge ungefähr \
+            prozent davon am ende dieses jahrhunderts verdrängt sein werden";
+
+        assert_eq!(
+            detector_for_all_languages.clean_up_input_text(text.to_string()),
+            expected_cleaned_text
+        );
+    }
+
+    #[rstest]
+    fn assert_text_is_split_into_words_correctly(detector_for_all_languages: LanguageDetector) {
+        assert_eq!(
+            detector_for_all_languages.split_text_into_words("this is a sentence"),
+            vec!["this", "is", "a", "sentence"]
+        );
+        assert_eq!(
+            detector_for_all_languages.split_text_into_words("sentence"),
+            vec!["sentence"]
+        );
+        assert_eq!(
+            detector_for_all_languages
+                .split_text_into_words("上海大学是一个好大学 this is a sentence"),
+            vec![
+                "上", "海", "大", "学", "是", "一", "个", "好", "大", "学", "this", "is", "a",
+                "sentence"
+            ]
+        );
+    }
+
+    #[rstest(
+        language,
+        ngram,
+        expected_probability,
+        case(English, "a", 0.01),
+        case(English, "lt", 0.12),
+        case(English, "ter", 0.21),
+        case(English, "alte", 0.25),
+        case(English, "alter", 0.29),
+        case(German, "t", 0.08),
+        case(German, "er", 0.18),
+        case(German, "alt", 0.22),
+        case(German, "lter", 0.28),
+        case(German, "alter", 0.3)
+    )]
+    fn assert_ngram_probability_lookup_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        language: Language,
+        ngram: &str,
+        expected_probability: f64,
+    ) {
+        let probability = detector_for_english_and_german
+            .look_up_ngram_probability(&language, &Ngram::new(ngram));
+        assert_eq!(
+            probability, expected_probability,
+            "expected probability {} for language '{:?}' and ngram '{}', got {}",
+            expected_probability, language, ngram, probability
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "zerogram detected")]
+    fn assert_ngram_probability_lookup_does_not_work_for_zerogram(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        detector_for_english_and_german.look_up_ngram_probability(&English, &Ngram::new(""));
+    }
+
+    #[rstest(
+        ngrams, expected_sum_of_probabilities,
+        case(
+            hashset!("a", "l", "t", "e", "r"),
+            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln()
+        ),
+        case(
+            // back off unknown Trigram("tez") to known Bigram("te")
+            hashset!("alt", "lte", "tez"),
+            0.19_f64.ln() + 0.2_f64.ln() + 0.13_f64.ln()
+        ),
+        case(
+            // back off unknown Fivegram("aquas") to known Unigram("a")
+            hashset!("aquas"),
+            0.01_f64.ln()
+        )
+    )]
+    fn assert_summation_of_ngram_probabilities_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        ngrams: HashSet<&str>,
+        expected_sum_of_probabilities: f64,
+    ) {
+        let mapped_ngrams = ngrams
+            .iter()
+            .map(|&it| Ngram::new(it))
+            .collect::<HashSet<_>>();
+
+        let sum_of_probabilities = detector_for_english_and_german
+            .compute_sum_of_ngram_probabilities(
+                &English,
+                &mapped_ngrams,
+                &|language: &Language, ngram: &Ngram| {
+                    detector_for_english_and_german
+                        .look_up_smoothed_ngram_probability(language, ngram)
+                },
+            );
+
+        assert!(
+            approx_eq!(
+                f64,
+                sum_of_probabilities,
+                expected_sum_of_probabilities,
+                ulps = 1
+            ),
+            "expected sum {} for language '{:?}' and ngrams {:?}, got {}",
+            expected_sum_of_probabilities,
+            English,
+            ngrams,
+            sum_of_probabilities
+        );
+    }
+
+    #[rstest(
+        test_data_model,
+        expected_probabilities,
+        case::unigram_model(
+            test_data_model(hashset!("a", "l", "t", "e", "r")),
+            hashmap!(
+                English => 0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln(),
+                German => 0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln()
+            )
+        ),
+        case::trigram_model(
+            test_data_model(hashset!("alt", "lte", "ter", "wxy")),
+            hashmap!(
+                English => 0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln(),
+                German => 0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln()
+            )
+        ),
+        case::quadrigram_model(
+            test_data_model(hashset!("alte", "lter", "wxyz")),
+            hashmap!(
+                English => 0.25_f64.ln() + 0.26_f64.ln(),
+                German => 0.27_f64.ln() + 0.28_f64.ln()
+            )
+        )
+    )]
+    fn assert_computation_of_language_probabilities_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        test_data_model: TestDataLanguageModel,
+        expected_probabilities: HashMap<Language, f64>,
+    ) {
+        let probabilities = detector_for_english_and_german.compute_language_probabilities(
+            &test_data_model,
+            &hashset!(English, German),
+            &|language: &Language, ngram: &Ngram| {
+                detector_for_english_and_german.look_up_smoothed_ngram_probability(language, ngram)
+            },
+        );
+
+        for (language, probability) in probabilities {
+            let expected_probability = expected_probabilities[&language];
+
+            assert!(
+                approx_eq!(f64, probability, expected_probability, ulps = 1),
+                "expected probability {} for language '{:?}', got {}",
+                expected_probability,
+                language,
+                probability
+            );
+        }
+    }
+
+    #[rstest]
+    fn assert_computation_of_confidence_values_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let unigram_count_for_both_languages = 5.0;
+
+        let total_probability_for_german = (
+            // unigrams
+            0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln() +
+            // bigrams
+            0.15_f64.ln() + 0.16_f64.ln() + 0.17_f64.ln() + 0.18_f64.ln() +
+            // trigrams
+            0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln() +
+            // quadrigrams
+            0.27_f64.ln() + 0.28_f64.ln() +
+            // fivegrams
+            0.3_f64.ln()
+        ) / unigram_count_for_both_languages;
+
+        let total_probability_for_english = (
+            // unigrams
+            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln() +
+            // bigrams
+            0.11_f64.ln() + 0.12_f64.ln() + 0.13_f64.ln() + 0.14_f64.ln() +
+            // trigrams
+            0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln() +
+            // quadrigrams
+            0.25_f64.ln() + 0.26_f64.ln() +
+            // fivegrams
+            0.29_f64.ln()
+        ) / unigram_count_for_both_languages;
+
+        let expected_confidence_for_german = 1.0;
+        let expected_confidence_for_english =
+            total_probability_for_german / total_probability_for_english;
+
+        let confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values("Alter");
+
+        assert_eq!(
+            confidence_values[0],
+            (German, expected_confidence_for_german)
+        );
+        assert_eq!(confidence_values[1].0, English);
+        assert!(approx_eq!(
+            f64,
+            confidence_values[1].1,
+            expected_confidence_for_english,
+            ulps = 1
+        ));
+    }
+
+    #[rstest(
+        smoothing,
+        expected_probability,
+        case(Smoothing::BackOff, 0.01),
+        case(Smoothing::Additive { alpha: 0.01 }, 0.01 / 1.01),
+        case(Smoothing::PenalizedBackOff { penalty: 0.4 }, 0.4_f64.powi(4) * 0.01)
+    )]
+    fn assert_smoothing_is_applied_to_unknown_ngrams(
+        mut detector_for_english_and_german: LanguageDetector,
+        smoothing: Smoothing,
+        expected_probability: f64,
+    ) {
+        detector_for_english_and_german.smoothing = smoothing;
+
+        let probability = detector_for_english_and_german
+            .look_up_smoothed_ngram_probability(&English, &Ngram::new("aquas"));
+
+        assert!(
+            approx_eq!(f64, probability, expected_probability, ulps = 2),
+            "expected probability {} for smoothing {:?}, got {}",
+            expected_probability,
+            smoothing,
+            probability
+        );
+    }
+
+    #[rstest(
+        smoothing,
+        case(Smoothing::Additive { alpha: 0.001 }),
+        case(Smoothing::PenalizedBackOff { penalty: 0.4 }),
+        case(Smoothing::LinearInterpolation { weight: 0.9 })
+    )]
+    fn assert_language_of_german_noun_alter_is_detected_correctly_with_smoothing(
+        mut detector_for_english_and_german: LanguageDetector,
+        smoothing: Smoothing,
+    ) {
+        detector_for_english_and_german.smoothing = smoothing;
+
+        let detected_language = detector_for_english_and_german.detect_language_of("Alter");
+        assert_eq!(detected_language, Some(German));
+    }
+
+    #[rstest]
+    fn assert_language_of_german_noun_alter_is_detected_correctly(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let detected_language = detector_for_english_and_german.detect_language_of("Alter");
+        assert_eq!(detected_language, Some(German));
+    }
+
+    #[rstest]
+    fn assert_detection_of_german_noun_alter_is_explained(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let explanation = detector_for_english_and_german.explain("Alter");
+
+        assert_eq!(explanation.cleaned_text(), "alter");
+        assert_eq!(explanation.words(), ["alter"]);
+        assert_eq!(explanation.alphabets(), [(Alphabet::Latin, 1)]);
+        assert_eq!(explanation.rule(), &RuleDecision::Undecided);
+        assert_eq!(explanation.remaining_languages(), [English, German]);
+        assert_eq!(explanation.confidence_values().len(), 2);
+        for ((language, confidence), (expected_language, expected_confidence)) in explanation
+            .confidence_values()
+            .iter()
+            .zip(detector_for_english_and_german.compute_language_confidence_values("Alter"))
+        {
+            assert_eq!(language, &expected_language);
+            assert!(approx_eq!(
+                f64,
+                *confidence,
+                expected_confidence,
+                epsilon = 1e-12
+            ));
+        }
+        assert_eq!(explanation.language(), Some(German));
+
+        let evidence = &explanation.language_evidence()[1];
+        let unigram_log_probability = [0.06_f64, 0.07, 0.08, 0.09, 0.1]
+            .iter()
+            .map(|probability| probability.ln())
+            .sum::<f64>();
+        let total_log_probability = evidence
+            .log_probabilities()
+            .iter()
+            .map(|(_, log_probability)| log_probability)
+            .sum::<f64>()
+            / 5.0;
+
+        assert_eq!(evidence.language(), German);
+        assert_eq!(
+            evidence
+                .log_probabilities()
+                .iter()
+                .map(|(ngram_length, _)| *ngram_length)
+                .collect_vec(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(approx_eq!(
+            f64,
+            evidence.log_probabilities()[0].1,
+            unigram_log_probability,
+            epsilon = 1e-12
+        ));
+        assert_eq!(evidence.unigram_count(), 5);
+        assert!(approx_eq!(
+            f64,
+            evidence.total_log_probability(),
+            total_log_probability,
+            epsilon = 1e-12
+        ));
+        assert_eq!(
+            evidence
+                .top_ngrams()
+                .iter()
+                .map(|(ngram, _)| ngram.as_str())
+                .collect_vec(),
+            vec!["alter", "lter", "alte", "ter", "lte", "alt", "er", "te", "lt", "al"]
+        );
+        assert!(evidence.missing_ngrams().is_empty());
+        assert_eq!(evidence.missing_ngram_count(), 0);
+    }
+
+    #[rstest(
+        text,
+        expected_rule,
+        case("3<856%)§", RuleDecision::NoLetters),
+        case("σταμάτησε", RuleDecision::WordRules { language: Greek }),
+    )]
+    fn assert_rule_decisions_are_explained(
+        detector_for_all_languages: LanguageDetector,
+        text: &str,
+        expected_rule: RuleDecision,
+    ) {
+        let explanation = detector_for_all_languages.explain(text);
+
+        assert_eq!(explanation.rule(), &expected_rule);
+        assert!(explanation.language_evidence().is_empty());
+        assert_eq!(
+            explanation.language(),
+            detector_for_all_languages.detect_language_of(text)
+        );
+    }
+
+    #[rstest]
+    fn assert_filtering_to_single_language_is_explained(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let explanation = detector_for_english_and_german.explain("Männer");
+
+        assert_eq!(
+            explanation.rule(),
+            &RuleDecision::SingleRemainingLanguage { language: German }
+        );
+        assert_eq!(explanation.word_languages(), [(None, 1)]);
+        assert!(explanation.character_languages().contains(&(German, 1)));
+        assert_eq!(explanation.remaining_languages(), [German]);
+        assert_eq!(explanation.language(), Some(German));
+    }
+
+    #[test]
+    fn assert_explanation_lists_missing_ngrams() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+        let text = "languages are awesome";
+        let explanation = detector.explain(text);
+
+        assert_eq!(explanation.rule(), &RuleDecision::Undecided);
+        assert_eq!(explanation.language(), detector.detect_language_of(text));
+        assert_eq!(explanation.language_evidence().len(), 2);
+
+        for evidence in explanation.language_evidence() {
+            assert_eq!(evidence.top_ngrams().len(), MAXIMUM_LISTED_NGRAMS);
+            assert_eq!(
+                evidence.missing_ngrams().len(),
+                evidence.missing_ngram_count().min(MAXIMUM_LISTED_NGRAMS)
+            );
+        }
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["rule"]["rule"], "undecided");
+        assert_eq!(json["language_evidence"][0]["language"], "ENGLISH");
+    }
+
+    #[rstest(
+        text,
+        options,
+        case("Alter", DetectionOptions::new()),
+        case("Alter", DetectionOptions::new().with_languages(&[English])),
+        case("Alter", DetectionOptions::new().with_languages(&[French])),
+        case("Alter", DetectionOptions::new().with_minimum_relative_distance(0.25)),
+        case("Alter", DetectionOptions::new().with_hints(&[(English, 10.0)])),
+        case("", DetectionOptions::new()),
+        case("3<856%)§", DetectionOptions::new()),
+        case("σταμάτησε", DetectionOptions::new())
+    )]
+    fn assert_explanation_ends_with_detection_outcome(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        options: DetectionOptions,
+    ) {
+        let explanation = detector_for_english_and_german.explain_with_options(text, &options);
+        let outcome =
+            detector_for_english_and_german.detect_language_outcome_with_options(text, &options);
+
+        assert_eq!(explanation.outcome(), &outcome);
+        assert_eq!(explanation.language(), outcome.language());
+    }
+
+    #[rstest]
+    fn assert_explanation_honours_detection_options(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let explanation = detector_for_english_and_german
+            .explain_with_options("Alter", &DetectionOptions::new().with_languages(&[English]));
+
+        assert_eq!(
+            explanation.rule(),
+            &RuleDecision::SingleRemainingLanguage { language: English }
+        );
+        assert_eq!(explanation.remaining_languages(), [English]);
+        assert_eq!(
+            explanation.outcome(),
+            &DetectionOutcome::Detected {
+                language: English,
+                source: DecisionSource::Rules
+            }
+        );
+
+        let explanation = detector_for_english_and_german.explain_with_options(
+            "Alter",
+            &DetectionOptions::new().with_minimum_relative_distance(0.25),
+        );
+
+        assert_eq!(explanation.language_evidence().len(), 2);
+        assert_eq!(
+            explanation.outcome(),
+            &DetectionOutcome::Ambiguous {
+                candidates: vec![German, English]
+            }
+        );
+        assert_eq!(
+            detector_for_english_and_german
+                .explain("σταμάτησε")
+                .outcome(),
+            &DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::UnsupportedScript
+            }
+        );
+    }
+
+    #[rstest(
+        text,
+        expected_outcome,
+        case(
+            "Alter",
+            DetectionOutcome::Detected { language: German, source: DecisionSource::Ngrams }
+        ),
+        case(
+            "Männer",
+            DetectionOutcome::Detected { language: German, source: DecisionSource::Rules }
+        ),
+        case(
+            " \n  \t",
+            DetectionOutcome::Undetermined { reason: UndeterminedReason::EmptyText }
+        ),
+        case(
+            "3<856%)§",
+            DetectionOutcome::Undetermined { reason: UndeterminedReason::NoLetters }
+        ),
+        case(
+            "σταμάτησε",
+            DetectionOutcome::Undetermined { reason: UndeterminedReason::UnsupportedScript }
+        ),
+    )]
+    fn assert_detection_outcome_is_described_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        expected_outcome: DetectionOutcome,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_of(text),
+            expected_outcome
+        );
+    }
+
+    #[rstest]
+    fn assert_close_languages_are_ambiguous_candidates(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        detector_for_english_and_german.minimum_relative_distance = 0.25;
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_of("Alter"),
+            DetectionOutcome::Ambiguous {
+                candidates: vec![German, English]
+            }
         );
         assert_eq!(
-            detector_for_all_languages.split_text_into_words("sentence"),
-            vec!["sentence"]
+            detector_for_english_and_german.detect_language_of("Alter"),
+            None
         );
+    }
+
+    #[test]
+    fn assert_too_short_text_is_described_correctly() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_low_accuracy_mode()
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+
         assert_eq!(
-            detector_for_all_languages
-                .split_text_into_words("上海大学是一个好大学 this is a sentence"),
-            vec![
-                "上", "海", "大", "学", "是", "一", "个", "好", "大", "学", "this", "is", "a",
-                "sentence"
-            ]
+            detector.detect_language_outcome_of("ab"),
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::TooShort
+            }
         );
     }
 
-    #[rstest(
-        language,
-        ngram,
-        expected_probability,
-        case(English, "a", 0.01),
-        case(English, "lt", 0.12),
-        case(English, "ter", 0.21),
-        case(English, "alte", 0.25),
-        case(English, "alter", 0.29),
-        case(German, "t", 0.08),
-        case(German, "er", 0.18),
-        case(German, "alt", 0.22),
-        case(German, "lter", 0.28),
-        case(German, "alter", 0.3)
-    )]
-    fn assert_ngram_probability_lookup_works_correctly(
+    #[rstest]
+    fn assert_unknown_ngrams_are_described_correctly(
         detector_for_english_and_german: LanguageDetector,
-        language: Language,
-        ngram: &str,
-        expected_probability: f64,
     ) {
-        let probability = detector_for_english_and_german
-            .look_up_ngram_probability(&language, &Ngram::new(ngram));
         assert_eq!(
-            probability, expected_probability,
-            "expected probability {} for language '{:?}' and ngram '{}', got {}",
-            expected_probability, language, ngram, probability
+            detector_for_english_and_german.detect_language_outcome_of("w"),
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::UnknownNgrams
+            }
         );
     }
 
     #[rstest]
-    #[should_panic(expected = "zerogram detected")]
-    fn assert_ngram_probability_lookup_does_not_work_for_zerogram(
+    fn assert_language_is_detected_among_given_languages(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        detector_for_english_and_german.look_up_ngram_probability(&English, &Ngram::new(""));
+        assert_eq!(
+            detector_for_english_and_german.detect_language_among("Alter", &[English]),
+            Some(English)
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_among("Alter", &[English, German]),
+            Some(German)
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_with_options(
+                "Alter",
+                &DetectionOptions::new().with_languages(&[French])
+            ),
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::NoLanguages
+            }
+        );
+        assert!(detector_for_english_and_german
+            .compute_language_confidence_values_with_options(
+                "Alter",
+                &DetectionOptions::new().with_languages(&[French])
+            )
+            .is_empty());
     }
 
-    #[rstest(
-        ngrams, expected_sum_of_probabilities,
-        case(
-            hashset!("a", "l", "t", "e", "r"),
-            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln()
-        ),
-        case(
-            // back off unknown Trigram("tez") to known Bigram("te")
-            hashset!("alt", "lte", "tez"),
-            0.19_f64.ln() + 0.2_f64.ln() + 0.13_f64.ln()
-        ),
-        case(
-            // back off unknown Fivegram("aquas") to known Unigram("a")
-            hashset!("aquas"),
-            0.01_f64.ln()
-        )
-    )]
-    fn assert_summation_of_ngram_probabilities_works_correctly(
+    #[rstest]
+    fn assert_minimum_relative_distance_is_overridden_per_call(
         detector_for_english_and_german: LanguageDetector,
-        ngrams: HashSet<&str>,
-        expected_sum_of_probabilities: f64,
     ) {
-        let mapped_ngrams = ngrams
-            .iter()
-            .map(|&it| Ngram::new(it))
-            .collect::<HashSet<_>>();
+        let options = DetectionOptions::new().with_minimum_relative_distance(0.25);
 
-        let sum_of_probabilities = detector_for_english_and_german
-            .compute_sum_of_ngram_probabilities(&English, &mapped_ngrams);
+        assert_eq!(
+            detector_for_english_and_german.detect_language_with_options("Alter", &options),
+            None
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(German)
+        );
+    }
 
-        assert!(
-            approx_eq!(
-                f64,
-                sum_of_probabilities,
-                expected_sum_of_probabilities,
-                ulps = 1
+    #[test]
+    fn assert_only_models_of_given_languages_are_loaded() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+        let language = detector.detect_language_among("languages are awesome", &[French, German]);
+
+        assert!(matches!(language, Some(French) | Some(German)));
+
+        for ngram_length in 1..6 {
+            assert_eq!(detector.model_store.loaded_model_count(ngram_length), 2);
+        }
+    }
+
+    #[rstest]
+    fn assert_prior_weights_are_combined_with_ngram_probabilities(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(German)
+        );
+
+        detector_for_english_and_german.prior_weights = hashmap!(English => 10_000.0);
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(English)
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_with_options(
+                "Alter",
+                &DetectionOptions::new().with_hints(&[(German, 10_000.0)])
             ),
-            "expected sum {} for language '{:?}' and ngrams {:?}, got {}",
-            expected_sum_of_probabilities,
-            English,
-            ngrams,
-            sum_of_probabilities
+            Some(German)
         );
     }
 
-    #[rstest(
-        test_data_model,
-        expected_probabilities,
-        case::unigram_model(
-            test_data_model(hashset!("a", "l", "t", "e", "r")),
-            hashmap!(
-                English => 0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln(),
-                German => 0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln()
-            )
-        ),
-        case::trigram_model(
-            test_data_model(hashset!("alt", "lte", "ter", "wxy")),
-            hashmap!(
-                English => 0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln(),
-                German => 0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln()
-            )
-        ),
-        case::quadrigram_model(
-            test_data_model(hashset!("alte", "lter", "wxyz")),
-            hashmap!(
-                English => 0.25_f64.ln() + 0.26_f64.ln(),
-                German => 0.27_f64.ln() + 0.28_f64.ln()
-            )
-        )
-    )]
-    fn assert_computation_of_language_probabilities_works_correctly(
+    #[rstest]
+    fn assert_hints_are_combined_with_ngram_probabilities(
         detector_for_english_and_german: LanguageDetector,
-        test_data_model: TestDataLanguageModel,
-        expected_probabilities: HashMap<Language, f64>,
     ) {
-        let probabilities = detector_for_english_and_german
-            .compute_language_probabilities(&test_data_model, &hashset!(English, German));
+        let options = DetectionOptions::new().with_accept_language("en-US, en;q=0.9");
+        let english_confidence = |confidence_values: Vec<(Language, f64)>| {
+            confidence_values
+                .into_iter()
+                .find(|(language, _)| language == &English)
+                .unwrap()
+                .1
+        };
 
-        for (language, probability) in probabilities {
-            let expected_probability = expected_probabilities[&language];
+        assert!(
+            english_confidence(
+                detector_for_english_and_german
+                    .compute_language_confidence_values_with_options("Alter", &options)
+            ) > english_confidence(
+                detector_for_english_and_german.compute_language_confidence_values("Alter")
+            )
+        );
+    }
 
-            assert!(
-                approx_eq!(f64, probability, expected_probability, ulps = 1),
-                "expected probability {} for language '{:?}', got {}",
-                expected_probability,
-                language,
-                probability
-            );
-        }
+    #[test]
+    fn assert_prior_weights_resolve_ambiguous_single_word() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+        let options = DetectionOptions::new().with_accept_language("en");
+
+        assert_eq!(detector.detect_language_of("Film"), Some(German));
+        assert_eq!(
+            detector.detect_language_with_options("Film", &options),
+            Some(English)
+        );
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_prior_weights(&[(English, 5.0)])
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+
+        assert_eq!(detector.detect_language_of("Film"), Some(English));
     }
 
     #[rstest]
-    fn assert_computation_of_confidence_values_works_correctly(
-        detector_for_english_and_german: LanguageDetector,
+    fn assert_text_fitting_language_model_badly_is_rejected(
+        mut detector_for_english_and_german: LanguageDetector,
     ) {
-        let unigram_count_for_both_languages = 5.0;
+        detector_for_english_and_german.rejection_thresholds =
+            hashmap!((German, TextLength::SingleWord) => -100.0);
 
-        let total_probability_for_german = (
-            // unigrams
-            0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln() +
-            // bigrams
-            0.15_f64.ln() + 0.16_f64.ln() + 0.17_f64.ln() + 0.18_f64.ln() +
-            // trigrams
-            0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln() +
-            // quadrigrams
-            0.27_f64.ln() + 0.28_f64.ln() +
-            // fivegrams
-            0.3_f64.ln()
-        ) / unigram_count_for_both_languages;
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(German)
+        );
 
-        let total_probability_for_english = (
-            // unigrams
-            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln() +
-            // bigrams
-            0.11_f64.ln() + 0.12_f64.ln() + 0.13_f64.ln() + 0.14_f64.ln() +
-            // trigrams
-            0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln() +
-            // quadrigrams
-            0.25_f64.ln() + 0.26_f64.ln() +
-            // fivegrams
-            0.29_f64.ln()
-        ) / unigram_count_for_both_languages;
+        detector_for_english_and_german.rejection_thresholds =
+            hashmap!((German, TextLength::ShortText) => 0.0);
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(German)
+        );
+
+        detector_for_english_and_german.rejection_thresholds =
+            hashmap!((German, TextLength::SingleWord) => 0.0);
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_of("Alter"),
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::UnknownLanguage
+            }
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            None
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_languages_of(&["Alter"]),
+            vec![None]
+        );
+        assert_eq!(
+            detector_for_english_and_german.explain("Alter").language(),
+            None
+        );
+        assert_eq!(
+            detector_for_english_and_german
+                .detect_language_of_reader("Alter".as_bytes())
+                .unwrap(),
+            None
+        );
+
+        let mut session = detector_for_english_and_german.start_session();
+        session.feed("Alter");
+
+        assert_eq!(session.current_language(), None);
+        assert_eq!(session.current_confidence_values()[0].0, German);
+    }
+
+    #[rstest]
+    fn assert_only_rejected_candidates_are_removed_from_ambiguous_outcome(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        detector_for_english_and_german.minimum_relative_distance = 0.25;
+        detector_for_english_and_german.rejection_thresholds = hashmap!(
+            (German, TextLength::SingleWord) => 0.0,
+            (English, TextLength::SingleWord) => -100.0
+        );
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_of("Alter"),
+            DetectionOutcome::Ambiguous {
+                candidates: vec![English]
+            }
+        );
+
+        detector_for_english_and_german.rejection_thresholds = hashmap!(
+            (German, TextLength::SingleWord) => 0.0,
+            (English, TextLength::SingleWord) => 0.0
+        );
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_outcome_of("Alter"),
+            DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::UnknownLanguage
+            }
+        );
+    }
+
+    #[rstest]
+    fn assert_rejection_thresholds_are_fitted_to_goodness_of_fit(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        let english_fit = detector_for_english_and_german
+            .compute_goodness_of_fit("Alter", &English)
+            .unwrap();
+        let german_fit = detector_for_english_and_german
+            .compute_goodness_of_fit("Alter", &German)
+            .unwrap();
+
+        assert!(german_fit < 0.0 && german_fit > UNKNOWN_NGRAM_FREQUENCY.ln());
+        assert_eq!(
+            detector_for_english_and_german.compute_goodness_of_fit("Alter", &French),
+            None
+        );
+        assert_eq!(
+            detector_for_english_and_german.compute_goodness_of_fit("1234", &German),
+            None
+        );
+
+        let thresholds = detector_for_english_and_german.fit_rejection_thresholds(
+            &[(German, "Alter"), (English, "Alter"), (French, "Alter")],
+            0.01,
+        );
+
+        assert_eq!(
+            thresholds
+                .iter()
+                .map(|(language, text_length, _)| (language, text_length))
+                .collect_vec(),
+            vec![
+                (&English, &TextLength::SingleWord),
+                (&German, &TextLength::SingleWord)
+            ]
+        );
+        assert_eq!(thresholds[0].2, english_fit);
+        assert_eq!(thresholds[1].2, german_fit);
+
+        detector_for_english_and_german.rejection_thresholds = thresholds
+            .into_iter()
+            .map(|(language, text_length, threshold)| ((language, text_length), threshold))
+            .collect();
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(German)
+        );
+    }
+
+    #[rstest]
+    fn assert_no_language_is_returned_when_no_ngram_probabilities_are_available(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let detected_language = detector_for_english_and_german.detect_language_of("проарплап");
+        assert_eq!(detected_language, None);
+    }
 
-        let expected_confidence_for_german = 1.0;
-        let expected_confidence_for_english =
-            total_probability_for_german / total_probability_for_english;
+    #[rstest]
+    fn assert_no_confidence_values_are_returned_when_no_ngram_probabilities_are_available(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values("проарплап");
 
+        assert_eq!(confidence_values, vec![]);
+    }
+
+    #[rstest]
+    fn assert_calibrated_probabilities_are_computed_correctly(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
         let confidence_values =
             detector_for_english_and_german.compute_language_confidence_values("Alter");
+        let probabilities =
+            detector_for_english_and_german.compute_calibrated_probabilities("Alter");
 
         assert_eq!(
-            confidence_values[0],
-            (German, expected_confidence_for_german)
+            probabilities
+                .iter()
+                .map(|(language, _)| language.clone())
+                .collect_vec(),
+            confidence_values
+                .iter()
+                .map(|(language, _)| language.clone())
+                .collect_vec()
         );
-        assert_eq!(confidence_values[1].0, English);
         assert!(approx_eq!(
             f64,
-            confidence_values[1].1,
-            expected_confidence_for_english,
-            ulps = 1
+            probabilities
+                .iter()
+                .map(|(_, probability)| probability)
+                .sum(),
+            1.0,
+            ulps = 2
         ));
+        assert!(probabilities[0].1 > probabilities[1].1);
     }
 
     #[rstest]
-    fn assert_language_of_german_noun_alter_is_detected_correctly(
+    fn assert_calibrated_probabilities_ignore_prior_weights(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        let probabilities =
+            detector_for_english_and_german.compute_calibrated_probabilities("Alter");
+
+        detector_for_english_and_german.prior_weights = hashmap!(English => 10_000.0);
+
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of("Alter"),
+            Some(English)
+        );
+        let probabilities_with_prior_weights =
+            detector_for_english_and_german.compute_calibrated_probabilities("Alter");
+
+        assert_eq!(probabilities_with_prior_weights.len(), probabilities.len());
+        for ((first_language, first_probability), (second_language, second_probability)) in
+            probabilities_with_prior_weights.iter().zip(&probabilities)
+        {
+            assert_eq!(first_language, second_language);
+            assert!(approx_eq!(
+                f64,
+                *first_probability,
+                *second_probability,
+                epsilon = 1e-12
+            ));
+        }
+        assert_eq!(probabilities[0].0, German);
+    }
+
+    #[rstest]
+    fn assert_no_calibrated_probabilities_are_returned_when_no_ngram_probabilities_are_available(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        let detected_language = detector_for_english_and_german.detect_language_of("Alter");
-        assert_eq!(detected_language, Some(German));
+        assert_eq!(
+            detector_for_english_and_german.compute_calibrated_probabilities("проарплап"),
+            vec![]
+        );
     }
 
     #[rstest]
-    fn assert_no_language_is_returned_when_no_ngram_probabilities_are_available(
+    fn assert_calibration_temperature_is_fitted_on_samples(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        let detected_language = detector_for_english_and_german.detect_language_of("проарплап");
-        assert_eq!(detected_language, None);
+        let samples = [(German, "Alter"), (German, "Alter"), (English, "проарплап")];
+        let temperature = detector_for_english_and_german.fit_calibration_temperature(&samples);
+
+        assert!(temperature > 0.0);
+        assert!(temperature < DEFAULT_CALIBRATION_TEMPERATURE);
     }
 
     #[rstest]
-    fn assert_no_confidence_values_are_returned_when_no_ngram_probabilities_are_available(
+    fn assert_batch_confidence_values_match_single_text_confidence_values(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        let confidence_values =
-            detector_for_english_and_german.compute_language_confidence_values("проарплап");
+        let texts = ["Alter", "проарплап", "", "Alter"];
+        let batch_confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values_of_batch(&texts);
 
-        assert_eq!(confidence_values, vec![]);
+        assert_eq!(batch_confidence_values.len(), texts.len());
+
+        for (text, confidence_values) in texts.iter().zip(batch_confidence_values) {
+            let expected_confidence_values =
+                detector_for_english_and_german.compute_language_confidence_values(*text);
+
+            assert_eq!(confidence_values.len(), expected_confidence_values.len());
+
+            for ((language, confidence), (expected_language, expected_confidence)) in
+                confidence_values.iter().zip(expected_confidence_values)
+            {
+                assert_eq!(*language, expected_language);
+                assert!(
+                    approx_eq!(f64, *confidence, expected_confidence, ulps = 2),
+                    "expected confidence {} for language '{:?}' of text '{}', got {}",
+                    expected_confidence,
+                    language,
+                    text,
+                    confidence
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn assert_languages_of_batch_are_detected_correctly(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let texts = vec![
+            "Alter".to_string(),
+            "проарплап".to_string(),
+            "3<856%)§".to_string(),
+        ];
+        assert_eq!(
+            detector_for_english_and_german.detect_languages_of(&texts),
+            vec![Some(German), None, None]
+        );
+    }
+
+    #[rstest]
+    fn assert_languages_of_batch_are_detected_together_with_confidence_values(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let texts = ["Alter", "проарплап", "Alter"];
+        let detections =
+            detector_for_english_and_german.detect_languages_with_confidence_values_of(&texts);
+
+        assert_eq!(
+            detections
+                .iter()
+                .map(|(language, _)| language.clone())
+                .collect_vec(),
+            detector_for_english_and_german.detect_languages_of(&texts)
+        );
+
+        for ((_, confidence_values), expected_confidence_values) in detections.iter().zip(
+            detector_for_english_and_german.compute_language_confidence_values_of_batch(&texts),
+        ) {
+            assert_eq!(confidence_values.len(), expected_confidence_values.len());
+
+            for ((language, confidence), (expected_language, expected_confidence)) in
+                confidence_values.iter().zip(expected_confidence_values)
+            {
+                assert_eq!(*language, expected_language);
+                assert!(approx_eq!(
+                    f64,
+                    *confidence,
+                    expected_confidence,
+                    epsilon = 1e-12
+                ));
+            }
+        }
+    }
+
+    #[rstest]
+    fn assert_empty_batch_returns_no_results(detector_for_english_and_german: LanguageDetector) {
+        let texts: [&str; 0] = [];
+        assert_eq!(
+            detector_for_english_and_german.detect_languages_of(&texts),
+            vec![]
+        );
+        assert_eq!(
+            detector_for_english_and_german.compute_language_confidence_values_of_batch(&texts),
+            Vec::<Vec<(Language, f64)>>::new()
+        );
     }
 
     #[rstest(
@@ -1302,8 +3450,9 @@ mod tests {
         word: &str,
         expected_language: Option<Language>,
     ) {
-        let detected_language =
-            detector_for_all_languages.detect_language_with_rules(&vec![word.to_string()]);
+        let word_counts = detector_for_all_languages
+            .count_words(&[word.to_string()], &detector_for_all_languages.languages);
+        let detected_language = detector_for_all_languages.detect_language_with_rules(&word_counts);
         assert_eq!(
             detected_language, expected_language,
             "expected {:?} for word '{}', got {:?}",
@@ -1431,8 +3580,10 @@ mod tests {
         word: &str,
         expected_languages: HashSet<Language>,
     ) {
-        let filtered_languages =
-            detector_for_all_languages.filter_languages_by_rules(vec![word.to_string()]);
+        let word_counts = detector_for_all_languages
+            .count_words(&[word.to_string()], &detector_for_all_languages.languages);
+        let filtered_languages = detector_for_all_languages
+            .filter_languages_by_rules(&word_counts, &detector_for_all_languages.languages);
         assert_eq!(
             filtered_languages, expected_languages,
             "expected {:?} for word '{}', got {:?}",
@@ -1440,6 +3591,35 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn assert_unique_characters_of_custom_languages_are_applied_to_language_filtering(
+        mut detector_for_all_languages: LanguageDetector,
+    ) {
+        let model_directory = tempfile::tempdir().unwrap();
+        let custom_language = Language::Custom(CustomLanguage::new(
+            "Koryak",
+            &[Alphabet::Latin],
+            Some("Ŏŏ"),
+            model_directory.path(),
+        ));
+        detector_for_all_languages
+            .languages
+            .insert(custom_language.clone());
+        detector_for_all_languages
+            .languages_with_unique_characters
+            .insert(custom_language.clone());
+
+        let languages = detector_for_all_languages.languages.clone();
+        let filter = |word: &str| {
+            let word_counts =
+                detector_for_all_languages.count_words(&[word.to_string()], &languages);
+            detector_for_all_languages.filter_languages_by_rules(&word_counts, &languages)
+        };
+
+        assert_eq!(filter("ŏla"), hashset!(custom_language));
+        assert_eq!(filter("pão"), hashset!(Portuguese, Vietnamese));
+    }
+
     #[rstest(invalid_str, case(""), case(" \n  \t;"), case("3<856%)§"))]
     fn assert_strings_without_letters_return_no_language(
         detector_for_all_languages: LanguageDetector,
@@ -1477,4 +3657,437 @@ mod tests {
             languages
         );
     }
+
+    #[test]
+    fn assert_multiple_languages_are_detected_in_mixed_language_text() {
+        let text = "Parlez-vous français? Ich spreche Französisch nur ein bisschen. \
+                    A little bit is better than nothing.";
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+        let results = detector.detect_multiple_languages_of(text);
+
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(results[0].language(), French);
+        assert_eq!(
+            &text[results[0].start_index()..results[0].end_index()],
+            "Parlez-vous français? "
+        );
+        assert_eq!(results[0].word_count(), 2);
+
+        assert_eq!(results[1].language(), German);
+        assert_eq!(
+            &text[results[1].start_index()..results[1].end_index()],
+            "Ich spreche Französisch nur ein bisschen. "
+        );
+        assert_eq!(results[1].word_count(), 6);
+
+        assert_eq!(results[2].language(), English);
+        assert_eq!(
+            &text[results[2].start_index()..results[2].end_index()],
+            "A little bit is better than nothing."
+        );
+        assert_eq!(results[2].word_count(), 7);
+    }
+
+    #[test]
+    fn assert_single_language_text_is_returned_as_one_section() {
+        let text = "  This is a plain English sentence without any foreign words.";
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+        let results = detector.detect_multiple_languages_of(text);
+
+        assert_eq!(
+            results,
+            vec![DetectionResult::new(0, text.len(), 10, English)]
+        );
+    }
+
+    #[rstest(invalid_str, case(""), case(" \n  \t;"), case("3<856%)§"))]
+    fn assert_strings_without_letters_return_no_sections(
+        detector_for_all_languages: LanguageDetector,
+        invalid_str: &str,
+    ) {
+        assert_eq!(
+            detector_for_all_languages.detect_multiple_languages_of(invalid_str),
+            vec![]
+        );
+    }
+
+    #[rstest(
+        text,
+        buffer_capacity,
+        case("Alter", 2),
+        case("Weltweit gibt es ungefähr 6.000 Sprachen.", 3),
+        case(
+            "Languages are awesome. They allow people from all over the world to talk \
+             to each other and to share their thoughts, ideas, and stories.",
+            5
+        ),
+        case(
+            "Ich spreche Französisch nur ein bisschen.\n\nIt is better than nothing.",
+            7
+        ),
+        case(" \n  \t;", 1)
+    )]
+    fn assert_confidence_values_of_reader_match_those_of_text(text: &str, buffer_capacity: usize) {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+        let reader = std::io::BufReader::with_capacity(buffer_capacity, text.as_bytes());
+        let confidence_values = detector
+            .compute_language_confidence_values_of_reader(reader)
+            .unwrap();
+        let expected_confidence_values = detector.compute_language_confidence_values(text);
+
+        assert_eq!(confidence_values.len(), expected_confidence_values.len());
+
+        for ((language, confidence), (expected_language, expected_confidence)) in
+            confidence_values.iter().zip(expected_confidence_values)
+        {
+            assert_eq!(*language, expected_language);
+            assert!(
+                approx_eq!(f64, *confidence, expected_confidence, epsilon = 1e-9),
+                "expected confidence {} for language '{:?}', got {}",
+                expected_confidence,
+                language,
+                confidence
+            );
+        }
+    }
+
+    #[test]
+    fn assert_reader_with_invalid_utf8_returns_error() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        let bytes = [b"This is valid text ".as_ref(), &[0xc3, 0x28]].concat();
+        let result = detector.detect_language_of_reader(bytes.as_slice());
+
+        assert!(matches!(
+            result,
+            Err(LinguaError::Io(error)) if error.kind() == std::io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn assert_reading_stops_early_once_margin_is_reached() {
+        let text = "Languages are awesome. They allow people from all over the world to talk \
+                    to each other and to share their thoughts, ideas, and stories. ";
+        let bytes = [text.repeat(10).as_bytes(), &[0xff]].concat();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        assert!(detector
+            .detect_language_of_reader(std::io::BufReader::with_capacity(64, bytes.as_slice()))
+            .is_err());
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_early_stopping_margin(0.01)
+            .build();
+        assert_eq!(
+            detector
+                .detect_language_of_reader(std::io::BufReader::with_capacity(64, bytes.as_slice()))
+                .unwrap(),
+            Some(English)
+        );
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_early_stopping_margin(0.01)
+            .with_long_text_threshold(usize::MAX)
+            .build();
+        assert!(detector
+            .detect_language_of_reader(std::io::BufReader::with_capacity(64, bytes.as_slice()))
+            .is_err());
+    }
+
+    struct CountingReader<'a> {
+        bytes: &'a [u8],
+        read_byte_count: usize,
+    }
+
+    impl Read for CountingReader<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let read_byte_count = self.bytes.read(buffer)?;
+            self.read_byte_count += read_byte_count;
+            Ok(read_byte_count)
+        }
+    }
+
+    #[test]
+    fn assert_reading_stops_after_the_buffer_reaching_the_long_text_threshold() {
+        let text = "Languages are awesome. They allow people from all over the world to talk \
+                    to each other and to share their thoughts, ideas, and stories. "
+            .repeat(100);
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_early_stopping_margin(0.01)
+            .build();
+        let mut reader = CountingReader {
+            bytes: text.as_bytes(),
+            read_byte_count: 0,
+        };
+
+        assert_eq!(
+            detector
+                .detect_language_of_reader(std::io::BufReader::with_capacity(64, &mut reader))
+                .unwrap(),
+            Some(English)
+        );
+        // The complete words of the first buffer contain 57 characters, those of the first
+        // two buffers 123 characters, so the margin is checked for the first time after the
+        // second buffer.
+        assert_eq!(reader.read_byte_count, 128);
+    }
+
+    #[test]
+    fn assert_lower_order_ngrams_are_only_looked_up_for_configured_ngram_lengths() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_ngram_lengths(3..6)
+            .with_long_text_ngram_lengths(3..4)
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+
+        assert_eq!(
+            detector.look_up_smoothed_ngram_probability(&English, &Ngram::new("qxz")),
+            0.0
+        );
+        assert_eq!(detector.model_store.loaded_model_count(1), 0);
+        assert_eq!(detector.model_store.loaded_model_count(2), 0);
+        assert_eq!(detector.model_store.loaded_model_count(3), 1);
+    }
+
+    #[test]
+    fn assert_language_models_can_be_loaded_from_directory() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let english_directory = model_directory.path().join("en");
+        std::fs::create_dir(&english_directory).unwrap();
+
+        let zip_file = std::fs::File::create(english_directory.join("unigrams.json.zip")).unwrap();
+        let mut zip = zip::ZipWriter::new(zip_file);
+        zip.start_file("unigrams.json", zip::write::FileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(
+            &mut zip,
+            br#"{"language":"ENGLISH","ngrams":{"1/4":"a b","1/2":"c"}}"#,
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_model_directory(model_directory.path())
+            .build();
+
+        assert_eq!(
+            detector.look_up_ngram_probability(&English, &Ngram::new("a")),
+            0.25
+        );
+        assert_eq!(
+            detector.look_up_ngram_probability(&English, &Ngram::new("c")),
+            0.5
+        );
+        assert_eq!(
+            detector.look_up_ngram_probability(&English, &Ngram::new("d")),
+            0.0
+        );
+
+        // German is not present in the directory, so the compiled-in model is used
+        assert!(detector.look_up_ngram_probability(&German, &Ngram::new("d")) > 0.0);
+    }
+
+    #[test]
+    fn assert_binary_language_models_take_precedence_in_directory() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let english_directory = model_directory.path().join("en");
+        std::fs::create_dir(&english_directory).unwrap();
+
+        let json_model = TrainingDataLanguageModel::from_json(
+            r#"{"language":"ENGLISH","ngrams":{"1/4":"ab","1/2":"cd"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            english_directory.join("bigrams.bin"),
+            BinaryLanguageModel::to_bytes(&json_model, 2),
+        )
+        .unwrap();
+        std::fs::write(english_directory.join("bigrams.json.zip"), b"corrupt").unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_model_directory(model_directory.path())
+            .build();
+
+        assert!(approx_eq!(
+            f64,
+            detector.look_up_ngram_probability(&English, &Ngram::new("ab")),
+            0.25,
+            epsilon = 0.001
+        ));
+        assert_eq!(
+            detector.look_up_ngram_probability(&English, &Ngram::new("xy")),
+            0.0
+        );
+    }
+
+    #[test]
+    fn assert_only_configured_ngram_lengths_are_preloaded() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_ngram_lengths(2..4)
+            .with_long_text_ngram_lengths(3..4)
+            .with_model_store(Arc::new(ModelStore::new()))
+            .with_preloaded_language_models()
+            .build();
+
+        for ngram_length in 1..6 {
+            let loaded_model_count = detector.model_store.loaded_model_count(ngram_length);
+            let expected_model_count = if (2..4).contains(&ngram_length) { 2 } else { 0 };
+
+            assert_eq!(
+                loaded_model_count, expected_model_count,
+                "unexpected number of loaded {}-gram models",
+                ngram_length
+            );
+        }
+    }
+
+    #[test]
+    fn assert_language_is_detected_in_low_accuracy_mode() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_low_accuracy_mode()
+            .with_model_store(Arc::new(ModelStore::new()))
+            .with_preloaded_language_models()
+            .build();
+
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(English)
+        );
+        assert_eq!(detector.detect_language_of("ab"), None);
+
+        for ngram_length in 1..6 {
+            let expected_model_count = if ngram_length == 3 { 2 } else { 0 };
+            assert_eq!(
+                detector.model_store.loaded_model_count(ngram_length),
+                expected_model_count
+            );
+        }
+    }
+
+    #[rstest(
+        smoothing,
+        case(Smoothing::BackOff),
+        case(Smoothing::PenalizedBackOff { penalty: 0.4 }),
+        case(Smoothing::LinearInterpolation { weight: 0.9 })
+    )]
+    fn assert_unknown_trigrams_do_not_load_other_models_in_low_accuracy_mode(smoothing: Smoothing) {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_low_accuracy_mode()
+            .with_smoothing(smoothing)
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+
+        detector.compute_language_confidence_values("qxzj wvkq zzxq");
+
+        for ngram_length in 1..6 {
+            let expected_model_count = if ngram_length == 3 { 2 } else { 0 };
+            assert_eq!(
+                detector.model_store.loaded_model_count(ngram_length),
+                expected_model_count,
+                "unexpected number of loaded {}-gram models",
+                ngram_length
+            );
+        }
+    }
+
+    #[test]
+    fn assert_texts_are_scored_with_configured_ngram_lengths() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_ngram_lengths(3..4)
+            .with_long_text_threshold(0)
+            .with_model_store(Arc::new(ModelStore::new()))
+            .build();
+
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(English)
+        );
+        assert!(detector.compute_language_confidence_values("ab").is_empty());
+        assert_eq!(detector.model_store.loaded_model_count(1), 0);
+
+        let mut session = detector.start_session();
+        session.feed("languages are awesome");
+        assert_eq!(session.current_language(), Some(English));
+    }
+
+    #[test]
+    fn assert_custom_language_is_detected_alongside_built_in_languages() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let input_file_path = model_directory.path().join("scots.txt");
+        std::fs::write(
+            &input_file_path,
+            "The bairns wis greetin ootside the hoose.\n\
+             Ah dinnae ken whit ye're haverin aboot.\n\
+             It's a braw bricht munelicht nicht the nicht.\n\
+             Gie's a wee keek at yer bonnie lassie's poke o' sweeties.\n\
+             The auld wifie wis awfie crabbit aboot the weather.\n",
+        )
+        .unwrap();
+
+        let scots = CustomLanguage::new("Scots", &[Alphabet::Latin], None, model_directory.path());
+        let custom_language = Language::Custom(scots.clone());
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            &input_file_path,
+            model_directory.path(),
+            &custom_language,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_custom_languages(&[scots])
+            .with_preloaded_language_models()
+            .build();
+
+        assert_eq!(
+            detector.detect_language_of("ah dinnae ken whit the bairns are greetin aboot"),
+            Some(custom_language)
+        );
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(English)
+        );
+    }
+
+    #[test]
+    fn assert_missing_models_of_custom_language_are_reported() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let scots = CustomLanguage::new("Scots", &[Alphabet::Latin], None, model_directory.path());
+
+        let result = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_custom_languages(&[scots])
+            .with_preloaded_language_models()
+            .try_build();
+
+        assert!(matches!(
+            result,
+            Err(LinguaError::LanguageModelNotFound {
+                language: Language::Custom(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn assert_alphabets_of_custom_languages_are_not_attributed_to_single_language() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let input_file_path = model_directory.path().join("pontic.txt");
+        std::fs::write(&input_file_path, "Καλημέρα σας, ντο κάμνετε;\n").unwrap();
+
+        let pontic =
+            CustomLanguage::new("Pontic", &[Alphabet::Greek], None, model_directory.path());
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            &input_file_path,
+            model_directory.path(),
+            &Language::Custom(pontic.clone()),
+            "\\p{L}",
+        )
+        .unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, Greek])
+            .with_custom_languages(&[pontic])
+            .build();
+
+        assert!(detector.one_language_alphabets.is_empty());
+    }
 }
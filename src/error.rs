@@ -0,0 +1,317 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// This enum specifies the errors which can occur when building a
+/// [LanguageDetector](crate::LanguageDetector), loading language models
+/// or writing language model and test data files.
+///
+/// As further errors may be added, the enum is marked as `#[non_exhaustive]`, so matching
+/// on it outside of this crate requires a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LinguaError {
+    /// Less than two languages have been specified to choose from.
+    NotEnoughLanguages,
+
+    /// The given minimum relative distance does not lie in between 0.0 and 0.99.
+    InvalidMinimumRelativeDistance(f64),
+
+    /// The given early stopping margin does not lie in between 0.0 exclusively
+    /// and 1.0 inclusively.
+    InvalidEarlyStoppingMargin(f64),
+
+    /// The given calibration temperature is not a finite number greater than 0.0.
+    InvalidCalibrationTemperature(f64),
+
+    /// The parameter of the given smoothing strategy does not lie in between 0.0 exclusively
+    /// and 1.0 inclusively.
+    InvalidSmoothingParameter(f64),
+
+    /// The given range of ngram lengths is empty or not contained in the range 1..6.
+    InvalidNgramLengthRange(Range<usize>),
+
+    /// The given prior weight of a language is not a finite number greater than 0.0.
+    InvalidPriorWeight(f64),
+
+    /// The given rejection threshold of a language is not a finite number.
+    InvalidRejectionThreshold(f64),
+
+    /// The given input file path is not absolute.
+    InputFilePathNotAbsolute(PathBuf),
+
+    /// The given input file does not exist.
+    InputFileNotFound(PathBuf),
+
+    /// The given input file path does not represent a regular file.
+    InputFilePathNotAFile(PathBuf),
+
+    /// The given input directory does not exist or is not a directory.
+    InputDirectoryNotFound(PathBuf),
+
+    /// The given output directory path is not absolute.
+    OutputDirectoryPathNotAbsolute(PathBuf),
+
+    /// The given output directory does not exist.
+    OutputDirectoryNotFound(PathBuf),
+
+    /// The given output directory path does not represent a directory.
+    OutputDirectoryPathNotADirectory(PathBuf),
+
+    /// The given language model directory does not exist or is not a directory.
+    ModelDirectoryNotFound(PathBuf),
+
+    /// A memory budget has been set on a builder together with a model store.
+    MemoryBudgetWithModelStore,
+
+    /// A model directory has been set on a builder together with a model store.
+    ModelDirectoryWithModelStore,
+
+    /// The given name of a custom language is empty or the name of a built-in language.
+    InvalidCustomLanguageName(String),
+
+    /// No alphabet has been specified for the custom language with the given name.
+    CustomLanguageWithoutAlphabets(String),
+
+    /// A language model file could not be found. The path is the directory in which the file
+    /// has been looked up, or `None` if only the language models compiled into the binary have
+    /// been looked up, such as when no model directory is set and the feature of the language
+    /// is not enabled.
+    LanguageModelNotFound {
+        language: Language,
+        ngram_length: usize,
+        path: Option<PathBuf>,
+    },
+
+    /// A language model file could not be read, unzipped or deserialized.
+    /// The language is `None` if it is not known, such as when a language model file
+    /// is converted or merged.
+    CorruptLanguageModel {
+        language: Option<Language>,
+        ngram_length: usize,
+        reason: String,
+    },
+
+    /// The training text of a language contains more ngrams of the given length than
+    /// language models can be created from, which is `u32::MAX`.
+    TooManyNgrams {
+        language: Language,
+        ngram_length: usize,
+        count: u64,
+    },
+
+    /// The given character class cannot be compiled to a valid regular expression.
+    InvalidCharacterClass(String),
+
+    /// An I/O error occurred while reading or writing files.
+    Io(io::Error),
+}
+
+impl Display for LinguaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LinguaError::NotEnoughLanguages => {
+                write!(
+                    f,
+                    "LanguageDetector needs at least 2 languages to choose from"
+                )
+            }
+            LinguaError::InvalidMinimumRelativeDistance(_) => {
+                write!(
+                    f,
+                    "minimum relative distance must lie in between 0.0 and 0.99"
+                )
+            }
+            LinguaError::InvalidEarlyStoppingMargin(_) => {
+                write!(
+                    f,
+                    "early stopping margin must lie in between 0.0 exclusively and 1.0 inclusively"
+                )
+            }
+            LinguaError::InvalidCalibrationTemperature(_) => {
+                write!(
+                    f,
+                    "calibration temperature must be a finite number greater than 0.0"
+                )
+            }
+            LinguaError::InvalidSmoothingParameter(_) => {
+                write!(
+                    f,
+                    "smoothing parameter must lie in between 0.0 exclusively and 1.0 inclusively"
+                )
+            }
+            LinguaError::InvalidNgramLengthRange(range) => {
+                write!(
+                    f,
+                    "ngram length range {:?} must not be empty and must lie within 1..6",
+                    range
+                )
+            }
+            LinguaError::InvalidPriorWeight(_) => {
+                write!(f, "prior weight must be a finite number greater than 0.0")
+            }
+            LinguaError::InvalidRejectionThreshold(_) => {
+                write!(f, "rejection threshold must be a finite number")
+            }
+            LinguaError::InputFilePathNotAbsolute(path) => {
+                write!(f, "Input file path '{}' is not absolute", path.display())
+            }
+            LinguaError::InputFileNotFound(path) => {
+                write!(f, "Input file '{}' does not exist", path.display())
+            }
+            LinguaError::InputFilePathNotAFile(path) => write!(
+                f,
+                "Input file path '{}' does not represent a regular file",
+                path.display()
+            ),
+            LinguaError::InputDirectoryNotFound(path) => write!(
+                f,
+                "Input directory '{}' does not exist or does not represent a directory",
+                path.display()
+            ),
+            LinguaError::OutputDirectoryPathNotAbsolute(path) => write!(
+                f,
+                "Output directory path '{}' is not absolute",
+                path.display()
+            ),
+            LinguaError::OutputDirectoryNotFound(path) => {
+                write!(f, "Output directory '{}' does not exist", path.display())
+            }
+            LinguaError::OutputDirectoryPathNotADirectory(path) => write!(
+                f,
+                "Output directory path '{}' does not represent a directory",
+                path.display()
+            ),
+            LinguaError::ModelDirectoryNotFound(path) => write!(
+                f,
+                "Language model directory '{}' does not exist or does not represent a directory",
+                path.display()
+            ),
+            LinguaError::MemoryBudgetWithModelStore => write!(
+                f,
+                "memory budget must be set on the model store instead of the builder \
+                 if a model store is used"
+            ),
+            LinguaError::ModelDirectoryWithModelStore => write!(
+                f,
+                "model directory must be set on the model store instead of the builder \
+                 if a model store is used"
+            ),
+            LinguaError::InvalidCustomLanguageName(name) => write!(
+                f,
+                "'{}' is not a valid name for a custom language as it is empty \
+                 or the name of a built-in language",
+                name
+            ),
+            LinguaError::CustomLanguageWithoutAlphabets(name) => write!(
+                f,
+                "Custom language '{}' must be written in at least one alphabet",
+                name
+            ),
+            LinguaError::LanguageModelNotFound {
+                language,
+                ngram_length,
+                path: Some(path),
+            } => write!(
+                f,
+                "The {}-gram language model for {:?} cannot be found in '{}'",
+                ngram_length,
+                language,
+                path.display()
+            ),
+            LinguaError::LanguageModelNotFound {
+                language: Language::Custom(language),
+                ngram_length,
+                path: None,
+            } => write!(
+                f,
+                "The {}-gram language model for {:?} cannot be found \
+                 as custom languages have no compiled-in language models",
+                ngram_length, language
+            ),
+            LinguaError::LanguageModelNotFound {
+                language,
+                ngram_length,
+                path: None,
+            } => write!(
+                f,
+                "The {}-gram language model for {:?} is not compiled in, \
+                 enable the feature '{}' or provide a model directory",
+                ngram_length,
+                language,
+                format!("{:?}", language).to_lowercase()
+            ),
+            LinguaError::CorruptLanguageModel {
+                language: Some(language),
+                ngram_length,
+                reason,
+            } => write!(
+                f,
+                "The {}-gram language model for {:?} cannot be loaded: {}",
+                ngram_length, language, reason
+            ),
+            LinguaError::CorruptLanguageModel {
+                language: None,
+                ngram_length,
+                reason,
+            } => write!(
+                f,
+                "The {}-gram language model cannot be loaded: {}",
+                ngram_length, reason
+            ),
+            LinguaError::TooManyNgrams {
+                language,
+                ngram_length,
+                count,
+            } => write!(
+                f,
+                "The training text for {:?} contains {} {}-grams, \
+                 but language models can be created from at most {} ngrams of each length",
+                language,
+                count,
+                ngram_length,
+                u32::MAX
+            ),
+            LinguaError::InvalidCharacterClass(char_class) => write!(
+                f,
+                "The character class '{}' cannot be compiled to a valid regular expression",
+                char_class
+            ),
+            LinguaError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for LinguaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LinguaError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LinguaError {
+    fn from(error: io::Error) -> Self {
+        LinguaError::Io(error)
+    }
+}
@@ -0,0 +1,297 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+use crate::outcome::{DetectionOutcome, UndeterminedReason};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// The maximum number of ngrams listed per language as top contributing or missing ngrams.
+pub(crate) const MAXIMUM_LISTED_NGRAMS: usize = 10;
+
+/// This struct traces how the language of a text is detected, step by step.
+///
+/// Instances of it are returned by [LanguageDetector::explain]. It can be serialized,
+/// for example to JSON with `serde_json`, in order to inspect wrong detections.
+///
+/// [LanguageDetector::explain]: crate::LanguageDetector::explain
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DetectionExplanation {
+    pub(crate) cleaned_text: String,
+    pub(crate) words: Vec<String>,
+    pub(crate) alphabets: Vec<(Alphabet, u32)>,
+    pub(crate) word_languages: Vec<(Option<Language>, u32)>,
+    pub(crate) character_languages: Vec<(Language, u32)>,
+    pub(crate) rule: RuleDecision,
+    pub(crate) remaining_languages: Vec<Language>,
+    pub(crate) language_evidence: Vec<LanguageEvidence>,
+    pub(crate) confidence_values: Vec<(Language, f64)>,
+    pub(crate) language: Option<Language>,
+    pub(crate) outcome: DetectionOutcome,
+}
+
+impl DetectionExplanation {
+    pub(crate) fn new() -> Self {
+        Self {
+            cleaned_text: String::new(),
+            words: vec![],
+            alphabets: vec![],
+            word_languages: vec![],
+            character_languages: vec![],
+            rule: RuleDecision::NoLetters,
+            remaining_languages: vec![],
+            language_evidence: vec![],
+            confidence_values: vec![],
+            language: None,
+            outcome: DetectionOutcome::Undetermined {
+                reason: UndeterminedReason::EmptyText,
+            },
+        }
+    }
+
+    /// Returns the input text after lowercasing and removing punctuation and numbers.
+    pub fn cleaned_text(&self) -> &str {
+        &self.cleaned_text
+    }
+
+    /// Returns the words the cleaned text has been split into.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Returns the alphabets the words are written in, together with the number of words
+    /// written in each of them, sorted by this number in descending order.
+    pub fn alphabets(&self) -> &[(Alphabet, u32)] {
+        &self.alphabets
+    }
+
+    /// Returns the languages which single words have been attributed to by their unique
+    /// alphabets or characters, together with the number of these words. `None` counts
+    /// the words which could not be attributed to any language.
+    pub fn word_languages(&self) -> &[(Option<Language>, u32)] {
+        &self.word_languages
+    }
+
+    /// Returns the languages which the characters of the words point to,
+    /// together with the number of matching characters.
+    pub fn character_languages(&self) -> &[(Language, u32)] {
+        &self.character_languages
+    }
+
+    /// Returns the decision of the rule-based engine.
+    pub fn rule(&self) -> &RuleDecision {
+        &self.rule
+    }
+
+    /// Returns the languages which remain possible after filtering them by the alphabets
+    /// and characters of the words, sorted alphabetically.
+    pub fn remaining_languages(&self) -> &[Language] {
+        &self.remaining_languages
+    }
+
+    /// Returns the evidence the ngram models provide for each remaining language, in the
+    /// same order as [remaining_languages]. It is empty if the rule-based engine has decided.
+    ///
+    /// [remaining_languages]: DetectionExplanation::remaining_languages
+    pub fn language_evidence(&self) -> &[LanguageEvidence] {
+        &self.language_evidence
+    }
+
+    /// Returns the confidence values as computed by
+    /// [LanguageDetector::compute_language_confidence_values].
+    ///
+    /// [LanguageDetector::compute_language_confidence_values]: crate::LanguageDetector::compute_language_confidence_values
+    pub fn confidence_values(&self) -> &[(Language, f64)] {
+        &self.confidence_values
+    }
+
+    /// Returns the language as detected by [LanguageDetector::detect_language_of].
+    ///
+    /// [LanguageDetector::detect_language_of]: crate::LanguageDetector::detect_language_of
+    pub fn language(&self) -> Option<Language> {
+        self.language.clone()
+    }
+
+    /// Returns the outcome as described by [LanguageDetector::detect_language_outcome_of],
+    /// including the reason why no language has been detected.
+    ///
+    /// [LanguageDetector::detect_language_outcome_of]: crate::LanguageDetector::detect_language_outcome_of
+    pub fn outcome(&self) -> &DetectionOutcome {
+        &self.outcome
+    }
+}
+
+/// This enum describes how the rule-based engine has decided on a text,
+/// before any ngram model is consulted.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RuleDecision {
+    /// The text contains no letters, so no language is detected.
+    NoLetters,
+
+    /// The majority of words has been attributed to a single language
+    /// by their unique alphabets or characters.
+    WordRules { language: Language },
+
+    /// Only a single language remains possible after filtering the languages
+    /// by the alphabets and characters of the words.
+    SingleRemainingLanguage { language: Language },
+
+    /// The rules have not decided, so the remaining languages are scored by the ngram models.
+    Undecided,
+}
+
+/// This struct describes the evidence the ngram models provide for a single language.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LanguageEvidence {
+    pub(crate) language: Language,
+    pub(crate) log_probabilities: Vec<(usize, f64)>,
+    pub(crate) unigram_count: u32,
+    pub(crate) total_log_probability: f64,
+    pub(crate) goodness_of_fit: f64,
+    pub(crate) rejection_threshold: Option<f64>,
+    pub(crate) top_ngrams: Vec<(String, f64)>,
+    pub(crate) missing_ngrams: Vec<String>,
+    pub(crate) missing_ngram_count: usize,
+}
+
+impl LanguageEvidence {
+    /// Returns the language this evidence is about.
+    pub fn language(&self) -> Language {
+        self.language.clone()
+    }
+
+    /// Returns the sum of the log-probabilities of the text's ngrams for each ngram length,
+    /// sorted by ngram length in ascending order. The probabilities are smoothed according
+    /// to the configured strategy.
+    pub fn log_probabilities(&self) -> &[(usize, f64)] {
+        &self.log_probabilities
+    }
+
+    /// Returns the number of the text's unigrams having a probability in this language.
+    /// The summed up log-probabilities are divided by it if unigrams are scored.
+    pub fn unigram_count(&self) -> u32 {
+        self.unigram_count
+    }
+
+    /// Returns the log-probability which the confidence values are computed from.
+    pub fn total_log_probability(&self) -> f64 {
+        self.total_log_probability
+    }
+
+    /// Returns how well the text fits the language model of this language, as computed by
+    /// [LanguageDetector::compute_goodness_of_fit].
+    ///
+    /// [LanguageDetector::compute_goodness_of_fit]: crate::LanguageDetector::compute_goodness_of_fit
+    pub fn goodness_of_fit(&self) -> f64 {
+        self.goodness_of_fit
+    }
+
+    /// Returns the rejection threshold of this language for the length of the text, if any.
+    /// If this language is the most likely one and its goodness of fit falls below the
+    /// threshold, no language is detected.
+    pub fn rejection_threshold(&self) -> Option<f64> {
+        self.rejection_threshold
+    }
+
+    /// Returns the ngrams with the highest probabilities in this language together
+    /// with their log-probabilities, sorted in descending order.
+    pub fn top_ngrams(&self) -> &[(String, f64)] {
+        &self.top_ngrams
+    }
+
+    /// Returns ngrams which are unknown to the language model of their length,
+    /// longest ngrams first. At most ten ngrams are listed.
+    pub fn missing_ngrams(&self) -> &[String] {
+        &self.missing_ngrams
+    }
+
+    /// Returns the total number of ngrams which are unknown to the language model of their length.
+    pub fn missing_ngram_count(&self) -> usize {
+        self.missing_ngram_count
+    }
+}
+
+/// Sorts the given counts in descending order, breaking ties by the keys' debug representation.
+pub(crate) fn sort_counts<T: Debug>(counts: HashMap<T, u32>) -> Vec<(T, u32)> {
+    counts
+        .into_iter()
+        .sorted_by(|(first_key, first_count), (second_key, second_count)| {
+            second_count
+                .cmp(first_count)
+                .then_with(|| format!("{:?}", first_key).cmp(&format!("{:?}", second_key)))
+        })
+        .collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use crate::outcome::DecisionSource;
+    use serde_json::json;
+
+    #[test]
+    fn assert_counts_are_sorted_by_count_and_key() {
+        let counts = hashmap!(Alphabet::Latin => 2, Alphabet::Cyrillic => 2, Alphabet::Greek => 3);
+
+        assert_eq!(
+            sort_counts(counts),
+            vec![
+                (Alphabet::Greek, 3),
+                (Alphabet::Cyrillic, 2),
+                (Alphabet::Latin, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn assert_explanation_is_serialized_to_json() {
+        let mut explanation = DetectionExplanation::new();
+        explanation.cleaned_text = "hello world".to_string();
+        explanation.words = vec!["hello".to_string(), "world".to_string()];
+        explanation.alphabets = vec![(Alphabet::Latin, 2)];
+        explanation.word_languages = vec![(None, 2)];
+        explanation.rule = RuleDecision::SingleRemainingLanguage { language: English };
+        explanation.remaining_languages = vec![English];
+        explanation.confidence_values = vec![(English, 1.0)];
+        explanation.language = Some(English);
+        explanation.outcome = DetectionOutcome::Detected {
+            language: English,
+            source: DecisionSource::Rules,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&explanation).unwrap(),
+            json!({
+                "cleaned_text": "hello world",
+                "words": ["hello", "world"],
+                "alphabets": [["Latin", 2]],
+                "word_languages": [[null, 2]],
+                "character_languages": [],
+                "rule": { "rule": "single_remaining_language", "language": "ENGLISH" },
+                "remaining_languages": ["ENGLISH"],
+                "language_evidence": [],
+                "confidence_values": [["ENGLISH", 1.0]],
+                "language": "ENGLISH",
+                "outcome": { "outcome": "detected", "language": "ENGLISH", "source": "rules" }
+            })
+        );
+    }
+}
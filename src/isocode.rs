@@ -24,303 +24,303 @@ use strum_macros::EnumString;
 #[allow(clippy::upper_case_acronyms)]
 #[strum(ascii_case_insensitive)]
 pub enum IsoCode639_1 {
-    #[cfg(feature = "afrikaans")]
+    #[cfg(any(feature = "afrikaans", feature = "external-models"))]
     /// The ISO 639-1 code for [`Afrikaans`](crate::language::Language::Afrikaans)
     AF,
 
-    #[cfg(feature = "arabic")]
+    #[cfg(any(feature = "arabic", feature = "external-models"))]
     /// The ISO 639-1 code for [`Arabic`](crate::language::Language::Arabic)
     AR,
 
-    #[cfg(feature = "azerbaijani")]
+    #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
     /// The ISO 639-1 code for [`Azerbaijani`](crate::language::Language::Azerbaijani)
     AZ,
 
-    #[cfg(feature = "belarusian")]
+    #[cfg(any(feature = "belarusian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Belarusian`](crate::language::Language::Belarusian)
     BE,
 
-    #[cfg(feature = "bulgarian")]
+    #[cfg(any(feature = "bulgarian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Bulgarian`](crate::language::Language::Bulgarian)
     BG,
 
-    #[cfg(feature = "bengali")]
+    #[cfg(any(feature = "bengali", feature = "external-models"))]
     /// The ISO 639-1 code for [`Bengali`](crate::language::Language::Bengali)
     BN,
 
-    #[cfg(feature = "bosnian")]
+    #[cfg(any(feature = "bosnian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Bosnian`](crate::language::Language::Bosnian)
     BS,
 
-    #[cfg(feature = "catalan")]
+    #[cfg(any(feature = "catalan", feature = "external-models"))]
     /// The ISO 639-1 code for [`Catalan`](crate::language::Language::Catalan)
     CA,
 
-    #[cfg(feature = "czech")]
+    #[cfg(any(feature = "czech", feature = "external-models"))]
     /// The ISO 639-1 code for [`Czech`](crate::language::Language::Czech)
     CS,
 
-    #[cfg(feature = "welsh")]
+    #[cfg(any(feature = "welsh", feature = "external-models"))]
     /// The ISO 639-1 code for [`Welsh`](crate::language::Language::Welsh)
     CY,
 
-    #[cfg(feature = "danish")]
+    #[cfg(any(feature = "danish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Danish`](crate::language::Language::Danish)
     DA,
 
-    #[cfg(feature = "german")]
+    #[cfg(any(feature = "german", feature = "external-models"))]
     /// The ISO 639-1 code for [`German`](crate::language::Language::German)
     DE,
 
-    #[cfg(feature = "greek")]
+    #[cfg(any(feature = "greek", feature = "external-models"))]
     /// The ISO 639-1 code for [`Greek`](crate::language::Language::Greek)
     EL,
 
-    #[cfg(feature = "english")]
+    #[cfg(any(feature = "english", feature = "external-models"))]
     /// The ISO 639-1 code for [`English`](crate::language::Language::English)
     EN,
 
-    #[cfg(feature = "esperanto")]
+    #[cfg(any(feature = "esperanto", feature = "external-models"))]
     /// The ISO 639-1 code for [`Esperanto`](crate::language::Language::Esperanto)
     EO,
 
-    #[cfg(feature = "spanish")]
+    #[cfg(any(feature = "spanish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Spanish`](crate::language::Language::Spanish)
     ES,
 
-    #[cfg(feature = "estonian")]
+    #[cfg(any(feature = "estonian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Estonian`](crate::language::Language::Estonian)
     ET,
 
-    #[cfg(feature = "basque")]
+    #[cfg(any(feature = "basque", feature = "external-models"))]
     /// The ISO 639-1 code for [`Basque`](crate::language::Language::Basque)
     EU,
 
-    #[cfg(feature = "persian")]
+    #[cfg(any(feature = "persian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Persian`](crate::language::Language::Persian)
     FA,
 
-    #[cfg(feature = "finnish")]
+    #[cfg(any(feature = "finnish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Finnish`](crate::language::Language::Finnish)
     FI,
 
-    #[cfg(feature = "french")]
+    #[cfg(any(feature = "french", feature = "external-models"))]
     /// The ISO 639-1 code for [`French`](crate::language::Language::French)
     FR,
 
-    #[cfg(feature = "irish")]
+    #[cfg(any(feature = "irish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Irish`](crate::language::Language::Irish)
     GA,
 
-    #[cfg(feature = "gujarati")]
+    #[cfg(any(feature = "gujarati", feature = "external-models"))]
     /// The ISO 639-1 code for [`Gujarati`](crate::language::Language::Gujarati)
     GU,
 
-    #[cfg(feature = "hebrew")]
+    #[cfg(any(feature = "hebrew", feature = "external-models"))]
     /// The ISO 639-1 code for [`Hebrew`](crate::language::Language::Hebrew)
     HE,
 
-    #[cfg(feature = "hindi")]
+    #[cfg(any(feature = "hindi", feature = "external-models"))]
     /// The ISO 639-1 code for [`Hindi`](crate::language::Language::Hindi)
     HI,
 
-    #[cfg(feature = "croatian")]
+    #[cfg(any(feature = "croatian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Croatian`](crate::language::Language::Croatian)
     HR,
 
-    #[cfg(feature = "hungarian")]
+    #[cfg(any(feature = "hungarian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Hungarian`](crate::language::Language::Hungarian)
     HU,
 
-    #[cfg(feature = "armenian")]
+    #[cfg(any(feature = "armenian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Armenian`](crate::language::Language::Armenian)
     HY,
 
-    #[cfg(feature = "indonesian")]
+    #[cfg(any(feature = "indonesian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Indonesian`](crate::language::Language::Indonesian)
     ID,
 
-    #[cfg(feature = "icelandic")]
+    #[cfg(any(feature = "icelandic", feature = "external-models"))]
     /// The ISO 639-1 code for [`Icelandic`](crate::language::Language::Icelandic)
     IS,
 
-    #[cfg(feature = "italian")]
+    #[cfg(any(feature = "italian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Italian`](crate::language::Language::Italian)
     IT,
 
-    #[cfg(feature = "japanese")]
+    #[cfg(any(feature = "japanese", feature = "external-models"))]
     /// The ISO 639-1 code for [`Japanese`](crate::language::Language::Japanese)
     JA,
 
-    #[cfg(feature = "georgian")]
+    #[cfg(any(feature = "georgian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Georgian`](crate::language::Language::Georgian)
     KA,
 
-    #[cfg(feature = "kazakh")]
+    #[cfg(any(feature = "kazakh", feature = "external-models"))]
     /// The ISO 639-1 code for [`Kazakh`](crate::language::Language::Kazakh)
     KK,
 
-    #[cfg(feature = "korean")]
+    #[cfg(any(feature = "korean", feature = "external-models"))]
     /// The ISO 639-1 code for [`Korean`](crate::language::Language::Korean)
     KO,
 
-    #[cfg(feature = "latin")]
+    #[cfg(any(feature = "latin", feature = "external-models"))]
     /// The ISO 639-1 code for [`Latin`](crate::language::Language::Latin)
     LA,
 
-    #[cfg(feature = "ganda")]
+    #[cfg(any(feature = "ganda", feature = "external-models"))]
     /// The ISO 639-1 code for [`Ganda`](crate::language::Language::Ganda)
     LG,
 
-    #[cfg(feature = "lithuanian")]
+    #[cfg(any(feature = "lithuanian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Lithuanian`](crate::language::Language::Lithuanian)
     LT,
 
-    #[cfg(feature = "latvian")]
+    #[cfg(any(feature = "latvian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Latvian`](crate::language::Language::Latvian)
     LV,
 
-    #[cfg(feature = "maori")]
+    #[cfg(any(feature = "maori", feature = "external-models"))]
     /// The ISO 639-1 code for [`Maori`](crate::language::Language::Maori)
     MI,
 
-    #[cfg(feature = "macedonian")]
+    #[cfg(any(feature = "macedonian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Macedonian`](crate::language::Language::Macedonian)
     MK,
 
-    #[cfg(feature = "mongolian")]
+    #[cfg(any(feature = "mongolian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Mongolian`](crate::language::Language::Mongolian)
     MN,
 
-    #[cfg(feature = "marathi")]
+    #[cfg(any(feature = "marathi", feature = "external-models"))]
     /// The ISO 639-1 code for [`Marathi`](crate::language::Language::Marathi)
     MR,
 
-    #[cfg(feature = "malay")]
+    #[cfg(any(feature = "malay", feature = "external-models"))]
     /// The ISO 639-1 code for [`Malay`](crate::language::Language::Malay)
     MS,
 
-    #[cfg(feature = "bokmal")]
+    #[cfg(any(feature = "bokmal", feature = "external-models"))]
     /// The ISO 639-1 code for [`Norwegian Bokmal`](crate::language::Language::Bokmal)
     NB,
 
-    #[cfg(feature = "dutch")]
+    #[cfg(any(feature = "dutch", feature = "external-models"))]
     /// The ISO 639-1 code for [`Dutch`](crate::language::Language::Dutch)
     NL,
 
-    #[cfg(feature = "nynorsk")]
+    #[cfg(any(feature = "nynorsk", feature = "external-models"))]
     /// The ISO 639-1 code for [`Norwegian Nynorsk`](crate::language::Language::Nynorsk)
     NN,
 
-    #[cfg(feature = "punjabi")]
+    #[cfg(any(feature = "punjabi", feature = "external-models"))]
     /// The ISO 639-1 code for [`Punjabi`](crate::language::Language::Punjabi)
     PA,
 
-    #[cfg(feature = "polish")]
+    #[cfg(any(feature = "polish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Polish`](crate::language::Language::Polish)
     PL,
 
-    #[cfg(feature = "portuguese")]
+    #[cfg(any(feature = "portuguese", feature = "external-models"))]
     /// The ISO 639-1 code for [`Portuguese`](crate::language::Language::Portuguese)
     PT,
 
-    #[cfg(feature = "romanian")]
+    #[cfg(any(feature = "romanian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Romanian`](crate::language::Language::Romanian)
     RO,
 
-    #[cfg(feature = "russian")]
+    #[cfg(any(feature = "russian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Russian`](crate::language::Language::Russian)
     RU,
 
-    #[cfg(feature = "slovak")]
+    #[cfg(any(feature = "slovak", feature = "external-models"))]
     /// The ISO 639-1 code for [`Slovak`](crate::language::Language::Slovak)
     SK,
 
-    #[cfg(feature = "slovene")]
+    #[cfg(any(feature = "slovene", feature = "external-models"))]
     /// The ISO 639-1 code for [`Slovene`](crate::language::Language::Slovene)
     SL,
 
-    #[cfg(feature = "shona")]
+    #[cfg(any(feature = "shona", feature = "external-models"))]
     /// The ISO 639-1 code for [`Shona`](crate::language::Language::Shona)
     SN,
 
-    #[cfg(feature = "somali")]
+    #[cfg(any(feature = "somali", feature = "external-models"))]
     /// The ISO 639-1 code for [`Somali`](crate::language::Language::Somali)
     SO,
 
-    #[cfg(feature = "albanian")]
+    #[cfg(any(feature = "albanian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Albanian`](crate::language::Language::Albanian)
     SQ,
 
-    #[cfg(feature = "serbian")]
+    #[cfg(any(feature = "serbian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Serbian`](crate::language::Language::Serbian)
     SR,
 
-    #[cfg(feature = "sotho")]
+    #[cfg(any(feature = "sotho", feature = "external-models"))]
     /// The ISO 639-1 code for [`Sotho`](crate::language::Language::Sotho)
     ST,
 
-    #[cfg(feature = "swedish")]
+    #[cfg(any(feature = "swedish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Swedish`](crate::language::Language::Swedish)
     SV,
 
-    #[cfg(feature = "swahili")]
+    #[cfg(any(feature = "swahili", feature = "external-models"))]
     /// The ISO 639-1 code for [`Swahili`](crate::language::Language::Swahili)
     SW,
 
-    #[cfg(feature = "tamil")]
+    #[cfg(any(feature = "tamil", feature = "external-models"))]
     /// The ISO 639-1 code for [`Tamil`](crate::language::Language::Tamil)
     TA,
 
-    #[cfg(feature = "telugu")]
+    #[cfg(any(feature = "telugu", feature = "external-models"))]
     /// The ISO 639-1 code for [`Telugu`](crate::language::Language::Telugu)
     TE,
 
-    #[cfg(feature = "thai")]
+    #[cfg(any(feature = "thai", feature = "external-models"))]
     /// The ISO 639-1 code for [`Thai`](crate::language::Language::Thai)
     TH,
 
-    #[cfg(feature = "tagalog")]
+    #[cfg(any(feature = "tagalog", feature = "external-models"))]
     /// The ISO 639-1 code for [`Tagalog`](crate::language::Language::Tagalog)
     TL,
 
-    #[cfg(feature = "tswana")]
+    #[cfg(any(feature = "tswana", feature = "external-models"))]
     /// The ISO 639-1 code for [`Tswana`](crate::language::Language::Tswana)
     TN,
 
-    #[cfg(feature = "turkish")]
+    #[cfg(any(feature = "turkish", feature = "external-models"))]
     /// The ISO 639-1 code for [`Turkish`](crate::language::Language::Turkish)
     TR,
 
-    #[cfg(feature = "tsonga")]
+    #[cfg(any(feature = "tsonga", feature = "external-models"))]
     /// The ISO 639-1 code for [`Tsonga`](crate::language::Language::Tsonga)
     TS,
 
-    #[cfg(feature = "ukrainian")]
+    #[cfg(any(feature = "ukrainian", feature = "external-models"))]
     /// The ISO 639-1 code for [`Ukrainian`](crate::language::Language::Ukrainian)
     UK,
 
-    #[cfg(feature = "urdu")]
+    #[cfg(any(feature = "urdu", feature = "external-models"))]
     /// The ISO 639-1 code for [`Urdu`](crate::language::Language::Urdu)
     UR,
 
-    #[cfg(feature = "vietnamese")]
+    #[cfg(any(feature = "vietnamese", feature = "external-models"))]
     /// The ISO 639-1 code for [`Vietnamese`](crate::language::Language::Vietnamese)
     VI,
 
-    #[cfg(feature = "xhosa")]
+    #[cfg(any(feature = "xhosa", feature = "external-models"))]
     /// The ISO 639-1 code for [`Xhosa`](crate::language::Language::Xhosa)
     XH,
 
-    #[cfg(feature = "yoruba")]
+    #[cfg(any(feature = "yoruba", feature = "external-models"))]
     /// The ISO 639-1 code for [`Yoruba`](crate::language::Language::Yoruba)
     YO,
 
-    #[cfg(feature = "chinese")]
+    #[cfg(any(feature = "chinese", feature = "external-models"))]
     /// The ISO 639-1 code for [`Chinese`](crate::language::Language::Chinese)
     ZH,
 
-    #[cfg(feature = "zulu")]
+    #[cfg(any(feature = "zulu", feature = "external-models"))]
     /// The ISO 639-1 code for [`Zulu`](crate::language::Language::Zulu)
     ZU,
 }
@@ -332,303 +332,303 @@ pub enum IsoCode639_1 {
 #[allow(clippy::upper_case_acronyms)]
 #[strum(ascii_case_insensitive)]
 pub enum IsoCode639_3 {
-    #[cfg(feature = "afrikaans")]
+    #[cfg(any(feature = "afrikaans", feature = "external-models"))]
     /// The ISO 639-3 code for [`Afrikaans`](crate::language::Language::Afrikaans)
     AFR,
 
-    #[cfg(feature = "arabic")]
+    #[cfg(any(feature = "arabic", feature = "external-models"))]
     /// The ISO 639-3 code for [`Arabic`](crate::language::Language::Arabic)
     ARA,
 
-    #[cfg(feature = "azerbaijani")]
+    #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
     /// The ISO 639-3 code for [`Azerbaijani`](crate::language::Language::Azerbaijani)
     AZE,
 
-    #[cfg(feature = "belarusian")]
+    #[cfg(any(feature = "belarusian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Belarusian`](crate::language::Language::Belarusian)
     BEL,
 
-    #[cfg(feature = "bengali")]
+    #[cfg(any(feature = "bengali", feature = "external-models"))]
     /// The ISO 639-3 code for [`Bengali`](crate::language::Language::Bengali)
     BEN,
 
-    #[cfg(feature = "bosnian")]
+    #[cfg(any(feature = "bosnian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Bosnian`](crate::language::Language::Bosnian)
     BOS,
 
-    #[cfg(feature = "bulgarian")]
+    #[cfg(any(feature = "bulgarian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Bulgarian`](crate::language::Language::Bulgarian)
     BUL,
 
-    #[cfg(feature = "catalan")]
+    #[cfg(any(feature = "catalan", feature = "external-models"))]
     /// The ISO 639-3 code for [`Catalan`](crate::language::Language::Catalan)
     CAT,
 
-    #[cfg(feature = "czech")]
+    #[cfg(any(feature = "czech", feature = "external-models"))]
     /// The ISO 639-3 code for [`Czech`](crate::language::Language::Czech)
     CES,
 
-    #[cfg(feature = "welsh")]
+    #[cfg(any(feature = "welsh", feature = "external-models"))]
     /// The ISO 639-3 code for [`Welsh`](crate::language::Language::Welsh)
     CYM,
 
-    #[cfg(feature = "danish")]
+    #[cfg(any(feature = "danish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Danish`](crate::language::Language::Danish)
     DAN,
 
-    #[cfg(feature = "german")]
+    #[cfg(any(feature = "german", feature = "external-models"))]
     /// The ISO 639-3 code for [`German`](crate::language::Language::German)
     DEU,
 
-    #[cfg(feature = "greek")]
+    #[cfg(any(feature = "greek", feature = "external-models"))]
     /// The ISO 639-3 code for [`Greek`](crate::language::Language::Greek)
     ELL,
 
-    #[cfg(feature = "english")]
+    #[cfg(any(feature = "english", feature = "external-models"))]
     /// The ISO 639-3 code for [`English`](crate::language::Language::English)
     ENG,
 
-    #[cfg(feature = "esperanto")]
+    #[cfg(any(feature = "esperanto", feature = "external-models"))]
     /// The ISO 639-3 code for [`Esperanto`](crate::language::Language::Esperanto)
     EPO,
 
-    #[cfg(feature = "estonian")]
+    #[cfg(any(feature = "estonian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Estonian`](crate::language::Language::Estonian)
     EST,
 
-    #[cfg(feature = "basque")]
+    #[cfg(any(feature = "basque", feature = "external-models"))]
     /// The ISO 639-3 code for [`Basque`](crate::language::Language::Basque)
     EUS,
 
-    #[cfg(feature = "persian")]
+    #[cfg(any(feature = "persian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Persian`](crate::language::Language::Persian)
     FAS,
 
-    #[cfg(feature = "finnish")]
+    #[cfg(any(feature = "finnish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Finnish`](crate::language::Language::Finnish)
     FIN,
 
-    #[cfg(feature = "french")]
+    #[cfg(any(feature = "french", feature = "external-models"))]
     /// The ISO 639-3 code for [`French`](crate::language::Language::French)
     FRA,
 
-    #[cfg(feature = "irish")]
+    #[cfg(any(feature = "irish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Irish`](crate::language::Language::Irish)
     GLE,
 
-    #[cfg(feature = "gujarati")]
+    #[cfg(any(feature = "gujarati", feature = "external-models"))]
     /// The ISO 639-3 code for [`Gujarati`](crate::language::Language::Gujarati)
     GUJ,
 
-    #[cfg(feature = "hebrew")]
+    #[cfg(any(feature = "hebrew", feature = "external-models"))]
     /// The ISO 639-3 code for [`Hebrew`](crate::language::Language::Hebrew)
     HEB,
 
-    #[cfg(feature = "hindi")]
+    #[cfg(any(feature = "hindi", feature = "external-models"))]
     /// The ISO 639-3 code for [`Hindi`](crate::language::Language::Hindi)
     HIN,
 
-    #[cfg(feature = "croatian")]
+    #[cfg(any(feature = "croatian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Croatian`](crate::language::Language::Croatian)
     HRV,
 
-    #[cfg(feature = "hungarian")]
+    #[cfg(any(feature = "hungarian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Hungarian`](crate::language::Language::Hungarian)
     HUN,
 
-    #[cfg(feature = "armenian")]
+    #[cfg(any(feature = "armenian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Armenian`](crate::language::Language::Armenian)
     HYE,
 
-    #[cfg(feature = "indonesian")]
+    #[cfg(any(feature = "indonesian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Indonesian`](crate::language::Language::Indonesian)
     IND,
 
-    #[cfg(feature = "icelandic")]
+    #[cfg(any(feature = "icelandic", feature = "external-models"))]
     /// The ISO 639-3 code for [`Icelandic`](crate::language::Language::Icelandic)
     ISL,
 
-    #[cfg(feature = "italian")]
+    #[cfg(any(feature = "italian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Italian`](crate::language::Language::Italian)
     ITA,
 
-    #[cfg(feature = "japanese")]
+    #[cfg(any(feature = "japanese", feature = "external-models"))]
     /// The ISO 639-3 code for [`Japanese`](crate::language::Language::Japanese)
     JPN,
 
-    #[cfg(feature = "georgian")]
+    #[cfg(any(feature = "georgian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Georgian`](crate::language::Language::Georgian)
     KAT,
 
-    #[cfg(feature = "kazakh")]
+    #[cfg(any(feature = "kazakh", feature = "external-models"))]
     /// The ISO 639-3 code for [`Kazakh`](crate::language::Language::Kazakh)
     KAZ,
 
-    #[cfg(feature = "korean")]
+    #[cfg(any(feature = "korean", feature = "external-models"))]
     /// The ISO 639-3 code for [`Korean`](crate::language::Language::Korean)
     KOR,
 
-    #[cfg(feature = "latin")]
+    #[cfg(any(feature = "latin", feature = "external-models"))]
     /// The ISO 639-3 code for [`Latin`](crate::language::Language::Latin)
     LAT,
 
-    #[cfg(feature = "latvian")]
+    #[cfg(any(feature = "latvian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Latvian`](crate::language::Language::Latvian)
     LAV,
 
-    #[cfg(feature = "lithuanian")]
+    #[cfg(any(feature = "lithuanian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Lithuanian`](crate::language::Language::Lithuanian)
     LIT,
 
-    #[cfg(feature = "ganda")]
+    #[cfg(any(feature = "ganda", feature = "external-models"))]
     /// The ISO 639-3 code for [`Ganda`](crate::language::Language::Ganda)
     LUG,
 
-    #[cfg(feature = "marathi")]
+    #[cfg(any(feature = "marathi", feature = "external-models"))]
     /// The ISO 639-3 code for [`Marathi`](crate::language::Language::Marathi)
     MAR,
 
-    #[cfg(feature = "macedonian")]
+    #[cfg(any(feature = "macedonian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Macedonian`](crate::language::Language::Macedonian)
     MKD,
 
-    #[cfg(feature = "mongolian")]
+    #[cfg(any(feature = "mongolian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Mongolian`](crate::language::Language::Mongolian)
     MON,
 
-    #[cfg(feature = "maori")]
+    #[cfg(any(feature = "maori", feature = "external-models"))]
     /// The ISO 639-3 code for [`Maori`](crate::language::Language::Maori)
     MRI,
 
-    #[cfg(feature = "malay")]
+    #[cfg(any(feature = "malay", feature = "external-models"))]
     /// The ISO 639-3 code for [`Malay`](crate::language::Language::Malay)
     MSA,
 
-    #[cfg(feature = "dutch")]
+    #[cfg(any(feature = "dutch", feature = "external-models"))]
     /// The ISO 639-3 code for [`Dutch`](crate::language::Language::Dutch)
     NLD,
 
-    #[cfg(feature = "nynorsk")]
+    #[cfg(any(feature = "nynorsk", feature = "external-models"))]
     /// The ISO 639-3 code for [`Norwegian Nynorsk`](crate::language::Language::Nynorsk)
     NNO,
 
-    #[cfg(feature = "bokmal")]
+    #[cfg(any(feature = "bokmal", feature = "external-models"))]
     /// The ISO 639-3 code for [`Norwegian Bokmal`](crate::language::Language::Bokmal)
     NOB,
 
-    #[cfg(feature = "punjabi")]
+    #[cfg(any(feature = "punjabi", feature = "external-models"))]
     /// The ISO 639-3 code for [`Punjabi`](crate::language::Language::Punjabi)
     PAN,
 
-    #[cfg(feature = "polish")]
+    #[cfg(any(feature = "polish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Polish`](crate::language::Language::Polish)
     POL,
 
-    #[cfg(feature = "portuguese")]
+    #[cfg(any(feature = "portuguese", feature = "external-models"))]
     /// The ISO 639-3 code for [`Portuguese`](crate::language::Language::Portuguese)
     POR,
 
-    #[cfg(feature = "romanian")]
+    #[cfg(any(feature = "romanian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Romanian`](crate::language::Language::Romanian)
     RON,
 
-    #[cfg(feature = "russian")]
+    #[cfg(any(feature = "russian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Russian`](crate::language::Language::Russian)
     RUS,
 
-    #[cfg(feature = "slovak")]
+    #[cfg(any(feature = "slovak", feature = "external-models"))]
     /// The ISO 639-3 code for [`Slovak`](crate::language::Language::Slovak)
     SLK,
 
-    #[cfg(feature = "slovene")]
+    #[cfg(any(feature = "slovene", feature = "external-models"))]
     /// The ISO 639-3 code for [`Slovene`](crate::language::Language::Slovene)
     SLV,
 
-    #[cfg(feature = "shona")]
+    #[cfg(any(feature = "shona", feature = "external-models"))]
     /// The ISO 639-3 code for [`Shona`](crate::language::Language::Shona)
     SNA,
 
-    #[cfg(feature = "somali")]
+    #[cfg(any(feature = "somali", feature = "external-models"))]
     /// The ISO 639-3 code for [`Somali`](crate::language::Language::Somali)
     SOM,
 
-    #[cfg(feature = "sotho")]
+    #[cfg(any(feature = "sotho", feature = "external-models"))]
     /// The ISO 639-3 code for [`Sotho`](crate::language::Language::Sotho)
     SOT,
 
-    #[cfg(feature = "spanish")]
+    #[cfg(any(feature = "spanish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Spanish`](crate::language::Language::Spanish)
     SPA,
 
-    #[cfg(feature = "albanian")]
+    #[cfg(any(feature = "albanian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Albanian`](crate::language::Language::Albanian)
     SQI,
 
-    #[cfg(feature = "serbian")]
+    #[cfg(any(feature = "serbian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Serbian`](crate::language::Language::Serbian)
     SRP,
 
-    #[cfg(feature = "swahili")]
+    #[cfg(any(feature = "swahili", feature = "external-models"))]
     /// The ISO 639-3 code for [`Swahili`](crate::language::Language::Swahili)
     SWA,
 
-    #[cfg(feature = "swedish")]
+    #[cfg(any(feature = "swedish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Swedish`](crate::language::Language::Swedish)
     SWE,
 
-    #[cfg(feature = "tamil")]
+    #[cfg(any(feature = "tamil", feature = "external-models"))]
     /// The ISO 639-3 code for [`Tamil`](crate::language::Language::Tamil)
     TAM,
 
-    #[cfg(feature = "telugu")]
+    #[cfg(any(feature = "telugu", feature = "external-models"))]
     /// The ISO 639-3 code for [`Telugu`](crate::language::Language::Telugu)
     TEL,
 
-    #[cfg(feature = "tagalog")]
+    #[cfg(any(feature = "tagalog", feature = "external-models"))]
     /// The ISO 639-3 code for [`Tagalog`](crate::language::Language::Tagalog)
     TGL,
 
-    #[cfg(feature = "thai")]
+    #[cfg(any(feature = "thai", feature = "external-models"))]
     /// The ISO 639-3 code for [`Thai`](crate::language::Language::Thai)
     THA,
 
-    #[cfg(feature = "tswana")]
+    #[cfg(any(feature = "tswana", feature = "external-models"))]
     /// The ISO 639-3 code for [`Tswana`](crate::language::Language::Tswana)
     TSN,
 
-    #[cfg(feature = "tsonga")]
+    #[cfg(any(feature = "tsonga", feature = "external-models"))]
     /// The ISO 639-3 code for [`Tsonga`](crate::language::Language::Tsonga)
     TSO,
 
-    #[cfg(feature = "turkish")]
+    #[cfg(any(feature = "turkish", feature = "external-models"))]
     /// The ISO 639-3 code for [`Turkish`](crate::language::Language::Turkish)
     TUR,
 
-    #[cfg(feature = "ukrainian")]
+    #[cfg(any(feature = "ukrainian", feature = "external-models"))]
     /// The ISO 639-3 code for [`Ukrainian`](crate::language::Language::Ukrainian)
     UKR,
 
-    #[cfg(feature = "urdu")]
+    #[cfg(any(feature = "urdu", feature = "external-models"))]
     /// The ISO 639-3 code for [`Urdu`](crate::language::Language::Urdu)
     URD,
 
-    #[cfg(feature = "vietnamese")]
+    #[cfg(any(feature = "vietnamese", feature = "external-models"))]
     /// The ISO 639-3 code for [`Vietnamese`](crate::language::Language::Vietnamese)
     VIE,
 
-    #[cfg(feature = "xhosa")]
+    #[cfg(any(feature = "xhosa", feature = "external-models"))]
     /// The ISO 639-3 code for [`Xhosa`](crate::language::Language::Xhosa)
     XHO,
 
-    #[cfg(feature = "yoruba")]
+    #[cfg(any(feature = "yoruba", feature = "external-models"))]
     /// The ISO 639-3 code for [`Yoruba`](crate::language::Language::Yoruba)
     YOR,
 
-    #[cfg(feature = "chinese")]
+    #[cfg(any(feature = "chinese", feature = "external-models"))]
     /// The ISO 639-3 code for [`Chinese`](crate::language::Language::Chinese)
     ZHO,
 
-    #[cfg(feature = "zulu")]
+    #[cfg(any(feature = "zulu", feature = "external-models"))]
     /// The ISO 639-3 code for [`Zulu`](crate::language::Language::Zulu)
     ZUL,
 }
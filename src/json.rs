@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use crate::error::LinguaError;
+use crate::model::BinaryLanguageModel;
 use crate::ngram::Ngram;
 use crate::Language;
 use include_dir::Dir;
@@ -243,254 +245,412 @@ use lingua_yoruba_language_model::YORUBA_MODELS_DIRECTORY;
 #[cfg(feature = "zulu")]
 use lingua_zulu_language_model::ZULU_MODELS_DIRECTORY;
 
-use std::io::{Cursor, Read};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
-pub(crate) fn load_json(language: Language, ngram_length: usize) -> std::io::Result<String> {
+pub(crate) fn load_json(language: Language, ngram_length: usize) -> Result<String, LinguaError> {
+    let file_name = get_language_model_file_name(ngram_length);
+    let zip_file = get_language_models_directory(language.clone())
+        .and_then(|directory| directory.get_file(&file_name))
+        .ok_or_else(|| language_model_not_found_error(&language, ngram_length, None))?;
+    read_zipped_json(Cursor::new(zip_file.contents()))
+        .map_err(|reason| corrupt_language_model_error(&language, ngram_length, reason))
+}
+
+/// Loads the language model from `<directory>/<ISO 639-1 code>/<ngram name>s.json.zip`.
+/// If this file does not exist, the compiled-in language model is loaded instead.
+/// Custom languages are loaded from their own model directory without any fallback.
+pub(crate) fn load_json_from_directory(
+    directory: &Path,
+    language: Language,
+    ngram_length: usize,
+) -> Result<String, LinguaError> {
+    let language_model_directory = get_language_model_directory(directory, &language);
+    let file_path = language_model_directory.join(get_language_model_file_name(ngram_length));
+
+    if !file_path.is_file() {
+        return match language {
+            Language::Custom(_) => Err(language_model_not_found_error(
+                &language,
+                ngram_length,
+                Some(language_model_directory),
+            )),
+            _ => load_json(language.clone(), ngram_length).map_err(|err| match err {
+                LinguaError::LanguageModelNotFound { .. } => language_model_not_found_error(
+                    &language,
+                    ngram_length,
+                    Some(language_model_directory),
+                ),
+                _ => err,
+            }),
+        };
+    }
+
+    let zip_file = File::open(&file_path)
+        .map_err(|err| corrupt_language_model_error(&language, ngram_length, err.to_string()))?;
+    read_zipped_json(zip_file)
+        .map_err(|reason| corrupt_language_model_error(&language, ngram_length, reason))
+}
+
+/// Loads the binary language model from `<directory>/<ISO 639-1 code>/<ngram name>s.bin`.
+/// If this file does not exist, `None` is returned. The file is read into memory in full,
+/// it is not memory-mapped.
+pub(crate) fn load_binary_model_from_directory(
+    directory: &Path,
+    language: Language,
+    ngram_length: usize,
+) -> Result<Option<BinaryLanguageModel>, LinguaError> {
+    let file_path = get_language_model_directory(directory, &language)
+        .join(get_binary_language_model_file_name(ngram_length));
+
+    if !file_path.is_file() {
+        return Ok(None);
+    }
+
+    let bytes = std::fs::read(&file_path)
+        .map_err(|err| corrupt_language_model_error(&language, ngram_length, err.to_string()))?;
+    let model = BinaryLanguageModel::from_bytes(bytes)
+        .map_err(|reason| corrupt_language_model_error(&language, ngram_length, reason))?;
+
+    if model.ngram_length() != ngram_length {
+        return Err(corrupt_language_model_error(
+            &language,
+            ngram_length,
+            format!("file contains {}-grams", model.ngram_length()),
+        ));
+    }
+
+    Ok(Some(model))
+}
+
+/// Checks whether a language model file of the given language and ngram length exists,
+/// looking it up in the same places as the loading functions but without reading it.
+/// Files in `directory` take precedence over the compiled-in language models.
+pub(crate) fn check_language_model_file_exists(
+    directory: Option<&Path>,
+    language: &Language,
+    ngram_length: usize,
+) -> Result<(), LinguaError> {
+    let language_model_directory =
+        directory.map(|directory| get_language_model_directory(directory, language));
+
+    if let Some(language_model_directory) = &language_model_directory {
+        let file_names = [
+            get_binary_language_model_file_name(ngram_length),
+            get_language_model_file_name(ngram_length),
+        ];
+
+        if file_names
+            .iter()
+            .any(|file_name| language_model_directory.join(file_name).is_file())
+        {
+            return Ok(());
+        }
+    }
+
+    let file_name = get_language_model_file_name(ngram_length);
+    get_language_models_directory(language.clone())
+        .and_then(|directory| directory.get_file(&file_name))
+        .map(|_| ())
+        .ok_or_else(|| {
+            language_model_not_found_error(language, ngram_length, language_model_directory)
+        })
+}
+
+/// Returns the directory containing the language models of the given language,
+/// which is the model directory of a custom language or a subdirectory named by
+/// the ISO 639-1 code of a built-in language otherwise.
+fn get_language_model_directory(directory: &Path, language: &Language) -> PathBuf {
+    match (language, language.checked_iso_code_639_1()) {
+        (Language::Custom(language), _) => language.model_directory().to_path_buf(),
+        (_, iso_code) => directory.join(iso_code.map_or_else(String::new, |it| it.to_string())),
+    }
+}
+
+pub(crate) fn get_language_model_file_name(ngram_length: usize) -> String {
+    let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+    format!("{}s.json.zip", ngram_name)
+}
+
+pub(crate) fn get_binary_language_model_file_name(ngram_length: usize) -> String {
     let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
-    let file_path = format!("{}s.json.zip", ngram_name);
-    let directory = get_language_models_directory(language);
-    let zip_file = directory.get_file(file_path).unwrap();
-    let zip_file_reader = Cursor::new(zip_file.contents());
-    let mut archive = ZipArchive::new(zip_file_reader).unwrap();
-    let mut json_file = archive.by_index(0).unwrap();
+    format!("{}s.bin", ngram_name)
+}
+
+pub(crate) fn read_zipped_json<R: Read + Seek>(zip_file_reader: R) -> Result<String, String> {
+    let mut archive = ZipArchive::new(zip_file_reader).map_err(|err| err.to_string())?;
+    let mut json_file = archive.by_index(0).map_err(|err| err.to_string())?;
     let mut json = String::new();
-    json_file.read_to_string(&mut json)?;
+    json_file
+        .read_to_string(&mut json)
+        .map_err(|err| err.to_string())?;
     Ok(json)
 }
 
-fn get_language_models_directory(language: Language) -> Dir<'static> {
+fn corrupt_language_model_error(
+    language: &Language,
+    ngram_length: usize,
+    reason: String,
+) -> LinguaError {
+    LinguaError::CorruptLanguageModel {
+        language: Some(language.clone()),
+        ngram_length,
+        reason,
+    }
+}
+
+fn language_model_not_found_error(
+    language: &Language,
+    ngram_length: usize,
+    path: Option<PathBuf>,
+) -> LinguaError {
+    LinguaError::LanguageModelNotFound {
+        language: language.clone(),
+        ngram_length,
+        path,
+    }
+}
+
+/// Returns the compiled-in language models of the given language, or `None` if they are
+/// not compiled into the binary because the respective feature is not enabled.
+fn get_language_models_directory(language: Language) -> Option<Dir<'static>> {
     match language {
         #[cfg(feature = "afrikaans")]
-        Language::Afrikaans => AFRIKAANS_MODELS_DIRECTORY,
+        Language::Afrikaans => Some(AFRIKAANS_MODELS_DIRECTORY),
 
         #[cfg(feature = "albanian")]
-        Language::Albanian => ALBANIAN_MODELS_DIRECTORY,
+        Language::Albanian => Some(ALBANIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "arabic")]
-        Language::Arabic => ARABIC_MODELS_DIRECTORY,
+        Language::Arabic => Some(ARABIC_MODELS_DIRECTORY),
 
         #[cfg(feature = "armenian")]
-        Language::Armenian => ARMENIAN_MODELS_DIRECTORY,
+        Language::Armenian => Some(ARMENIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "azerbaijani")]
-        Language::Azerbaijani => AZERBAIJANI_MODELS_DIRECTORY,
+        Language::Azerbaijani => Some(AZERBAIJANI_MODELS_DIRECTORY),
 
         #[cfg(feature = "basque")]
-        Language::Basque => BASQUE_MODELS_DIRECTORY,
+        Language::Basque => Some(BASQUE_MODELS_DIRECTORY),
 
         #[cfg(feature = "belarusian")]
-        Language::Belarusian => BELARUSIAN_MODELS_DIRECTORY,
+        Language::Belarusian => Some(BELARUSIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "bengali")]
-        Language::Bengali => BENGALI_MODELS_DIRECTORY,
+        Language::Bengali => Some(BENGALI_MODELS_DIRECTORY),
 
         #[cfg(feature = "bokmal")]
-        Language::Bokmal => BOKMAL_MODELS_DIRECTORY,
+        Language::Bokmal => Some(BOKMAL_MODELS_DIRECTORY),
 
         #[cfg(feature = "bosnian")]
-        Language::Bosnian => BOSNIAN_MODELS_DIRECTORY,
+        Language::Bosnian => Some(BOSNIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "bulgarian")]
-        Language::Bulgarian => BULGARIAN_MODELS_DIRECTORY,
+        Language::Bulgarian => Some(BULGARIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "catalan")]
-        Language::Catalan => CATALAN_MODELS_DIRECTORY,
+        Language::Catalan => Some(CATALAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "chinese")]
-        Language::Chinese => CHINESE_MODELS_DIRECTORY,
+        Language::Chinese => Some(CHINESE_MODELS_DIRECTORY),
 
         #[cfg(feature = "croatian")]
-        Language::Croatian => CROATIAN_MODELS_DIRECTORY,
+        Language::Croatian => Some(CROATIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "czech")]
-        Language::Czech => CZECH_MODELS_DIRECTORY,
+        Language::Czech => Some(CZECH_MODELS_DIRECTORY),
 
         #[cfg(feature = "danish")]
-        Language::Danish => DANISH_MODELS_DIRECTORY,
+        Language::Danish => Some(DANISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "dutch")]
-        Language::Dutch => DUTCH_MODELS_DIRECTORY,
+        Language::Dutch => Some(DUTCH_MODELS_DIRECTORY),
 
         #[cfg(feature = "english")]
-        Language::English => ENGLISH_MODELS_DIRECTORY,
+        Language::English => Some(ENGLISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "esperanto")]
-        Language::Esperanto => ESPERANTO_MODELS_DIRECTORY,
+        Language::Esperanto => Some(ESPERANTO_MODELS_DIRECTORY),
 
         #[cfg(feature = "estonian")]
-        Language::Estonian => ESTONIAN_MODELS_DIRECTORY,
+        Language::Estonian => Some(ESTONIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "finnish")]
-        Language::Finnish => FINNISH_MODELS_DIRECTORY,
+        Language::Finnish => Some(FINNISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "french")]
-        Language::French => FRENCH_MODELS_DIRECTORY,
+        Language::French => Some(FRENCH_MODELS_DIRECTORY),
 
         #[cfg(feature = "ganda")]
-        Language::Ganda => GANDA_MODELS_DIRECTORY,
+        Language::Ganda => Some(GANDA_MODELS_DIRECTORY),
 
         #[cfg(feature = "georgian")]
-        Language::Georgian => GEORGIAN_MODELS_DIRECTORY,
+        Language::Georgian => Some(GEORGIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "german")]
-        Language::German => GERMAN_MODELS_DIRECTORY,
+        Language::German => Some(GERMAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "greek")]
-        Language::Greek => GREEK_MODELS_DIRECTORY,
+        Language::Greek => Some(GREEK_MODELS_DIRECTORY),
 
         #[cfg(feature = "gujarati")]
-        Language::Gujarati => GUJARATI_MODELS_DIRECTORY,
+        Language::Gujarati => Some(GUJARATI_MODELS_DIRECTORY),
 
         #[cfg(feature = "hebrew")]
-        Language::Hebrew => HEBREW_MODELS_DIRECTORY,
+        Language::Hebrew => Some(HEBREW_MODELS_DIRECTORY),
 
         #[cfg(feature = "hindi")]
-        Language::Hindi => HINDI_MODELS_DIRECTORY,
+        Language::Hindi => Some(HINDI_MODELS_DIRECTORY),
 
         #[cfg(feature = "hungarian")]
-        Language::Hungarian => HUNGARIAN_MODELS_DIRECTORY,
+        Language::Hungarian => Some(HUNGARIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "icelandic")]
-        Language::Icelandic => ICELANDIC_MODELS_DIRECTORY,
+        Language::Icelandic => Some(ICELANDIC_MODELS_DIRECTORY),
 
         #[cfg(feature = "indonesian")]
-        Language::Indonesian => INDONESIAN_MODELS_DIRECTORY,
+        Language::Indonesian => Some(INDONESIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "irish")]
-        Language::Irish => IRISH_MODELS_DIRECTORY,
+        Language::Irish => Some(IRISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "italian")]
-        Language::Italian => ITALIAN_MODELS_DIRECTORY,
+        Language::Italian => Some(ITALIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "japanese")]
-        Language::Japanese => JAPANESE_MODELS_DIRECTORY,
+        Language::Japanese => Some(JAPANESE_MODELS_DIRECTORY),
 
         #[cfg(feature = "kazakh")]
-        Language::Kazakh => KAZAKH_MODELS_DIRECTORY,
+        Language::Kazakh => Some(KAZAKH_MODELS_DIRECTORY),
 
         #[cfg(feature = "korean")]
-        Language::Korean => KOREAN_MODELS_DIRECTORY,
+        Language::Korean => Some(KOREAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "latin")]
-        Language::Latin => LATIN_MODELS_DIRECTORY,
+        Language::Latin => Some(LATIN_MODELS_DIRECTORY),
 
         #[cfg(feature = "latvian")]
-        Language::Latvian => LATVIAN_MODELS_DIRECTORY,
+        Language::Latvian => Some(LATVIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "lithuanian")]
-        Language::Lithuanian => LITHUANIAN_MODELS_DIRECTORY,
+        Language::Lithuanian => Some(LITHUANIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "macedonian")]
-        Language::Macedonian => MACEDONIAN_MODELS_DIRECTORY,
+        Language::Macedonian => Some(MACEDONIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "malay")]
-        Language::Malay => MALAY_MODELS_DIRECTORY,
+        Language::Malay => Some(MALAY_MODELS_DIRECTORY),
 
         #[cfg(feature = "maori")]
-        Language::Maori => MAORI_MODELS_DIRECTORY,
+        Language::Maori => Some(MAORI_MODELS_DIRECTORY),
 
         #[cfg(feature = "marathi")]
-        Language::Marathi => MARATHI_MODELS_DIRECTORY,
+        Language::Marathi => Some(MARATHI_MODELS_DIRECTORY),
 
         #[cfg(feature = "mongolian")]
-        Language::Mongolian => MONGOLIAN_MODELS_DIRECTORY,
+        Language::Mongolian => Some(MONGOLIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "nynorsk")]
-        Language::Nynorsk => NYNORSK_MODELS_DIRECTORY,
+        Language::Nynorsk => Some(NYNORSK_MODELS_DIRECTORY),
 
         #[cfg(feature = "persian")]
-        Language::Persian => PERSIAN_MODELS_DIRECTORY,
+        Language::Persian => Some(PERSIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "polish")]
-        Language::Polish => POLISH_MODELS_DIRECTORY,
+        Language::Polish => Some(POLISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "portuguese")]
-        Language::Portuguese => PORTUGUESE_MODELS_DIRECTORY,
+        Language::Portuguese => Some(PORTUGUESE_MODELS_DIRECTORY),
 
         #[cfg(feature = "punjabi")]
-        Language::Punjabi => PUNJABI_MODELS_DIRECTORY,
+        Language::Punjabi => Some(PUNJABI_MODELS_DIRECTORY),
 
         #[cfg(feature = "romanian")]
-        Language::Romanian => ROMANIAN_MODELS_DIRECTORY,
+        Language::Romanian => Some(ROMANIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "russian")]
-        Language::Russian => RUSSIAN_MODELS_DIRECTORY,
+        Language::Russian => Some(RUSSIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "serbian")]
-        Language::Serbian => SERBIAN_MODELS_DIRECTORY,
+        Language::Serbian => Some(SERBIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "shona")]
-        Language::Shona => SHONA_MODELS_DIRECTORY,
+        Language::Shona => Some(SHONA_MODELS_DIRECTORY),
 
         #[cfg(feature = "slovak")]
-        Language::Slovak => SLOVAK_MODELS_DIRECTORY,
+        Language::Slovak => Some(SLOVAK_MODELS_DIRECTORY),
 
         #[cfg(feature = "slovene")]
-        Language::Slovene => SLOVENE_MODELS_DIRECTORY,
+        Language::Slovene => Some(SLOVENE_MODELS_DIRECTORY),
 
         #[cfg(feature = "somali")]
-        Language::Somali => SOMALI_MODELS_DIRECTORY,
+        Language::Somali => Some(SOMALI_MODELS_DIRECTORY),
 
         #[cfg(feature = "sotho")]
-        Language::Sotho => SOTHO_MODELS_DIRECTORY,
+        Language::Sotho => Some(SOTHO_MODELS_DIRECTORY),
 
         #[cfg(feature = "spanish")]
-        Language::Spanish => SPANISH_MODELS_DIRECTORY,
+        Language::Spanish => Some(SPANISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "swahili")]
-        Language::Swahili => SWAHILI_MODELS_DIRECTORY,
+        Language::Swahili => Some(SWAHILI_MODELS_DIRECTORY),
 
         #[cfg(feature = "swedish")]
-        Language::Swedish => SWEDISH_MODELS_DIRECTORY,
+        Language::Swedish => Some(SWEDISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "tagalog")]
-        Language::Tagalog => TAGALOG_MODELS_DIRECTORY,
+        Language::Tagalog => Some(TAGALOG_MODELS_DIRECTORY),
 
         #[cfg(feature = "tamil")]
-        Language::Tamil => TAMIL_MODELS_DIRECTORY,
+        Language::Tamil => Some(TAMIL_MODELS_DIRECTORY),
 
         #[cfg(feature = "telugu")]
-        Language::Telugu => TELUGU_MODELS_DIRECTORY,
+        Language::Telugu => Some(TELUGU_MODELS_DIRECTORY),
 
         #[cfg(feature = "thai")]
-        Language::Thai => THAI_MODELS_DIRECTORY,
+        Language::Thai => Some(THAI_MODELS_DIRECTORY),
 
         #[cfg(feature = "tsonga")]
-        Language::Tsonga => TSONGA_MODELS_DIRECTORY,
+        Language::Tsonga => Some(TSONGA_MODELS_DIRECTORY),
 
         #[cfg(feature = "tswana")]
-        Language::Tswana => TSWANA_MODELS_DIRECTORY,
+        Language::Tswana => Some(TSWANA_MODELS_DIRECTORY),
 
         #[cfg(feature = "turkish")]
-        Language::Turkish => TURKISH_MODELS_DIRECTORY,
+        Language::Turkish => Some(TURKISH_MODELS_DIRECTORY),
 
         #[cfg(feature = "ukrainian")]
-        Language::Ukrainian => UKRAINIAN_MODELS_DIRECTORY,
+        Language::Ukrainian => Some(UKRAINIAN_MODELS_DIRECTORY),
 
         #[cfg(feature = "urdu")]
-        Language::Urdu => URDU_MODELS_DIRECTORY,
+        Language::Urdu => Some(URDU_MODELS_DIRECTORY),
 
         #[cfg(feature = "vietnamese")]
-        Language::Vietnamese => VIETNAMESE_MODELS_DIRECTORY,
+        Language::Vietnamese => Some(VIETNAMESE_MODELS_DIRECTORY),
 
         #[cfg(feature = "welsh")]
-        Language::Welsh => WELSH_MODELS_DIRECTORY,
+        Language::Welsh => Some(WELSH_MODELS_DIRECTORY),
 
         #[cfg(feature = "xhosa")]
-        Language::Xhosa => XHOSA_MODELS_DIRECTORY,
+        Language::Xhosa => Some(XHOSA_MODELS_DIRECTORY),
 
         #[cfg(feature = "yoruba")]
-        Language::Yoruba => YORUBA_MODELS_DIRECTORY,
+        Language::Yoruba => Some(YORUBA_MODELS_DIRECTORY),
 
         #[cfg(feature = "zulu")]
-        Language::Zulu => ZULU_MODELS_DIRECTORY,
+        Language::Zulu => Some(ZULU_MODELS_DIRECTORY),
+
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::custom::CustomLanguage;
     use crate::minify;
 
     const EXPECTED_UNIGRAM_MODEL: &str = r#"
@@ -582,4 +742,36 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), minify(EXPECTED_UNIGRAM_MODEL));
     }
+
+    #[test]
+    fn test_missing_language_models_are_distinguished_from_corrupt_ones() {
+        let directory = tempfile::tempdir().unwrap();
+        let language = Language::Custom(CustomLanguage::new(
+            "Scots",
+            &[Alphabet::Latin],
+            None,
+            directory.path(),
+        ));
+
+        assert!(matches!(
+            check_language_model_file_exists(None, &language, 1),
+            Err(LinguaError::LanguageModelNotFound { path: None, .. })
+        ));
+        assert!(matches!(
+            load_json_from_directory(directory.path(), language.clone(), 1),
+            Err(LinguaError::LanguageModelNotFound { path: Some(path), .. })
+                if path == directory.path()
+        ));
+
+        std::fs::write(directory.path().join("unigrams.json.zip"), "no zip file").unwrap();
+
+        assert!(check_language_model_file_exists(Some(directory.path()), &language, 1).is_ok());
+        assert!(matches!(
+            load_json_from_directory(directory.path(), language, 1),
+            Err(LinguaError::CorruptLanguageModel {
+                ngram_length: 1,
+                ..
+            })
+        ));
+    }
 }
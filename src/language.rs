@@ -15,6 +15,7 @@
  */
 
 use crate::alphabet::Alphabet;
+use crate::custom::CustomLanguage;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -22,237 +23,328 @@ use std::str::FromStr;
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString};
 
-/// This enum specifies the so far 75 supported languages which can be detected by *Lingua*.
+/// The names of all built-in languages, regardless of the Cargo features which are enabled.
+const BUILT_IN_LANGUAGE_NAMES: [&str; 75] = [
+    "Afrikaans",
+    "Albanian",
+    "Arabic",
+    "Armenian",
+    "Azerbaijani",
+    "Basque",
+    "Belarusian",
+    "Bengali",
+    "Bokmal",
+    "Bosnian",
+    "Bulgarian",
+    "Catalan",
+    "Chinese",
+    "Croatian",
+    "Czech",
+    "Danish",
+    "Dutch",
+    "English",
+    "Esperanto",
+    "Estonian",
+    "Finnish",
+    "French",
+    "Ganda",
+    "Georgian",
+    "German",
+    "Greek",
+    "Gujarati",
+    "Hebrew",
+    "Hindi",
+    "Hungarian",
+    "Icelandic",
+    "Indonesian",
+    "Irish",
+    "Italian",
+    "Japanese",
+    "Kazakh",
+    "Korean",
+    "Latin",
+    "Latvian",
+    "Lithuanian",
+    "Macedonian",
+    "Malay",
+    "Maori",
+    "Marathi",
+    "Mongolian",
+    "Nynorsk",
+    "Persian",
+    "Polish",
+    "Portuguese",
+    "Punjabi",
+    "Romanian",
+    "Russian",
+    "Serbian",
+    "Shona",
+    "Slovak",
+    "Slovene",
+    "Somali",
+    "Sotho",
+    "Spanish",
+    "Swahili",
+    "Swedish",
+    "Tagalog",
+    "Tamil",
+    "Telugu",
+    "Thai",
+    "Tsonga",
+    "Tswana",
+    "Turkish",
+    "Ukrainian",
+    "Urdu",
+    "Vietnamese",
+    "Welsh",
+    "Xhosa",
+    "Yoruba",
+    "Zulu",
+];
+
+/// This enum specifies the so far 75 supported languages which can be detected by *Lingua*
+/// as well as custom languages defined by the user.
+///
+/// As further languages may be added, the enum is marked as `#[non_exhaustive]`, so matching
+/// on it outside of this crate requires a wildcard arm. The ISO codes of a [Language::Custom]
+/// language are not defined, so prefer [Language::checked_iso_code_639_1] and
+/// [Language::checked_iso_code_639_3] if custom languages may occur.
 #[derive(
     Clone, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd, EnumString,
 )]
 #[serde(rename_all(serialize = "UPPERCASE", deserialize = "UPPERCASE"))]
 #[strum(ascii_case_insensitive)]
+#[non_exhaustive]
 pub enum Language {
-    #[cfg(feature = "afrikaans")]
+    #[cfg(any(feature = "afrikaans", feature = "external-models"))]
     Afrikaans,
 
-    #[cfg(feature = "albanian")]
+    #[cfg(any(feature = "albanian", feature = "external-models"))]
     Albanian,
 
-    #[cfg(feature = "arabic")]
+    #[cfg(any(feature = "arabic", feature = "external-models"))]
     Arabic,
 
-    #[cfg(feature = "armenian")]
+    #[cfg(any(feature = "armenian", feature = "external-models"))]
     Armenian,
 
-    #[cfg(feature = "azerbaijani")]
+    #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
     Azerbaijani,
 
-    #[cfg(feature = "basque")]
+    #[cfg(any(feature = "basque", feature = "external-models"))]
     Basque,
 
-    #[cfg(feature = "belarusian")]
+    #[cfg(any(feature = "belarusian", feature = "external-models"))]
     Belarusian,
 
-    #[cfg(feature = "bengali")]
+    #[cfg(any(feature = "bengali", feature = "external-models"))]
     Bengali,
 
-    #[cfg(feature = "bokmal")]
+    #[cfg(any(feature = "bokmal", feature = "external-models"))]
     Bokmal,
 
-    #[cfg(feature = "bosnian")]
+    #[cfg(any(feature = "bosnian", feature = "external-models"))]
     Bosnian,
 
-    #[cfg(feature = "bulgarian")]
+    #[cfg(any(feature = "bulgarian", feature = "external-models"))]
     Bulgarian,
 
-    #[cfg(feature = "catalan")]
+    #[cfg(any(feature = "catalan", feature = "external-models"))]
     Catalan,
 
-    #[cfg(feature = "chinese")]
+    #[cfg(any(feature = "chinese", feature = "external-models"))]
     Chinese,
 
-    #[cfg(feature = "croatian")]
+    #[cfg(any(feature = "croatian", feature = "external-models"))]
     Croatian,
 
-    #[cfg(feature = "czech")]
+    #[cfg(any(feature = "czech", feature = "external-models"))]
     Czech,
 
-    #[cfg(feature = "danish")]
+    #[cfg(any(feature = "danish", feature = "external-models"))]
     Danish,
 
-    #[cfg(feature = "dutch")]
+    #[cfg(any(feature = "dutch", feature = "external-models"))]
     Dutch,
 
-    #[cfg(feature = "english")]
+    #[cfg(any(feature = "english", feature = "external-models"))]
     English,
 
-    #[cfg(feature = "esperanto")]
+    #[cfg(any(feature = "esperanto", feature = "external-models"))]
     Esperanto,
 
-    #[cfg(feature = "estonian")]
+    #[cfg(any(feature = "estonian", feature = "external-models"))]
     Estonian,
 
-    #[cfg(feature = "finnish")]
+    #[cfg(any(feature = "finnish", feature = "external-models"))]
     Finnish,
 
-    #[cfg(feature = "french")]
+    #[cfg(any(feature = "french", feature = "external-models"))]
     French,
 
-    #[cfg(feature = "ganda")]
+    #[cfg(any(feature = "ganda", feature = "external-models"))]
     Ganda,
 
-    #[cfg(feature = "georgian")]
+    #[cfg(any(feature = "georgian", feature = "external-models"))]
     Georgian,
 
-    #[cfg(feature = "german")]
+    #[cfg(any(feature = "german", feature = "external-models"))]
     German,
 
-    #[cfg(feature = "greek")]
+    #[cfg(any(feature = "greek", feature = "external-models"))]
     Greek,
 
-    #[cfg(feature = "gujarati")]
+    #[cfg(any(feature = "gujarati", feature = "external-models"))]
     Gujarati,
 
-    #[cfg(feature = "hebrew")]
+    #[cfg(any(feature = "hebrew", feature = "external-models"))]
     Hebrew,
 
-    #[cfg(feature = "hindi")]
+    #[cfg(any(feature = "hindi", feature = "external-models"))]
     Hindi,
 
-    #[cfg(feature = "hungarian")]
+    #[cfg(any(feature = "hungarian", feature = "external-models"))]
     Hungarian,
 
-    #[cfg(feature = "icelandic")]
+    #[cfg(any(feature = "icelandic", feature = "external-models"))]
     Icelandic,
 
-    #[cfg(feature = "indonesian")]
+    #[cfg(any(feature = "indonesian", feature = "external-models"))]
     Indonesian,
 
-    #[cfg(feature = "irish")]
+    #[cfg(any(feature = "irish", feature = "external-models"))]
     Irish,
 
-    #[cfg(feature = "italian")]
+    #[cfg(any(feature = "italian", feature = "external-models"))]
     Italian,
 
-    #[cfg(feature = "japanese")]
+    #[cfg(any(feature = "japanese", feature = "external-models"))]
     Japanese,
 
-    #[cfg(feature = "kazakh")]
+    #[cfg(any(feature = "kazakh", feature = "external-models"))]
     Kazakh,
 
-    #[cfg(feature = "korean")]
+    #[cfg(any(feature = "korean", feature = "external-models"))]
     Korean,
 
-    #[cfg(feature = "latin")]
+    #[cfg(any(feature = "latin", feature = "external-models"))]
     Latin,
 
-    #[cfg(feature = "latvian")]
+    #[cfg(any(feature = "latvian", feature = "external-models"))]
     Latvian,
 
-    #[cfg(feature = "lithuanian")]
+    #[cfg(any(feature = "lithuanian", feature = "external-models"))]
     Lithuanian,
 
-    #[cfg(feature = "macedonian")]
+    #[cfg(any(feature = "macedonian", feature = "external-models"))]
     Macedonian,
 
-    #[cfg(feature = "malay")]
+    #[cfg(any(feature = "malay", feature = "external-models"))]
     Malay,
 
-    #[cfg(feature = "maori")]
+    #[cfg(any(feature = "maori", feature = "external-models"))]
     Maori,
 
-    #[cfg(feature = "marathi")]
+    #[cfg(any(feature = "marathi", feature = "external-models"))]
     Marathi,
 
-    #[cfg(feature = "mongolian")]
+    #[cfg(any(feature = "mongolian", feature = "external-models"))]
     Mongolian,
 
-    #[cfg(feature = "nynorsk")]
+    #[cfg(any(feature = "nynorsk", feature = "external-models"))]
     Nynorsk,
 
-    #[cfg(feature = "persian")]
+    #[cfg(any(feature = "persian", feature = "external-models"))]
     Persian,
 
-    #[cfg(feature = "polish")]
+    #[cfg(any(feature = "polish", feature = "external-models"))]
     Polish,
 
-    #[cfg(feature = "portuguese")]
+    #[cfg(any(feature = "portuguese", feature = "external-models"))]
     Portuguese,
 
-    #[cfg(feature = "punjabi")]
+    #[cfg(any(feature = "punjabi", feature = "external-models"))]
     Punjabi,
 
-    #[cfg(feature = "romanian")]
+    #[cfg(any(feature = "romanian", feature = "external-models"))]
     Romanian,
 
-    #[cfg(feature = "russian")]
+    #[cfg(any(feature = "russian", feature = "external-models"))]
     Russian,
 
-    #[cfg(feature = "serbian")]
+    #[cfg(any(feature = "serbian", feature = "external-models"))]
     Serbian,
 
-    #[cfg(feature = "shona")]
+    #[cfg(any(feature = "shona", feature = "external-models"))]
     Shona,
 
-    #[cfg(feature = "slovak")]
+    #[cfg(any(feature = "slovak", feature = "external-models"))]
     Slovak,
 
-    #[cfg(feature = "slovene")]
+    #[cfg(any(feature = "slovene", feature = "external-models"))]
     Slovene,
 
-    #[cfg(feature = "somali")]
+    #[cfg(any(feature = "somali", feature = "external-models"))]
     Somali,
 
-    #[cfg(feature = "sotho")]
+    #[cfg(any(feature = "sotho", feature = "external-models"))]
     Sotho,
 
-    #[cfg(feature = "spanish")]
+    #[cfg(any(feature = "spanish", feature = "external-models"))]
     Spanish,
 
-    #[cfg(feature = "swahili")]
+    #[cfg(any(feature = "swahili", feature = "external-models"))]
     Swahili,
 
-    #[cfg(feature = "swedish")]
+    #[cfg(any(feature = "swedish", feature = "external-models"))]
     Swedish,
 
-    #[cfg(feature = "tagalog")]
+    #[cfg(any(feature = "tagalog", feature = "external-models"))]
     Tagalog,
 
-    #[cfg(feature = "tamil")]
+    #[cfg(any(feature = "tamil", feature = "external-models"))]
     Tamil,
 
-    #[cfg(feature = "telugu")]
+    #[cfg(any(feature = "telugu", feature = "external-models"))]
     Telugu,
 
-    #[cfg(feature = "thai")]
+    #[cfg(any(feature = "thai", feature = "external-models"))]
     Thai,
 
-    #[cfg(feature = "tsonga")]
+    #[cfg(any(feature = "tsonga", feature = "external-models"))]
     Tsonga,
 
-    #[cfg(feature = "tswana")]
+    #[cfg(any(feature = "tswana", feature = "external-models"))]
     Tswana,
 
-    #[cfg(feature = "turkish")]
+    #[cfg(any(feature = "turkish", feature = "external-models"))]
     Turkish,
 
-    #[cfg(feature = "ukrainian")]
+    #[cfg(any(feature = "ukrainian", feature = "external-models"))]
     Ukrainian,
 
-    #[cfg(feature = "urdu")]
+    #[cfg(any(feature = "urdu", feature = "external-models"))]
     Urdu,
 
-    #[cfg(feature = "vietnamese")]
+    #[cfg(any(feature = "vietnamese", feature = "external-models"))]
     Vietnamese,
 
-    #[cfg(feature = "welsh")]
+    #[cfg(any(feature = "welsh", feature = "external-models"))]
     Welsh,
 
-    #[cfg(feature = "xhosa")]
+    #[cfg(any(feature = "xhosa", feature = "external-models"))]
     Xhosa,
 
-    #[cfg(feature = "yoruba")]
+    #[cfg(any(feature = "yoruba", feature = "external-models"))]
     Yoruba,
 
-    #[cfg(feature = "zulu")]
+    #[cfg(any(feature = "zulu", feature = "external-models"))]
     Zulu,
+
+    /// A language defined by the user which is not part of the built-in languages.
+    /// It is never returned by [Language::all] and similar methods.
+    #[strum(disabled)]
+    Custom(CustomLanguage),
 }
 
 impl Language {
@@ -263,7 +355,7 @@ impl Language {
     pub fn all_spoken_ones() -> HashSet<Language> {
         Language::iter()
             .filter(|it| {
-                if cfg!(feature = "latin") {
+                if cfg!(any(feature = "latin", feature = "external-models")) {
                     it != &Language::from_str("Latin").unwrap()
                 } else {
                     true
@@ -298,770 +390,812 @@ impl Language {
 
     pub fn from_iso_code_639_1(iso_code: &IsoCode639_1) -> Language {
         Language::iter()
-            .find(|it| &it.iso_code_639_1() == iso_code)
+            .find(|it| it.checked_iso_code_639_1().as_ref() == Some(iso_code))
             .unwrap()
     }
 
     pub fn from_iso_code_639_3(iso_code: &IsoCode639_3) -> Language {
         Language::iter()
-            .find(|it| &it.iso_code_639_3() == iso_code)
+            .find(|it| it.checked_iso_code_639_3().as_ref() == Some(iso_code))
             .unwrap()
     }
 
+    /// Returns the ISO 639-1 code of this language.
+    ///
+    /// ⚠ Panics if this is a [Language::Custom] language which has no ISO code.
+    /// Use [checked_iso_code_639_1] to get `None` instead.
+    ///
+    /// [checked_iso_code_639_1]: Language::checked_iso_code_639_1
     pub fn iso_code_639_1(&self) -> IsoCode639_1 {
+        self.checked_iso_code_639_1()
+            .unwrap_or_else(|| panic!("custom language {:?} has no ISO 639-1 code", self))
+    }
+
+    /// Returns the ISO 639-1 code of this language or `None` if this is
+    /// a [Language::Custom] language which has no ISO code.
+    pub fn checked_iso_code_639_1(&self) -> Option<IsoCode639_1> {
         match self {
-            #[cfg(feature = "afrikaans")]
-            Language::Afrikaans => IsoCode639_1::AF,
+            #[cfg(any(feature = "afrikaans", feature = "external-models"))]
+            Language::Afrikaans => Some(IsoCode639_1::AF),
 
-            #[cfg(feature = "albanian")]
-            Language::Albanian => IsoCode639_1::SQ,
+            #[cfg(any(feature = "albanian", feature = "external-models"))]
+            Language::Albanian => Some(IsoCode639_1::SQ),
 
-            #[cfg(feature = "arabic")]
-            Language::Arabic => IsoCode639_1::AR,
+            #[cfg(any(feature = "arabic", feature = "external-models"))]
+            Language::Arabic => Some(IsoCode639_1::AR),
 
-            #[cfg(feature = "armenian")]
-            Language::Armenian => IsoCode639_1::HY,
+            #[cfg(any(feature = "armenian", feature = "external-models"))]
+            Language::Armenian => Some(IsoCode639_1::HY),
 
-            #[cfg(feature = "azerbaijani")]
-            Language::Azerbaijani => IsoCode639_1::AZ,
+            #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
+            Language::Azerbaijani => Some(IsoCode639_1::AZ),
 
-            #[cfg(feature = "basque")]
-            Language::Basque => IsoCode639_1::EU,
+            #[cfg(any(feature = "basque", feature = "external-models"))]
+            Language::Basque => Some(IsoCode639_1::EU),
 
-            #[cfg(feature = "belarusian")]
-            Language::Belarusian => IsoCode639_1::BE,
+            #[cfg(any(feature = "belarusian", feature = "external-models"))]
+            Language::Belarusian => Some(IsoCode639_1::BE),
 
-            #[cfg(feature = "bengali")]
-            Language::Bengali => IsoCode639_1::BN,
+            #[cfg(any(feature = "bengali", feature = "external-models"))]
+            Language::Bengali => Some(IsoCode639_1::BN),
 
-            #[cfg(feature = "bokmal")]
-            Language::Bokmal => IsoCode639_1::NB,
+            #[cfg(any(feature = "bokmal", feature = "external-models"))]
+            Language::Bokmal => Some(IsoCode639_1::NB),
 
-            #[cfg(feature = "bosnian")]
-            Language::Bosnian => IsoCode639_1::BS,
+            #[cfg(any(feature = "bosnian", feature = "external-models"))]
+            Language::Bosnian => Some(IsoCode639_1::BS),
 
-            #[cfg(feature = "bulgarian")]
-            Language::Bulgarian => IsoCode639_1::BG,
+            #[cfg(any(feature = "bulgarian", feature = "external-models"))]
+            Language::Bulgarian => Some(IsoCode639_1::BG),
 
-            #[cfg(feature = "catalan")]
-            Language::Catalan => IsoCode639_1::CA,
+            #[cfg(any(feature = "catalan", feature = "external-models"))]
+            Language::Catalan => Some(IsoCode639_1::CA),
 
-            #[cfg(feature = "chinese")]
-            Language::Chinese => IsoCode639_1::ZH,
+            #[cfg(any(feature = "chinese", feature = "external-models"))]
+            Language::Chinese => Some(IsoCode639_1::ZH),
 
-            #[cfg(feature = "croatian")]
-            Language::Croatian => IsoCode639_1::HR,
+            #[cfg(any(feature = "croatian", feature = "external-models"))]
+            Language::Croatian => Some(IsoCode639_1::HR),
 
-            #[cfg(feature = "czech")]
-            Language::Czech => IsoCode639_1::CS,
+            #[cfg(any(feature = "czech", feature = "external-models"))]
+            Language::Czech => Some(IsoCode639_1::CS),
 
-            #[cfg(feature = "danish")]
-            Language::Danish => IsoCode639_1::DA,
+            #[cfg(any(feature = "danish", feature = "external-models"))]
+            Language::Danish => Some(IsoCode639_1::DA),
 
-            #[cfg(feature = "dutch")]
-            Language::Dutch => IsoCode639_1::NL,
+            #[cfg(any(feature = "dutch", feature = "external-models"))]
+            Language::Dutch => Some(IsoCode639_1::NL),
 
-            #[cfg(feature = "english")]
-            Language::English => IsoCode639_1::EN,
+            #[cfg(any(feature = "english", feature = "external-models"))]
+            Language::English => Some(IsoCode639_1::EN),
 
-            #[cfg(feature = "esperanto")]
-            Language::Esperanto => IsoCode639_1::EO,
+            #[cfg(any(feature = "esperanto", feature = "external-models"))]
+            Language::Esperanto => Some(IsoCode639_1::EO),
 
-            #[cfg(feature = "estonian")]
-            Language::Estonian => IsoCode639_1::ET,
+            #[cfg(any(feature = "estonian", feature = "external-models"))]
+            Language::Estonian => Some(IsoCode639_1::ET),
 
-            #[cfg(feature = "finnish")]
-            Language::Finnish => IsoCode639_1::FI,
+            #[cfg(any(feature = "finnish", feature = "external-models"))]
+            Language::Finnish => Some(IsoCode639_1::FI),
 
-            #[cfg(feature = "french")]
-            Language::French => IsoCode639_1::FR,
+            #[cfg(any(feature = "french", feature = "external-models"))]
+            Language::French => Some(IsoCode639_1::FR),
 
-            #[cfg(feature = "ganda")]
-            Language::Ganda => IsoCode639_1::LG,
+            #[cfg(any(feature = "ganda", feature = "external-models"))]
+            Language::Ganda => Some(IsoCode639_1::LG),
 
-            #[cfg(feature = "georgian")]
-            Language::Georgian => IsoCode639_1::KA,
+            #[cfg(any(feature = "georgian", feature = "external-models"))]
+            Language::Georgian => Some(IsoCode639_1::KA),
 
-            #[cfg(feature = "german")]
-            Language::German => IsoCode639_1::DE,
+            #[cfg(any(feature = "german", feature = "external-models"))]
+            Language::German => Some(IsoCode639_1::DE),
 
-            #[cfg(feature = "greek")]
-            Language::Greek => IsoCode639_1::EL,
+            #[cfg(any(feature = "greek", feature = "external-models"))]
+            Language::Greek => Some(IsoCode639_1::EL),
 
-            #[cfg(feature = "gujarati")]
-            Language::Gujarati => IsoCode639_1::GU,
+            #[cfg(any(feature = "gujarati", feature = "external-models"))]
+            Language::Gujarati => Some(IsoCode639_1::GU),
 
-            #[cfg(feature = "hebrew")]
-            Language::Hebrew => IsoCode639_1::HE,
+            #[cfg(any(feature = "hebrew", feature = "external-models"))]
+            Language::Hebrew => Some(IsoCode639_1::HE),
 
-            #[cfg(feature = "hindi")]
-            Language::Hindi => IsoCode639_1::HI,
+            #[cfg(any(feature = "hindi", feature = "external-models"))]
+            Language::Hindi => Some(IsoCode639_1::HI),
 
-            #[cfg(feature = "hungarian")]
-            Language::Hungarian => IsoCode639_1::HU,
+            #[cfg(any(feature = "hungarian", feature = "external-models"))]
+            Language::Hungarian => Some(IsoCode639_1::HU),
 
-            #[cfg(feature = "icelandic")]
-            Language::Icelandic => IsoCode639_1::IS,
+            #[cfg(any(feature = "icelandic", feature = "external-models"))]
+            Language::Icelandic => Some(IsoCode639_1::IS),
 
-            #[cfg(feature = "indonesian")]
-            Language::Indonesian => IsoCode639_1::ID,
+            #[cfg(any(feature = "indonesian", feature = "external-models"))]
+            Language::Indonesian => Some(IsoCode639_1::ID),
 
-            #[cfg(feature = "irish")]
-            Language::Irish => IsoCode639_1::GA,
+            #[cfg(any(feature = "irish", feature = "external-models"))]
+            Language::Irish => Some(IsoCode639_1::GA),
 
-            #[cfg(feature = "italian")]
-            Language::Italian => IsoCode639_1::IT,
+            #[cfg(any(feature = "italian", feature = "external-models"))]
+            Language::Italian => Some(IsoCode639_1::IT),
 
-            #[cfg(feature = "japanese")]
-            Language::Japanese => IsoCode639_1::JA,
+            #[cfg(any(feature = "japanese", feature = "external-models"))]
+            Language::Japanese => Some(IsoCode639_1::JA),
 
-            #[cfg(feature = "kazakh")]
-            Language::Kazakh => IsoCode639_1::KK,
+            #[cfg(any(feature = "kazakh", feature = "external-models"))]
+            Language::Kazakh => Some(IsoCode639_1::KK),
 
-            #[cfg(feature = "korean")]
-            Language::Korean => IsoCode639_1::KO,
+            #[cfg(any(feature = "korean", feature = "external-models"))]
+            Language::Korean => Some(IsoCode639_1::KO),
 
-            #[cfg(feature = "latin")]
-            Language::Latin => IsoCode639_1::LA,
+            #[cfg(any(feature = "latin", feature = "external-models"))]
+            Language::Latin => Some(IsoCode639_1::LA),
 
-            #[cfg(feature = "latvian")]
-            Language::Latvian => IsoCode639_1::LV,
+            #[cfg(any(feature = "latvian", feature = "external-models"))]
+            Language::Latvian => Some(IsoCode639_1::LV),
 
-            #[cfg(feature = "lithuanian")]
-            Language::Lithuanian => IsoCode639_1::LT,
+            #[cfg(any(feature = "lithuanian", feature = "external-models"))]
+            Language::Lithuanian => Some(IsoCode639_1::LT),
 
-            #[cfg(feature = "macedonian")]
-            Language::Macedonian => IsoCode639_1::MK,
+            #[cfg(any(feature = "macedonian", feature = "external-models"))]
+            Language::Macedonian => Some(IsoCode639_1::MK),
 
-            #[cfg(feature = "malay")]
-            Language::Malay => IsoCode639_1::MS,
+            #[cfg(any(feature = "malay", feature = "external-models"))]
+            Language::Malay => Some(IsoCode639_1::MS),
 
-            #[cfg(feature = "maori")]
-            Language::Maori => IsoCode639_1::MI,
+            #[cfg(any(feature = "maori", feature = "external-models"))]
+            Language::Maori => Some(IsoCode639_1::MI),
 
-            #[cfg(feature = "marathi")]
-            Language::Marathi => IsoCode639_1::MR,
+            #[cfg(any(feature = "marathi", feature = "external-models"))]
+            Language::Marathi => Some(IsoCode639_1::MR),
 
-            #[cfg(feature = "mongolian")]
-            Language::Mongolian => IsoCode639_1::MN,
+            #[cfg(any(feature = "mongolian", feature = "external-models"))]
+            Language::Mongolian => Some(IsoCode639_1::MN),
 
-            #[cfg(feature = "nynorsk")]
-            Language::Nynorsk => IsoCode639_1::NN,
+            #[cfg(any(feature = "nynorsk", feature = "external-models"))]
+            Language::Nynorsk => Some(IsoCode639_1::NN),
 
-            #[cfg(feature = "persian")]
-            Language::Persian => IsoCode639_1::FA,
+            #[cfg(any(feature = "persian", feature = "external-models"))]
+            Language::Persian => Some(IsoCode639_1::FA),
 
-            #[cfg(feature = "polish")]
-            Language::Polish => IsoCode639_1::PL,
+            #[cfg(any(feature = "polish", feature = "external-models"))]
+            Language::Polish => Some(IsoCode639_1::PL),
 
-            #[cfg(feature = "portuguese")]
-            Language::Portuguese => IsoCode639_1::PT,
+            #[cfg(any(feature = "portuguese", feature = "external-models"))]
+            Language::Portuguese => Some(IsoCode639_1::PT),
 
-            #[cfg(feature = "punjabi")]
-            Language::Punjabi => IsoCode639_1::PA,
+            #[cfg(any(feature = "punjabi", feature = "external-models"))]
+            Language::Punjabi => Some(IsoCode639_1::PA),
 
-            #[cfg(feature = "romanian")]
-            Language::Romanian => IsoCode639_1::RO,
+            #[cfg(any(feature = "romanian", feature = "external-models"))]
+            Language::Romanian => Some(IsoCode639_1::RO),
 
-            #[cfg(feature = "russian")]
-            Language::Russian => IsoCode639_1::RU,
+            #[cfg(any(feature = "russian", feature = "external-models"))]
+            Language::Russian => Some(IsoCode639_1::RU),
 
-            #[cfg(feature = "serbian")]
-            Language::Serbian => IsoCode639_1::SR,
+            #[cfg(any(feature = "serbian", feature = "external-models"))]
+            Language::Serbian => Some(IsoCode639_1::SR),
 
-            #[cfg(feature = "shona")]
-            Language::Shona => IsoCode639_1::SN,
+            #[cfg(any(feature = "shona", feature = "external-models"))]
+            Language::Shona => Some(IsoCode639_1::SN),
 
-            #[cfg(feature = "slovak")]
-            Language::Slovak => IsoCode639_1::SK,
+            #[cfg(any(feature = "slovak", feature = "external-models"))]
+            Language::Slovak => Some(IsoCode639_1::SK),
 
-            #[cfg(feature = "slovene")]
-            Language::Slovene => IsoCode639_1::SL,
+            #[cfg(any(feature = "slovene", feature = "external-models"))]
+            Language::Slovene => Some(IsoCode639_1::SL),
 
-            #[cfg(feature = "somali")]
-            Language::Somali => IsoCode639_1::SO,
+            #[cfg(any(feature = "somali", feature = "external-models"))]
+            Language::Somali => Some(IsoCode639_1::SO),
 
-            #[cfg(feature = "sotho")]
-            Language::Sotho => IsoCode639_1::ST,
+            #[cfg(any(feature = "sotho", feature = "external-models"))]
+            Language::Sotho => Some(IsoCode639_1::ST),
 
-            #[cfg(feature = "spanish")]
-            Language::Spanish => IsoCode639_1::ES,
+            #[cfg(any(feature = "spanish", feature = "external-models"))]
+            Language::Spanish => Some(IsoCode639_1::ES),
 
-            #[cfg(feature = "swahili")]
-            Language::Swahili => IsoCode639_1::SW,
+            #[cfg(any(feature = "swahili", feature = "external-models"))]
+            Language::Swahili => Some(IsoCode639_1::SW),
 
-            #[cfg(feature = "swedish")]
-            Language::Swedish => IsoCode639_1::SV,
+            #[cfg(any(feature = "swedish", feature = "external-models"))]
+            Language::Swedish => Some(IsoCode639_1::SV),
 
-            #[cfg(feature = "tagalog")]
-            Language::Tagalog => IsoCode639_1::TL,
+            #[cfg(any(feature = "tagalog", feature = "external-models"))]
+            Language::Tagalog => Some(IsoCode639_1::TL),
 
-            #[cfg(feature = "tamil")]
-            Language::Tamil => IsoCode639_1::TA,
+            #[cfg(any(feature = "tamil", feature = "external-models"))]
+            Language::Tamil => Some(IsoCode639_1::TA),
 
-            #[cfg(feature = "telugu")]
-            Language::Telugu => IsoCode639_1::TE,
+            #[cfg(any(feature = "telugu", feature = "external-models"))]
+            Language::Telugu => Some(IsoCode639_1::TE),
 
-            #[cfg(feature = "thai")]
-            Language::Thai => IsoCode639_1::TH,
+            #[cfg(any(feature = "thai", feature = "external-models"))]
+            Language::Thai => Some(IsoCode639_1::TH),
 
-            #[cfg(feature = "tsonga")]
-            Language::Tsonga => IsoCode639_1::TS,
+            #[cfg(any(feature = "tsonga", feature = "external-models"))]
+            Language::Tsonga => Some(IsoCode639_1::TS),
 
-            #[cfg(feature = "tswana")]
-            Language::Tswana => IsoCode639_1::TN,
+            #[cfg(any(feature = "tswana", feature = "external-models"))]
+            Language::Tswana => Some(IsoCode639_1::TN),
 
-            #[cfg(feature = "turkish")]
-            Language::Turkish => IsoCode639_1::TR,
+            #[cfg(any(feature = "turkish", feature = "external-models"))]
+            Language::Turkish => Some(IsoCode639_1::TR),
 
-            #[cfg(feature = "ukrainian")]
-            Language::Ukrainian => IsoCode639_1::UK,
+            #[cfg(any(feature = "ukrainian", feature = "external-models"))]
+            Language::Ukrainian => Some(IsoCode639_1::UK),
 
-            #[cfg(feature = "urdu")]
-            Language::Urdu => IsoCode639_1::UR,
+            #[cfg(any(feature = "urdu", feature = "external-models"))]
+            Language::Urdu => Some(IsoCode639_1::UR),
 
-            #[cfg(feature = "vietnamese")]
-            Language::Vietnamese => IsoCode639_1::VI,
+            #[cfg(any(feature = "vietnamese", feature = "external-models"))]
+            Language::Vietnamese => Some(IsoCode639_1::VI),
 
-            #[cfg(feature = "welsh")]
-            Language::Welsh => IsoCode639_1::CY,
+            #[cfg(any(feature = "welsh", feature = "external-models"))]
+            Language::Welsh => Some(IsoCode639_1::CY),
 
-            #[cfg(feature = "xhosa")]
-            Language::Xhosa => IsoCode639_1::XH,
+            #[cfg(any(feature = "xhosa", feature = "external-models"))]
+            Language::Xhosa => Some(IsoCode639_1::XH),
 
-            #[cfg(feature = "yoruba")]
-            Language::Yoruba => IsoCode639_1::YO,
+            #[cfg(any(feature = "yoruba", feature = "external-models"))]
+            Language::Yoruba => Some(IsoCode639_1::YO),
 
-            #[cfg(feature = "zulu")]
-            Language::Zulu => IsoCode639_1::ZU,
+            #[cfg(any(feature = "zulu", feature = "external-models"))]
+            Language::Zulu => Some(IsoCode639_1::ZU),
+
+            Language::Custom(_) => None,
         }
     }
 
+    /// Returns the ISO 639-3 code of this language.
+    ///
+    /// ⚠ Panics if this is a [Language::Custom] language which has no ISO code.
+    /// Use [checked_iso_code_639_3] to get `None` instead.
+    ///
+    /// [checked_iso_code_639_3]: Language::checked_iso_code_639_3
     pub fn iso_code_639_3(&self) -> IsoCode639_3 {
+        self.checked_iso_code_639_3()
+            .unwrap_or_else(|| panic!("custom language {:?} has no ISO 639-3 code", self))
+    }
+
+    /// Returns the ISO 639-3 code of this language or `None` if this is
+    /// a [Language::Custom] language which has no ISO code.
+    pub fn checked_iso_code_639_3(&self) -> Option<IsoCode639_3> {
         match self {
-            #[cfg(feature = "afrikaans")]
-            Language::Afrikaans => IsoCode639_3::AFR,
+            #[cfg(any(feature = "afrikaans", feature = "external-models"))]
+            Language::Afrikaans => Some(IsoCode639_3::AFR),
+
+            #[cfg(any(feature = "albanian", feature = "external-models"))]
+            Language::Albanian => Some(IsoCode639_3::SQI),
 
-            #[cfg(feature = "albanian")]
-            Language::Albanian => IsoCode639_3::SQI,
+            #[cfg(any(feature = "arabic", feature = "external-models"))]
+            Language::Arabic => Some(IsoCode639_3::ARA),
 
-            #[cfg(feature = "arabic")]
-            Language::Arabic => IsoCode639_3::ARA,
+            #[cfg(any(feature = "armenian", feature = "external-models"))]
+            Language::Armenian => Some(IsoCode639_3::HYE),
 
-            #[cfg(feature = "armenian")]
-            Language::Armenian => IsoCode639_3::HYE,
+            #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
+            Language::Azerbaijani => Some(IsoCode639_3::AZE),
 
-            #[cfg(feature = "azerbaijani")]
-            Language::Azerbaijani => IsoCode639_3::AZE,
+            #[cfg(any(feature = "basque", feature = "external-models"))]
+            Language::Basque => Some(IsoCode639_3::EUS),
 
-            #[cfg(feature = "basque")]
-            Language::Basque => IsoCode639_3::EUS,
+            #[cfg(any(feature = "belarusian", feature = "external-models"))]
+            Language::Belarusian => Some(IsoCode639_3::BEL),
 
-            #[cfg(feature = "belarusian")]
-            Language::Belarusian => IsoCode639_3::BEL,
+            #[cfg(any(feature = "bengali", feature = "external-models"))]
+            Language::Bengali => Some(IsoCode639_3::BEN),
 
-            #[cfg(feature = "bengali")]
-            Language::Bengali => IsoCode639_3::BEN,
+            #[cfg(any(feature = "bokmal", feature = "external-models"))]
+            Language::Bokmal => Some(IsoCode639_3::NOB),
 
-            #[cfg(feature = "bokmal")]
-            Language::Bokmal => IsoCode639_3::NOB,
+            #[cfg(any(feature = "bosnian", feature = "external-models"))]
+            Language::Bosnian => Some(IsoCode639_3::BOS),
 
-            #[cfg(feature = "bosnian")]
-            Language::Bosnian => IsoCode639_3::BOS,
+            #[cfg(any(feature = "bulgarian", feature = "external-models"))]
+            Language::Bulgarian => Some(IsoCode639_3::BUL),
 
-            #[cfg(feature = "bulgarian")]
-            Language::Bulgarian => IsoCode639_3::BUL,
+            #[cfg(any(feature = "catalan", feature = "external-models"))]
+            Language::Catalan => Some(IsoCode639_3::CAT),
 
-            #[cfg(feature = "catalan")]
-            Language::Catalan => IsoCode639_3::CAT,
+            #[cfg(any(feature = "chinese", feature = "external-models"))]
+            Language::Chinese => Some(IsoCode639_3::ZHO),
 
-            #[cfg(feature = "chinese")]
-            Language::Chinese => IsoCode639_3::ZHO,
+            #[cfg(any(feature = "croatian", feature = "external-models"))]
+            Language::Croatian => Some(IsoCode639_3::HRV),
 
-            #[cfg(feature = "croatian")]
-            Language::Croatian => IsoCode639_3::HRV,
+            #[cfg(any(feature = "czech", feature = "external-models"))]
+            Language::Czech => Some(IsoCode639_3::CES),
 
-            #[cfg(feature = "czech")]
-            Language::Czech => IsoCode639_3::CES,
+            #[cfg(any(feature = "danish", feature = "external-models"))]
+            Language::Danish => Some(IsoCode639_3::DAN),
 
-            #[cfg(feature = "danish")]
-            Language::Danish => IsoCode639_3::DAN,
+            #[cfg(any(feature = "dutch", feature = "external-models"))]
+            Language::Dutch => Some(IsoCode639_3::NLD),
 
-            #[cfg(feature = "dutch")]
-            Language::Dutch => IsoCode639_3::NLD,
+            #[cfg(any(feature = "english", feature = "external-models"))]
+            Language::English => Some(IsoCode639_3::ENG),
 
-            #[cfg(feature = "english")]
-            Language::English => IsoCode639_3::ENG,
+            #[cfg(any(feature = "esperanto", feature = "external-models"))]
+            Language::Esperanto => Some(IsoCode639_3::EPO),
 
-            #[cfg(feature = "esperanto")]
-            Language::Esperanto => IsoCode639_3::EPO,
+            #[cfg(any(feature = "estonian", feature = "external-models"))]
+            Language::Estonian => Some(IsoCode639_3::EST),
 
-            #[cfg(feature = "estonian")]
-            Language::Estonian => IsoCode639_3::EST,
+            #[cfg(any(feature = "finnish", feature = "external-models"))]
+            Language::Finnish => Some(IsoCode639_3::FIN),
 
-            #[cfg(feature = "finnish")]
-            Language::Finnish => IsoCode639_3::FIN,
+            #[cfg(any(feature = "french", feature = "external-models"))]
+            Language::French => Some(IsoCode639_3::FRA),
 
-            #[cfg(feature = "french")]
-            Language::French => IsoCode639_3::FRA,
+            #[cfg(any(feature = "ganda", feature = "external-models"))]
+            Language::Ganda => Some(IsoCode639_3::LUG),
 
-            #[cfg(feature = "ganda")]
-            Language::Ganda => IsoCode639_3::LUG,
+            #[cfg(any(feature = "georgian", feature = "external-models"))]
+            Language::Georgian => Some(IsoCode639_3::KAT),
 
-            #[cfg(feature = "georgian")]
-            Language::Georgian => IsoCode639_3::KAT,
+            #[cfg(any(feature = "german", feature = "external-models"))]
+            Language::German => Some(IsoCode639_3::DEU),
 
-            #[cfg(feature = "german")]
-            Language::German => IsoCode639_3::DEU,
+            #[cfg(any(feature = "greek", feature = "external-models"))]
+            Language::Greek => Some(IsoCode639_3::ELL),
 
-            #[cfg(feature = "greek")]
-            Language::Greek => IsoCode639_3::ELL,
+            #[cfg(any(feature = "gujarati", feature = "external-models"))]
+            Language::Gujarati => Some(IsoCode639_3::GUJ),
 
-            #[cfg(feature = "gujarati")]
-            Language::Gujarati => IsoCode639_3::GUJ,
+            #[cfg(any(feature = "hebrew", feature = "external-models"))]
+            Language::Hebrew => Some(IsoCode639_3::HEB),
 
-            #[cfg(feature = "hebrew")]
-            Language::Hebrew => IsoCode639_3::HEB,
+            #[cfg(any(feature = "hindi", feature = "external-models"))]
+            Language::Hindi => Some(IsoCode639_3::HIN),
 
-            #[cfg(feature = "hindi")]
-            Language::Hindi => IsoCode639_3::HIN,
+            #[cfg(any(feature = "hungarian", feature = "external-models"))]
+            Language::Hungarian => Some(IsoCode639_3::HUN),
 
-            #[cfg(feature = "hungarian")]
-            Language::Hungarian => IsoCode639_3::HUN,
+            #[cfg(any(feature = "icelandic", feature = "external-models"))]
+            Language::Icelandic => Some(IsoCode639_3::ISL),
 
-            #[cfg(feature = "icelandic")]
-            Language::Icelandic => IsoCode639_3::ISL,
+            #[cfg(any(feature = "indonesian", feature = "external-models"))]
+            Language::Indonesian => Some(IsoCode639_3::IND),
 
-            #[cfg(feature = "indonesian")]
-            Language::Indonesian => IsoCode639_3::IND,
+            #[cfg(any(feature = "irish", feature = "external-models"))]
+            Language::Irish => Some(IsoCode639_3::GLE),
 
-            #[cfg(feature = "irish")]
-            Language::Irish => IsoCode639_3::GLE,
+            #[cfg(any(feature = "italian", feature = "external-models"))]
+            Language::Italian => Some(IsoCode639_3::ITA),
 
-            #[cfg(feature = "italian")]
-            Language::Italian => IsoCode639_3::ITA,
+            #[cfg(any(feature = "japanese", feature = "external-models"))]
+            Language::Japanese => Some(IsoCode639_3::JPN),
 
-            #[cfg(feature = "japanese")]
-            Language::Japanese => IsoCode639_3::JPN,
+            #[cfg(any(feature = "kazakh", feature = "external-models"))]
+            Language::Kazakh => Some(IsoCode639_3::KAZ),
 
-            #[cfg(feature = "kazakh")]
-            Language::Kazakh => IsoCode639_3::KAZ,
+            #[cfg(any(feature = "korean", feature = "external-models"))]
+            Language::Korean => Some(IsoCode639_3::KOR),
 
-            #[cfg(feature = "korean")]
-            Language::Korean => IsoCode639_3::KOR,
+            #[cfg(any(feature = "latin", feature = "external-models"))]
+            Language::Latin => Some(IsoCode639_3::LAT),
 
-            #[cfg(feature = "latin")]
-            Language::Latin => IsoCode639_3::LAT,
+            #[cfg(any(feature = "latvian", feature = "external-models"))]
+            Language::Latvian => Some(IsoCode639_3::LAV),
 
-            #[cfg(feature = "latvian")]
-            Language::Latvian => IsoCode639_3::LAV,
+            #[cfg(any(feature = "lithuanian", feature = "external-models"))]
+            Language::Lithuanian => Some(IsoCode639_3::LIT),
 
-            #[cfg(feature = "lithuanian")]
-            Language::Lithuanian => IsoCode639_3::LIT,
+            #[cfg(any(feature = "macedonian", feature = "external-models"))]
+            Language::Macedonian => Some(IsoCode639_3::MKD),
 
-            #[cfg(feature = "macedonian")]
-            Language::Macedonian => IsoCode639_3::MKD,
+            #[cfg(any(feature = "malay", feature = "external-models"))]
+            Language::Malay => Some(IsoCode639_3::MSA),
 
-            #[cfg(feature = "malay")]
-            Language::Malay => IsoCode639_3::MSA,
+            #[cfg(any(feature = "maori", feature = "external-models"))]
+            Language::Maori => Some(IsoCode639_3::MRI),
 
-            #[cfg(feature = "maori")]
-            Language::Maori => IsoCode639_3::MRI,
+            #[cfg(any(feature = "marathi", feature = "external-models"))]
+            Language::Marathi => Some(IsoCode639_3::MAR),
 
-            #[cfg(feature = "marathi")]
-            Language::Marathi => IsoCode639_3::MAR,
+            #[cfg(any(feature = "mongolian", feature = "external-models"))]
+            Language::Mongolian => Some(IsoCode639_3::MON),
 
-            #[cfg(feature = "mongolian")]
-            Language::Mongolian => IsoCode639_3::MON,
+            #[cfg(any(feature = "nynorsk", feature = "external-models"))]
+            Language::Nynorsk => Some(IsoCode639_3::NNO),
 
-            #[cfg(feature = "nynorsk")]
-            Language::Nynorsk => IsoCode639_3::NNO,
+            #[cfg(any(feature = "persian", feature = "external-models"))]
+            Language::Persian => Some(IsoCode639_3::FAS),
 
-            #[cfg(feature = "persian")]
-            Language::Persian => IsoCode639_3::FAS,
+            #[cfg(any(feature = "polish", feature = "external-models"))]
+            Language::Polish => Some(IsoCode639_3::POL),
 
-            #[cfg(feature = "polish")]
-            Language::Polish => IsoCode639_3::POL,
+            #[cfg(any(feature = "portuguese", feature = "external-models"))]
+            Language::Portuguese => Some(IsoCode639_3::POR),
 
-            #[cfg(feature = "portuguese")]
-            Language::Portuguese => IsoCode639_3::POR,
+            #[cfg(any(feature = "punjabi", feature = "external-models"))]
+            Language::Punjabi => Some(IsoCode639_3::PAN),
 
-            #[cfg(feature = "punjabi")]
-            Language::Punjabi => IsoCode639_3::PAN,
+            #[cfg(any(feature = "romanian", feature = "external-models"))]
+            Language::Romanian => Some(IsoCode639_3::RON),
 
-            #[cfg(feature = "romanian")]
-            Language::Romanian => IsoCode639_3::RON,
+            #[cfg(any(feature = "russian", feature = "external-models"))]
+            Language::Russian => Some(IsoCode639_3::RUS),
 
-            #[cfg(feature = "russian")]
-            Language::Russian => IsoCode639_3::RUS,
+            #[cfg(any(feature = "serbian", feature = "external-models"))]
+            Language::Serbian => Some(IsoCode639_3::SRP),
 
-            #[cfg(feature = "serbian")]
-            Language::Serbian => IsoCode639_3::SRP,
+            #[cfg(any(feature = "shona", feature = "external-models"))]
+            Language::Shona => Some(IsoCode639_3::SNA),
 
-            #[cfg(feature = "shona")]
-            Language::Shona => IsoCode639_3::SNA,
+            #[cfg(any(feature = "slovak", feature = "external-models"))]
+            Language::Slovak => Some(IsoCode639_3::SLK),
 
-            #[cfg(feature = "slovak")]
-            Language::Slovak => IsoCode639_3::SLK,
+            #[cfg(any(feature = "slovene", feature = "external-models"))]
+            Language::Slovene => Some(IsoCode639_3::SLV),
 
-            #[cfg(feature = "slovene")]
-            Language::Slovene => IsoCode639_3::SLV,
+            #[cfg(any(feature = "somali", feature = "external-models"))]
+            Language::Somali => Some(IsoCode639_3::SOM),
 
-            #[cfg(feature = "somali")]
-            Language::Somali => IsoCode639_3::SOM,
+            #[cfg(any(feature = "sotho", feature = "external-models"))]
+            Language::Sotho => Some(IsoCode639_3::SOT),
 
-            #[cfg(feature = "sotho")]
-            Language::Sotho => IsoCode639_3::SOT,
+            #[cfg(any(feature = "spanish", feature = "external-models"))]
+            Language::Spanish => Some(IsoCode639_3::SPA),
 
-            #[cfg(feature = "spanish")]
-            Language::Spanish => IsoCode639_3::SPA,
+            #[cfg(any(feature = "swahili", feature = "external-models"))]
+            Language::Swahili => Some(IsoCode639_3::SWA),
 
-            #[cfg(feature = "swahili")]
-            Language::Swahili => IsoCode639_3::SWA,
+            #[cfg(any(feature = "swedish", feature = "external-models"))]
+            Language::Swedish => Some(IsoCode639_3::SWE),
 
-            #[cfg(feature = "swedish")]
-            Language::Swedish => IsoCode639_3::SWE,
+            #[cfg(any(feature = "tagalog", feature = "external-models"))]
+            Language::Tagalog => Some(IsoCode639_3::TGL),
 
-            #[cfg(feature = "tagalog")]
-            Language::Tagalog => IsoCode639_3::TGL,
+            #[cfg(any(feature = "tamil", feature = "external-models"))]
+            Language::Tamil => Some(IsoCode639_3::TAM),
 
-            #[cfg(feature = "tamil")]
-            Language::Tamil => IsoCode639_3::TAM,
+            #[cfg(any(feature = "telugu", feature = "external-models"))]
+            Language::Telugu => Some(IsoCode639_3::TEL),
 
-            #[cfg(feature = "telugu")]
-            Language::Telugu => IsoCode639_3::TEL,
+            #[cfg(any(feature = "thai", feature = "external-models"))]
+            Language::Thai => Some(IsoCode639_3::THA),
 
-            #[cfg(feature = "thai")]
-            Language::Thai => IsoCode639_3::THA,
+            #[cfg(any(feature = "tsonga", feature = "external-models"))]
+            Language::Tsonga => Some(IsoCode639_3::TSO),
 
-            #[cfg(feature = "tsonga")]
-            Language::Tsonga => IsoCode639_3::TSO,
+            #[cfg(any(feature = "tswana", feature = "external-models"))]
+            Language::Tswana => Some(IsoCode639_3::TSN),
 
-            #[cfg(feature = "tswana")]
-            Language::Tswana => IsoCode639_3::TSN,
+            #[cfg(any(feature = "turkish", feature = "external-models"))]
+            Language::Turkish => Some(IsoCode639_3::TUR),
 
-            #[cfg(feature = "turkish")]
-            Language::Turkish => IsoCode639_3::TUR,
+            #[cfg(any(feature = "ukrainian", feature = "external-models"))]
+            Language::Ukrainian => Some(IsoCode639_3::UKR),
 
-            #[cfg(feature = "ukrainian")]
-            Language::Ukrainian => IsoCode639_3::UKR,
+            #[cfg(any(feature = "urdu", feature = "external-models"))]
+            Language::Urdu => Some(IsoCode639_3::URD),
 
-            #[cfg(feature = "urdu")]
-            Language::Urdu => IsoCode639_3::URD,
+            #[cfg(any(feature = "vietnamese", feature = "external-models"))]
+            Language::Vietnamese => Some(IsoCode639_3::VIE),
 
-            #[cfg(feature = "vietnamese")]
-            Language::Vietnamese => IsoCode639_3::VIE,
+            #[cfg(any(feature = "welsh", feature = "external-models"))]
+            Language::Welsh => Some(IsoCode639_3::CYM),
 
-            #[cfg(feature = "welsh")]
-            Language::Welsh => IsoCode639_3::CYM,
+            #[cfg(any(feature = "xhosa", feature = "external-models"))]
+            Language::Xhosa => Some(IsoCode639_3::XHO),
 
-            #[cfg(feature = "xhosa")]
-            Language::Xhosa => IsoCode639_3::XHO,
+            #[cfg(any(feature = "yoruba", feature = "external-models"))]
+            Language::Yoruba => Some(IsoCode639_3::YOR),
 
-            #[cfg(feature = "yoruba")]
-            Language::Yoruba => IsoCode639_3::YOR,
+            #[cfg(any(feature = "zulu", feature = "external-models"))]
+            Language::Zulu => Some(IsoCode639_3::ZUL),
 
-            #[cfg(feature = "zulu")]
-            Language::Zulu => IsoCode639_3::ZUL,
+            Language::Custom(_) => None,
         }
     }
 
     pub(crate) fn alphabets(&self) -> HashSet<Alphabet> {
         match self {
-            #[cfg(feature = "afrikaans")]
+            #[cfg(any(feature = "afrikaans", feature = "external-models"))]
             Language::Afrikaans => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "albanian")]
+            #[cfg(any(feature = "albanian", feature = "external-models"))]
             Language::Albanian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "azerbaijani")]
+            #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
             Language::Azerbaijani => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "basque")]
+            #[cfg(any(feature = "basque", feature = "external-models"))]
             Language::Basque => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "bokmal")]
+            #[cfg(any(feature = "bokmal", feature = "external-models"))]
             Language::Bokmal => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "bosnian")]
+            #[cfg(any(feature = "bosnian", feature = "external-models"))]
             Language::Bosnian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "catalan")]
+            #[cfg(any(feature = "catalan", feature = "external-models"))]
             Language::Catalan => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "croatian")]
+            #[cfg(any(feature = "croatian", feature = "external-models"))]
             Language::Croatian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "czech")]
+            #[cfg(any(feature = "czech", feature = "external-models"))]
             Language::Czech => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "danish")]
+            #[cfg(any(feature = "danish", feature = "external-models"))]
             Language::Danish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "dutch")]
+            #[cfg(any(feature = "dutch", feature = "external-models"))]
             Language::Dutch => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "english")]
+            #[cfg(any(feature = "english", feature = "external-models"))]
             Language::English => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "esperanto")]
+            #[cfg(any(feature = "esperanto", feature = "external-models"))]
             Language::Esperanto => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "estonian")]
+            #[cfg(any(feature = "estonian", feature = "external-models"))]
             Language::Estonian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "finnish")]
+            #[cfg(any(feature = "finnish", feature = "external-models"))]
             Language::Finnish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "french")]
+            #[cfg(any(feature = "french", feature = "external-models"))]
             Language::French => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "ganda")]
+            #[cfg(any(feature = "ganda", feature = "external-models"))]
             Language::Ganda => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "german")]
+            #[cfg(any(feature = "german", feature = "external-models"))]
             Language::German => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "hungarian")]
+            #[cfg(any(feature = "hungarian", feature = "external-models"))]
             Language::Hungarian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "icelandic")]
+            #[cfg(any(feature = "icelandic", feature = "external-models"))]
             Language::Icelandic => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "indonesian")]
+            #[cfg(any(feature = "indonesian", feature = "external-models"))]
             Language::Indonesian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "irish")]
+            #[cfg(any(feature = "irish", feature = "external-models"))]
             Language::Irish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "italian")]
+            #[cfg(any(feature = "italian", feature = "external-models"))]
             Language::Italian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "latin")]
+            #[cfg(any(feature = "latin", feature = "external-models"))]
             Language::Latin => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "latvian")]
+            #[cfg(any(feature = "latvian", feature = "external-models"))]
             Language::Latvian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "lithuanian")]
+            #[cfg(any(feature = "lithuanian", feature = "external-models"))]
             Language::Lithuanian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "malay")]
+            #[cfg(any(feature = "malay", feature = "external-models"))]
             Language::Malay => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "maori")]
+            #[cfg(any(feature = "maori", feature = "external-models"))]
             Language::Maori => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "nynorsk")]
+            #[cfg(any(feature = "nynorsk", feature = "external-models"))]
             Language::Nynorsk => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "polish")]
+            #[cfg(any(feature = "polish", feature = "external-models"))]
             Language::Polish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "portuguese")]
+            #[cfg(any(feature = "portuguese", feature = "external-models"))]
             Language::Portuguese => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "romanian")]
+            #[cfg(any(feature = "romanian", feature = "external-models"))]
             Language::Romanian => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "shona")]
+            #[cfg(any(feature = "shona", feature = "external-models"))]
             Language::Shona => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "slovak")]
+            #[cfg(any(feature = "slovak", feature = "external-models"))]
             Language::Slovak => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "slovene")]
+            #[cfg(any(feature = "slovene", feature = "external-models"))]
             Language::Slovene => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "somali")]
+            #[cfg(any(feature = "somali", feature = "external-models"))]
             Language::Somali => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "sotho")]
+            #[cfg(any(feature = "sotho", feature = "external-models"))]
             Language::Sotho => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "spanish")]
+            #[cfg(any(feature = "spanish", feature = "external-models"))]
             Language::Spanish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "swahili")]
+            #[cfg(any(feature = "swahili", feature = "external-models"))]
             Language::Swahili => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "swedish")]
+            #[cfg(any(feature = "swedish", feature = "external-models"))]
             Language::Swedish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "tagalog")]
+            #[cfg(any(feature = "tagalog", feature = "external-models"))]
             Language::Tagalog => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "tsonga")]
+            #[cfg(any(feature = "tsonga", feature = "external-models"))]
             Language::Tsonga => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "tswana")]
+            #[cfg(any(feature = "tswana", feature = "external-models"))]
             Language::Tswana => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "turkish")]
+            #[cfg(any(feature = "turkish", feature = "external-models"))]
             Language::Turkish => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "vietnamese")]
+            #[cfg(any(feature = "vietnamese", feature = "external-models"))]
             Language::Vietnamese => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "welsh")]
+            #[cfg(any(feature = "welsh", feature = "external-models"))]
             Language::Welsh => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "xhosa")]
+            #[cfg(any(feature = "xhosa", feature = "external-models"))]
             Language::Xhosa => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "yoruba")]
+            #[cfg(any(feature = "yoruba", feature = "external-models"))]
             Language::Yoruba => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "zulu")]
+            #[cfg(any(feature = "zulu", feature = "external-models"))]
             Language::Zulu => hashset!(Alphabet::Latin),
 
-            #[cfg(feature = "belarusian")]
+            #[cfg(any(feature = "belarusian", feature = "external-models"))]
             Language::Belarusian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "bulgarian")]
+            #[cfg(any(feature = "bulgarian", feature = "external-models"))]
             Language::Bulgarian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "kazakh")]
+            #[cfg(any(feature = "kazakh", feature = "external-models"))]
             Language::Kazakh => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "macedonian")]
+            #[cfg(any(feature = "macedonian", feature = "external-models"))]
             Language::Macedonian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "mongolian")]
+            #[cfg(any(feature = "mongolian", feature = "external-models"))]
             Language::Mongolian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "russian")]
+            #[cfg(any(feature = "russian", feature = "external-models"))]
             Language::Russian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "serbian")]
+            #[cfg(any(feature = "serbian", feature = "external-models"))]
             Language::Serbian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "ukrainian")]
+            #[cfg(any(feature = "ukrainian", feature = "external-models"))]
             Language::Ukrainian => hashset!(Alphabet::Cyrillic),
 
-            #[cfg(feature = "arabic")]
+            #[cfg(any(feature = "arabic", feature = "external-models"))]
             Language::Arabic => hashset!(Alphabet::Arabic),
 
-            #[cfg(feature = "persian")]
+            #[cfg(any(feature = "persian", feature = "external-models"))]
             Language::Persian => hashset!(Alphabet::Arabic),
 
-            #[cfg(feature = "urdu")]
+            #[cfg(any(feature = "urdu", feature = "external-models"))]
             Language::Urdu => hashset!(Alphabet::Arabic),
 
-            #[cfg(feature = "hindi")]
+            #[cfg(any(feature = "hindi", feature = "external-models"))]
             Language::Hindi => hashset!(Alphabet::Devanagari),
 
-            #[cfg(feature = "marathi")]
+            #[cfg(any(feature = "marathi", feature = "external-models"))]
             Language::Marathi => hashset!(Alphabet::Devanagari),
 
-            #[cfg(feature = "armenian")]
+            #[cfg(any(feature = "armenian", feature = "external-models"))]
             Language::Armenian => hashset!(Alphabet::Armenian),
 
-            #[cfg(feature = "bengali")]
+            #[cfg(any(feature = "bengali", feature = "external-models"))]
             Language::Bengali => hashset!(Alphabet::Bengali),
 
-            #[cfg(feature = "chinese")]
+            #[cfg(any(feature = "chinese", feature = "external-models"))]
             Language::Chinese => hashset!(Alphabet::Han),
 
-            #[cfg(feature = "georgian")]
+            #[cfg(any(feature = "georgian", feature = "external-models"))]
             Language::Georgian => hashset!(Alphabet::Georgian),
 
-            #[cfg(feature = "greek")]
+            #[cfg(any(feature = "greek", feature = "external-models"))]
             Language::Greek => hashset!(Alphabet::Greek),
 
-            #[cfg(feature = "gujarati")]
+            #[cfg(any(feature = "gujarati", feature = "external-models"))]
             Language::Gujarati => hashset!(Alphabet::Gujarati),
 
-            #[cfg(feature = "hebrew")]
+            #[cfg(any(feature = "hebrew", feature = "external-models"))]
             Language::Hebrew => hashset!(Alphabet::Hebrew),
 
-            #[cfg(feature = "japanese")]
+            #[cfg(any(feature = "japanese", feature = "external-models"))]
             Language::Japanese => hashset!(Alphabet::Hiragana, Alphabet::Katakana, Alphabet::Han),
 
-            #[cfg(feature = "korean")]
+            #[cfg(any(feature = "korean", feature = "external-models"))]
             Language::Korean => hashset!(Alphabet::Hangul),
 
-            #[cfg(feature = "punjabi")]
+            #[cfg(any(feature = "punjabi", feature = "external-models"))]
             Language::Punjabi => hashset!(Alphabet::Gurmukhi),
 
-            #[cfg(feature = "tamil")]
+            #[cfg(any(feature = "tamil", feature = "external-models"))]
             Language::Tamil => hashset!(Alphabet::Tamil),
 
-            #[cfg(feature = "telugu")]
+            #[cfg(any(feature = "telugu", feature = "external-models"))]
             Language::Telugu => hashset!(Alphabet::Telugu),
 
-            #[cfg(feature = "thai")]
+            #[cfg(any(feature = "thai", feature = "external-models"))]
             Language::Thai => hashset!(Alphabet::Thai),
+
+            Language::Custom(language) => language.alphabets().clone(),
         }
     }
 
     pub(crate) fn unique_characters(&self) -> Option<&str> {
         match self {
-            #[cfg(feature = "azerbaijani")]
+            #[cfg(any(feature = "azerbaijani", feature = "external-models"))]
             Language::Azerbaijani => Some("Əə"),
 
-            #[cfg(feature = "catalan")]
+            #[cfg(any(feature = "catalan", feature = "external-models"))]
             Language::Catalan => Some("Ïï"),
 
-            #[cfg(feature = "czech")]
+            #[cfg(any(feature = "czech", feature = "external-models"))]
             Language::Czech => Some("ĚěŘřŮů"),
 
-            #[cfg(feature = "esperanto")]
+            #[cfg(any(feature = "esperanto", feature = "external-models"))]
             Language::Esperanto => Some("ĈĉĜĝĤĥĴĵŜŝŬŭ"),
 
-            #[cfg(feature = "german")]
+            #[cfg(any(feature = "german", feature = "external-models"))]
             Language::German => Some("ß"),
 
-            #[cfg(feature = "hungarian")]
+            #[cfg(any(feature = "hungarian", feature = "external-models"))]
             Language::Hungarian => Some("ŐőŰű"),
 
-            #[cfg(feature = "kazakh")]
+            #[cfg(any(feature = "kazakh", feature = "external-models"))]
             Language::Kazakh => Some("ӘәҒғҚқҢңҰұ"),
 
-            #[cfg(feature = "latvian")]
+            #[cfg(any(feature = "latvian", feature = "external-models"))]
             Language::Latvian => Some("ĢģĶķĻļŅņ"),
 
-            #[cfg(feature = "lithuanian")]
+            #[cfg(any(feature = "lithuanian", feature = "external-models"))]
             Language::Lithuanian => Some("ĖėĮįŲų"),
 
-            #[cfg(feature = "macedonian")]
+            #[cfg(any(feature = "macedonian", feature = "external-models"))]
             Language::Macedonian => Some("ЃѓЅѕЌќЏџ"),
 
-            #[cfg(feature = "marathi")]
+            #[cfg(any(feature = "marathi", feature = "external-models"))]
             Language::Marathi => Some("ळ"),
 
-            #[cfg(feature = "mongolian")]
+            #[cfg(any(feature = "mongolian", feature = "external-models"))]
             Language::Mongolian => Some("ӨөҮү"),
 
-            #[cfg(feature = "polish")]
+            #[cfg(any(feature = "polish", feature = "external-models"))]
             Language::Polish => Some("ŁłŃńŚśŹź"),
 
-            #[cfg(feature = "romanian")]
+            #[cfg(any(feature = "romanian", feature = "external-models"))]
             Language::Romanian => Some("Țţ"),
 
-            #[cfg(feature = "serbian")]
+            #[cfg(any(feature = "serbian", feature = "external-models"))]
             Language::Serbian => Some("ЂђЋћ"),
 
-            #[cfg(feature = "slovak")]
+            #[cfg(any(feature = "slovak", feature = "external-models"))]
             Language::Slovak => Some("ĹĺĽľŔŕ"),
 
-            #[cfg(feature = "spanish")]
+            #[cfg(any(feature = "spanish", feature = "external-models"))]
             Language::Spanish => Some("¿¡"),
 
-            #[cfg(feature = "ukrainian")]
+            #[cfg(any(feature = "ukrainian", feature = "external-models"))]
             Language::Ukrainian => Some("ҐґЄєЇї"),
 
-            #[cfg(feature = "vietnamese")]
+            #[cfg(any(feature = "vietnamese", feature = "external-models"))]
             Language::Vietnamese => Some("ẰằẦầẲẳẨẩẴẵẪẫẮắẤấẠạẶặẬậỀềẺẻỂểẼẽỄễẾếỆệỈỉĨĩỊịƠơỒồỜờỎỏỔổỞởỖỗỠỡỐốỚớỘộỢợƯưỪừỦủỬửŨũỮữỨứỤụỰựỲỳỶỷỸỹỴỵ"),
 
-            #[cfg(feature = "yoruba")]
+            #[cfg(any(feature = "yoruba", feature = "external-models"))]
             Language::Yoruba => Some("Ṣṣ"),
 
+            Language::Custom(language) => language.unique_characters(),
+
             _ => None,
         }
     }
 }
 
+/// Returns `true` if the given name is the name of a built-in language,
+/// even if that language is left out as Cargo feature.
+pub(crate) fn is_built_in_language_name(name: &str) -> bool {
+    BUILT_IN_LANGUAGE_NAMES
+        .iter()
+        .any(|built_in_name| built_in_name.eq_ignore_ascii_case(name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1086,6 +1220,31 @@ mod tests {
         assert_eq!(language, Language::English);
     }
 
+    #[test]
+    fn assert_built_in_language_names_are_complete() {
+        for language in Language::all() {
+            assert!(is_built_in_language_name(&format!("{:?}", language)));
+        }
+        assert!(is_built_in_language_name("german"));
+        assert!(!is_built_in_language_name("Scots"));
+    }
+
+    #[test]
+    fn assert_iso_codes_of_custom_languages_are_absent() {
+        let model_directory = tempfile::tempdir().unwrap();
+        let custom_language = Language::Custom(crate::custom::CustomLanguage::new(
+            "Scots",
+            &[Alphabet::Latin],
+            None,
+            model_directory.path(),
+        ));
+
+        assert_eq!(English.checked_iso_code_639_1(), Some(IsoCode639_1::EN));
+        assert_eq!(English.checked_iso_code_639_3(), Some(IsoCode639_3::ENG));
+        assert_eq!(custom_language.checked_iso_code_639_1(), None);
+        assert_eq!(custom_language.checked_iso_code_639_3(), None);
+    }
+
     #[test]
     fn assert_all_languages_are_available() {
         assert_eq!(
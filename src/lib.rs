@@ -166,7 +166,84 @@
 //! returned most of the time as in the example above. This is the return value for cases where
 //! language detection is not reliably possible.
 //!
-//! ### 7.3 Confidence values
+//! The method `detect_language_outcome_of()` tells apart why no language has been detected.
+//! It returns a `DetectionOutcome` which is either a detected language, the ambiguous candidates
+//! whose probabilities are too close to each other or the reason why the language cannot be
+//! determined at all, such as a text without any letters or a text written in an alphabet which
+//! none of the languages uses:
+//!
+//! ```
+//! use lingua::{DetectionOutcome, LanguageDetectorBuilder, UndeterminedReason};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+//!     .with_minimum_relative_distance(0.25)
+//!     .build();
+//! let outcome = detector.detect_language_outcome_of("languages are awesome");
+//!
+//! assert!(matches!(outcome, DetectionOutcome::Ambiguous { .. }));
+//! assert!(outcome.candidates().contains(&English));
+//! assert_eq!(
+//!     detector.detect_language_outcome_of("42 %"),
+//!     DetectionOutcome::Undetermined { reason: UndeterminedReason::NoLetters }
+//! );
+//! ```
+//!
+//! ### 7.3 Detection options per call
+//!
+//! A detector serving requests with different requirements does not need to be built anew for
+//! each of them. `DetectionOptions` restrict the languages a text is detected among and override
+//! the minimum relative distance for a single call, while the language models already loaded
+//! are reused. Languages which the detector has not been built from are ignored:
+//!
+//! ```
+//! use lingua::{DetectionOptions, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+//!     .build();
+//!
+//! assert_eq!(
+//!     detector.detect_language_among("languages are awesome", &[English, German]),
+//!     Some(English)
+//! );
+//!
+//! let options = DetectionOptions::new()
+//!     .with_languages(&[English, French])
+//!     .with_minimum_relative_distance(0.25);
+//!
+//! assert_eq!(detector.detect_language_with_options("languages are awesome", &options), None);
+//! ```
+//!
+//! ### 7.4 Prior weights and hints
+//!
+//! By default, all languages are considered equally likely before looking at a text. If most
+//! texts are written in a few languages, their prior weights can be set when building the detector.
+//! Languages without a weight are weighted with 1.0, so a weight of 3.0 makes a language three
+//! times as likely as each of them. Knowledge about a single text, such as the locale of a user,
+//! the `Accept-Language` header of an HTTP request or the country a request originates from,
+//! can be given as hints in the `DetectionOptions` which are multiplied with the prior weights:
+//!
+//! ```
+//! use lingua::{DetectionOptions, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+//!     .with_prior_weights(&[(English, 4.5)])
+//!     .build();
+//! let options = DetectionOptions::new()
+//!     .with_accept_language("de-CH, de;q=0.9, en;q=0.8")
+//!     .with_country("CH");
+//!
+//! assert_eq!(detector.detect_language_with_options("languages are awesome", &options), Some(English));
+//! ```
+//!
+//! The logarithms of the resulting prior probabilities are added to the summed up
+//! log-probabilities of the ngrams before both are divided by the number of unigrams of the
+//! text, so they are on the same scale as the likelihood. Their influence is therefore largest
+//! for single words and word pairs and decreases with the length of a text.
+//!
+//! ### 7.5 Confidence values
 //!
 //! Knowing about the most likely language is nice but how reliable is the computed likelihood?
 //! And how less likely are the other examined languages in comparison to the most likely one?
@@ -215,7 +292,287 @@
 //! returned vector will be empty. The confidence value for each language not being part of the
 //! returned vector is assumed to be 0.0.
 //!
-//! ### 7.4 Eager loading versus lazy loading
+//! ### 7.6 Calibrated probabilities
+//!
+//! The confidence values relate all languages to the most likely one which is always assigned
+//! the value 1.0. They are not probabilities and thresholding them across texts of different
+//! length is unreliable. If you need values which can be compared across texts, you can compute
+//! calibrated probabilities instead. They sum up to 1.0 and are computed by applying the softmax
+//! function to the length-normalized log-likelihoods of the languages, divided by a temperature.
+//! Prior weights and hints are not taken into account, so the probabilities reflect the language
+//! models alone.
+//!
+//! ```
+//! use lingua::{Language, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let languages = vec![English, French, German, Spanish];
+//! let detector = LanguageDetectorBuilder::from_languages(&languages).build();
+//! let probabilities: Vec<(Language, f64)> = detector.compute_calibrated_probabilities(
+//!     "languages are awesome"
+//! );
+//!
+//! assert_eq!(probabilities[0].0, English);
+//! assert!(probabilities[0].1 > 0.9);
+//!
+//! let sum: f64 = probabilities.iter().map(|(_, probability)| probability).sum();
+//! assert!((sum - 1.0).abs() < 1e-9);
+//! ```
+//!
+//! The default temperature has been fitted on the test data of all built-in languages. You can fit
+//! a temperature better suited for your own data with `LanguageDetector::fit_calibration_temperature`
+//! and set it with `LanguageDetectorBuilder::with_calibration_temperature`. The `calibration`
+//! example fits the temperature on the test data bundled with the language models. Its output,
+//! which the default temperature is taken from, is kept in `accuracy-reports/calibration.txt`:
+//!
+//! ```text
+//! cargo run --release --example calibration
+//! ```
+//!
+//! ### 7.7 Rejecting texts in unknown languages
+//!
+//! The confidence values are relative, so a text written in a language the detector has not been
+//! built from is assigned to the closest known language, often with high confidence. In order to
+//! recognize such texts, you can set rejection thresholds for the languages. If the ngram models
+//! decide on a language with a threshold, the text is checked against it additionally. The goodness
+//! of fit of a text is its log-likelihood in the language per character, with the ngrams scored like
+//! for detecting the language. It is an absolute metric which `LanguageDetector::compute_goodness_of_fit`
+//! returns as well. If it falls below the threshold, no language is detected and the outcome tells that
+//! the language is unknown.
+//!
+//! ```
+//! use lingua::{DetectionOutcome, LanguageDetectorBuilder, UndeterminedReason};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let languages = vec![English, French, German, Spanish];
+//! let detector = LanguageDetectorBuilder::from_languages(&languages).build();
+//! let samples = vec![
+//!     (English, "languages are awesome"),
+//!     (French, "les langues sont géniales"),
+//!     (German, "Sprachen sind großartig"),
+//!     (Spanish, "los idiomas son geniales"),
+//! ];
+//!
+//! // Finnish is unknown to the detector
+//! assert_eq!(detector.detect_language_of("kiitos paljon hyvin menee"), Some(German));
+//!
+//! let thresholds = detector.fit_rejection_thresholds(&samples, 0.0);
+//! let detector = LanguageDetectorBuilder::from_languages(&languages)
+//!     .with_rejection_thresholds(&thresholds)
+//!     .build();
+//!
+//! assert_eq!(detector.detect_language_of("languages are awesome"), Some(English));
+//! assert_eq!(
+//!     detector.detect_language_outcome_of("kiitos paljon hyvin menee"),
+//!     DetectionOutcome::Undetermined { reason: UndeterminedReason::UnknownLanguage }
+//! );
+//! ```
+//!
+//! Single words fit the language models less well than longer texts, and texts reaching the
+//! long-text threshold are scored with other ngram lengths. The thresholds are therefore set for
+//! each `TextLength` separately: single words, short texts of several words and long texts.
+//! `LanguageDetector::fit_rejection_thresholds` groups the samples by their length and chooses the
+//! threshold of each language and length such that the given fraction of these samples would be
+//! rejected. Texts of a length without threshold are never rejected, so provide samples of all
+//! lengths you detect. The thresholds depend on the languages and ngram lengths of the detector,
+//! so fit them with the same configuration you detect with. The `calibration` example fits
+//! thresholds on the test data bundled with the language models and reports for each length how
+//! many texts of a language left out of the detector are rejected. Texts detected with a
+//! `DetectionSession` or from a reader are rejected in the same way, but texts which the
+//! rule-based engine decides on are never rejected.
+//!
+//! ### 7.8 Smoothing of unknown ngrams
+//!
+//! By default, an ngram which does not occur in a language model is backed off to its longest
+//! known lower-order ngram. If even its unigram is unknown, the ngram is ignored for this language.
+//! Languages with small language models are therefore favored since unknown ngrams do not lower
+//! their probabilities. Other smoothing strategies assign a probability to every ngram instead:
+//!
+//! ```
+//! use lingua::{LanguageDetectorBuilder, Smoothing};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+//!     .with_smoothing(Smoothing::PenalizedBackOff { penalty: 0.4 })
+//!     .build();
+//!
+//! assert_eq!(detector.detect_language_of("languages are awesome"), Some(English));
+//! ```
+//!
+//! `Smoothing::Additive` adds a pseudo-frequency to every ngram, `Smoothing::PenalizedBackOff`
+//! penalizes every order backed off similar to Katz back-off and `Smoothing::LinearInterpolation`
+//! interpolates the relative frequencies of all orders with a fixed weight similar to
+//! Jelinek-Mercer smoothing. The `smoothing` example compares the accuracy of all strategies
+//! on the bundled test data, its results are listed in
+//! `accuracy-reports/smoothing-comparison.md`.
+//! The `accuracy_reports` example accepts a strategy and its parameter to measure its effect
+//! in detail:
+//!
+//! ```text
+//! cargo run --release --example smoothing -- 200
+//! cargo run --release --example accuracy_reports -- linear-interpolation 0.9
+//! ```
+//!
+//! ### 7.9 Explaining detections
+//!
+//! If a text is classified wrongly, it is often not obvious why. The method `explain()` returns
+//! a `DetectionExplanation` tracing every step of the detection: the cleaned text and its words,
+//! the alphabets found in them, the decision of the rule-based engine and the languages which
+//! remain possible afterwards. If the rules do not decide, the evidence of the ngram models is
+//! broken down for every remaining language, including the summed up log-probabilities per ngram
+//! length, the ngrams with the highest probabilities and the ngrams unknown to the language.
+//! The explanation ends with the same `DetectionOutcome` as `detect_language_outcome_of()`, and
+//! `explain_with_options()` explains a detection with `DetectionOptions`.
+//!
+//! ```
+//! use lingua::{LanguageDetectorBuilder, RuleDecision};
+//! use lingua::Language::{English, French, German, Spanish};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+//!     .build();
+//! let explanation = detector.explain("languages are awesome");
+//!
+//! assert_eq!(explanation.rule(), &RuleDecision::Undecided);
+//! assert_eq!(explanation.language(), Some(English));
+//! assert_eq!(explanation.outcome(), &detector.detect_language_outcome_of("languages are awesome"));
+//!
+//! for evidence in explanation.language_evidence() {
+//!     println!("{:?}: {:?}", evidence.language(), evidence.log_probabilities());
+//! }
+//! ```
+//!
+//! The explanation can be serialized with `serde`, for example to JSON with `serde_json::to_string`.
+//!
+//! ### 7.10 Detection of multiple languages in mixed-language texts
+//!
+//! In contrast to most other language detectors, *Lingua* is able to detect multiple languages
+//! in mixed-language texts. This feature can yield quite reasonable results but it is still
+//! in an experimental state and therefore the detection result is highly dependent on the input
+//! text. It works best with multiple long words for each language. The shorter the phrases and
+//! their words are, the less accurate are the results. Reducing the set of languages when
+//! building the language detector can also improve accuracy for this task if the languages
+//! occurring in the text are equal to the languages supported by the respective language
+//! detector instance.
+//!
+//! ```
+//! use lingua::{DetectionResult, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+//! let sentence = "Parlez-vous français? \
+//!     Ich spreche Französisch nur ein bisschen. \
+//!     A little bit is better than nothing.";
+//!
+//! let results: Vec<DetectionResult> = detector.detect_multiple_languages_of(sentence);
+//!
+//! assert_eq!(results.len(), 3);
+//!
+//! let (first, second, third) = (&results[0], &results[1], &results[2]);
+//!
+//! assert_eq!(first.language(), French);
+//! assert_eq!(
+//!     &sentence[first.start_index()..first.end_index()],
+//!     "Parlez-vous français? "
+//! );
+//!
+//! assert_eq!(second.language(), German);
+//! assert_eq!(
+//!     &sentence[second.start_index()..second.end_index()],
+//!     "Ich spreche Französisch nur ein bisschen. "
+//! );
+//!
+//! assert_eq!(third.language(), English);
+//! assert_eq!(
+//!     &sentence[third.start_index()..third.end_index()],
+//!     "A little bit is better than nothing."
+//! );
+//! ```
+//!
+//! In the example above, a vector of [DetectionResult] is returned. Each entry in the vector
+//! describes a contiguous single-language text section, providing start and end indices of the
+//! respective substring. The indices are byte offsets into the original input text.
+//!
+//! ### 7.11 Detection of languages of many texts at once
+//!
+//! In order to classify a large number of texts, such as the documents of a corpus or the rows
+//! of a dataset, the language detector provides batch counterparts of its detection methods.
+//! The texts are processed in parallel and every distinct ngram is looked up only once per
+//! language for the whole batch. This is considerably faster than calling the single-text methods
+//! in a loop. The results are returned in the same order as the input texts.
+//!
+//! ```
+//! use lingua::{Language, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+//! let texts = vec!["languages are awesome", "Les langues sont géniales", "3<856%)§"];
+//!
+//! let detected_languages: Vec<Option<Language>> = detector.detect_languages_of(&texts);
+//!
+//! assert_eq!(detected_languages, vec![Some(English), Some(French), None]);
+//!
+//! let confidence_values: Vec<Vec<(Language, f64)>> =
+//!     detector.compute_language_confidence_values_of_batch(&texts);
+//!
+//! assert_eq!(confidence_values.len(), texts.len());
+//! ```
+//!
+//! If both the languages and their confidence values are needed, call
+//! `detect_languages_with_confidence_values_of` to score each text only once. For large
+//! documents, `detect_language_with_confidence_values_of_reader` does the same.
+//!
+//! ### 7.12 Detection of languages of large documents
+//!
+//! Large documents such as log files or database dumps do not need to be read into memory
+//! entirely. The language detector can process any reader implementing `BufRead` piece by piece
+//! while the ngram probabilities are summed up incrementally. The result is the same as for the
+//! entire text. Optionally, reading stops early as soon as the most likely language leads the
+//! runner-up by a given margin of confidence. As large documents are usually written in a single
+//! language, their beginning often suffices to detect it reliably.
+//!
+//! ```
+//! use lingua::{Language, LanguageDetectorBuilder};
+//! use lingua::Language::{English, French, German};
+//! use std::io::BufReader;
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+//!     .with_early_stopping_margin(0.05)
+//!     .build();
+//!
+//! let document = "Languages are awesome. ".repeat(10_000);
+//! let reader = BufReader::new(document.as_bytes());
+//!
+//! let detected_language: Option<Language> = detector.detect_language_of_reader(reader).unwrap();
+//!
+//! assert_eq!(detected_language, Some(English));
+//! ```
+//!
+//! ### 7.13 Detection of languages of text arriving in pieces
+//!
+//! For chat messages or the output of speech-to-text engines, text often arrives piecemeal.
+//! Instead of detecting the language of the accumulated text from scratch whenever a new piece
+//! arrives, a detection session can be started which keeps the running sums of the ngram
+//! probabilities for each language. Every complete word is scored only once. As the session keeps
+//! each distinct ngram of the text, its memory grows with the number of distinct ngrams fed to it.
+//!
+//! ```
+//! use lingua::LanguageDetectorBuilder;
+//! use lingua::Language::{English, French, German};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+//! let mut session = detector.start_session();
+//!
+//! session.feed("Languages are ");
+//! session.feed("awesome");
+//!
+//! assert_eq!(session.current_language(), Some(English));
+//!
+//! let confidence_values = session.current_confidence_values();
+//!
+//! assert_eq!(confidence_values[0].0, English);
+//! ```
+//!
+//! ### 7.14 Eager loading versus lazy loading
 //!
 //! By default, *Lingua* uses lazy-loading to load only those language models on demand which are
 //! considered relevant by the rule-based filter engine. For web services, for instance, it is
@@ -229,10 +586,236 @@
 //! LanguageDetectorBuilder::from_all_languages().with_preloaded_language_models().build();
 //! ```
 //!
-//! Multiple instances of `LanguageDetector` share the same language models in memory which are
-//! accessed asynchronously by the instances.
+//! Each instance of `LanguageDetector` keeps its language models in a store of its own which is
+//! released as soon as the instance is dropped. If multiple instances shall share the same
+//! language models in memory, you can pass them the same `ModelStore`:
+//!
+//! ```
+//! use lingua::{LanguageDetectorBuilder, ModelStore};
+//! use std::sync::Arc;
+//!
+//! let store = Arc::new(ModelStore::new());
+//! let first_detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_model_store(store.clone())
+//!     .build();
+//! let second_detector = LanguageDetectorBuilder::from_all_spoken_languages()
+//!     .with_model_store(store)
+//!     .build();
+//! ```
+//!
+//! For long-running processes, language models which are not needed anymore can be unloaded
+//! explicitly. Alternatively, a memory budget can be set which unloads the least recently used
+//! language models automatically. Unloaded language models are loaded again on demand.
+//! The language models used by detections in progress, including concurrent ones, are never
+//! unloaded. If a model store is passed to the builder, the budget needs to be set on the store
+//! with `ModelStore::with_memory_budget` instead.
+//!
+//! ```
+//! use lingua::{Language, LanguageDetectorBuilder};
+//! use std::collections::HashMap;
+//!
+//! let detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_memory_budget(500 * 1024 * 1024)
+//!     .build();
+//!
+//! // Unload all language models for certain languages ...
+//! detector.unload_language_models(&[Language::Icelandic, Language::Latin]);
+//! // ... or all fivegram models.
+//! detector.unload_language_models_of_ngram_lengths(&[5]);
+//!
+//! // Approximate memory in bytes occupied by the language models of each language
+//! let memory_usage: HashMap<Language, usize> = detector.memory_usage();
+//! ```
+//!
+//! The language models needed for scoring can be reduced further by choosing the ngram lengths
+//! a text is scored with. By default, texts are scored with unigrams up to fivegrams and, from
+//! 120 characters on, with trigrams only. Only the language models of the configured ngram lengths
+//! are loaded, and smoothing does not back off to ngrams of any other length. The low accuracy mode scores all texts with trigrams only. It loads a fraction of the
+//! language models and is faster, but its accuracy for short texts such as single words or word
+//! pairs is lower. The accuracy reports contain the values of both modes for each language.
+//!
+//! ```
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! let detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_low_accuracy_mode()
+//!     .build();
+//!
+//! // Any other ngram lengths and the length from which on a text counts as long can be chosen, too.
+//! let detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_ngram_lengths(2..5)
+//!     .with_long_text_ngram_lengths(3..4)
+//!     .with_long_text_threshold(200)
+//!     .build();
+//! ```
+//!
+//! ### 7.15 Loading language models from a directory
+//!
+//! By default, the language models are compiled into the binary. If you want to ship updated
+//! language models without recompiling your application, you can load them from a directory
+//! at runtime instead:
+//!
+//! ```no_run
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! LanguageDetectorBuilder::from_all_languages().with_model_directory("/path/to/models").build();
+//! ```
+//!
+//! The directory is expected to contain a subdirectory for each language, named after its
+//! ISO 639-1 code, with the files `unigrams.json.zip` to `fivegrams.json.zip` as written by
+//! `LanguageModelFilesWriter`. The English models, for instance, are read from
+//! `/path/to/models/en/`. For every language model file not present in the directory,
+//! the compiled-in language model is used as a fallback. Each language still needs to be
+//! enabled as a feature of the crate.
+//!
+//! If all language models are loaded from a directory, the compiled-in ones are not needed at all.
+//! The feature `external-models` enables all languages without depending on the crates containing
+//! their language models, which keeps the binary small:
+//!
+//! ```toml
+//! [dependencies]
+//! lingua = { version = "1.3.2", default-features = false, features = ["external-models"] }
+//! ```
+//!
+//! In this case, building a `LanguageDetector` fails for every language whose language model
+//! files cannot be found in the model directory.
+//!
+//! Parsing the zipped JSON language models takes a noticeable amount of time. They can be
+//! converted once into a compact binary format which is loaded without any parsing:
+//!
+//! ```no_run
+//! use lingua::LanguageModelFilesWriter;
+//! use std::path::Path;
+//!
+//! # fn main() -> Result<(), lingua::LinguaError> {
+//! LanguageModelFilesWriter::convert_language_model_files_to_binary(
+//!     Path::new("/path/to/json-models/en"),
+//!     Path::new("/path/to/models/en"),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! This writes the files `unigrams.bin` to `fivegrams.bin` which take precedence over
+//! zipped JSON files in the same directory. The binary format only applies to language models
+//! loaded from a directory. The compiled-in language models are still zipped JSON files which
+//! are parsed when they are loaded, so the faster loading requires converting them and
+//! loading them with `with_model_directory`. Although the layout of the binary format is suitable
+//! for memory mapping, a binary file is currently read into memory in full when it is loaded.
+//!
+//! Language models can also be created in memory, from any lines of text or from several
+//! corpus files read in a single pass, and be used right away without writing them to files:
+//!
+//! ```
+//! use lingua::{LanguageDetectorBuilder, LanguageModelFilesWriter, ModelStore};
+//! use lingua::Language::{English, German};
+//! use std::sync::Arc;
+//!
+//! let lines = vec!["the first line of a corpus", "the second line of a corpus"];
+//! let models = LanguageModelFilesWriter::create_language_models(lines, &English, "\\p{L}").unwrap();
+//!
+//! let store = Arc::new(ModelStore::new());
+//! store.insert_language_models(&models);
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+//!     .with_model_store(store)
+//!     .build();
+//! ```
+//!
+//! For corpora too large to count all of their ngrams in memory, the counts can be spilled
+//! to disk whenever a memory budget is exceeded. The budget only applies to counting: the
+//! resulting language models hold all of their ngrams and counts in memory. A minimum count
+//! removes rare ngrams which mostly stem from typos and foreign words and keeps the models
+//! small. A progress callback reports how many lines of each file have been read so far:
+//!
+//! ```no_run
+//! use lingua::{Language, LanguageModelFilesWriter, TrainingOptions};
+//! use std::path::Path;
+//!
+//! let options = TrainingOptions::new()
+//!     .with_memory_budget(2 * 1024 * 1024 * 1024)
+//!     .with_minimum_count(3)
+//!     .with_progress_callback(1_000_000, |path, lines| eprintln!("{}: {}", path.display(), lines));
+//! let models = LanguageModelFilesWriter::create_language_models_from_files_with_options(
+//!     &[Path::new("/path/to/wikipedia-de.txt")],
+//!     &Language::German,
+//!     "\\p{L}",
+//!     &options,
+//! ).unwrap();
+//! ```
+//!
+//! Language model files only hold relative frequencies by default. If they are written
+//! together with the absolute frequencies of their ngrams, new training text can be merged
+//! into them later on without retraining from scratch. The relative frequencies are
+//! recomputed from the summed counts:
+//!
+//! ```no_run
+//! use lingua::{Language, LanguageModelFilesWriter};
+//! use std::path::Path;
+//!
+//! let model_directory = Path::new("/path/to/models/de");
+//! let models = LanguageModelFilesWriter::create_language_models_from_files(
+//!     &[Path::new("/path/to/wikipedia-de.txt")],
+//!     &Language::German,
+//!     "\\p{L}",
+//! ).unwrap();
+//!
+//! LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+//!     &models,
+//!     model_directory,
+//! ).unwrap();
+//!
+//! LanguageModelFilesWriter::merge_into_language_model_files(
+//!     &[Path::new("/path/to/news-de.txt")],
+//!     model_directory,
+//!     &Language::German,
+//!     "\\p{L}",
+//! ).unwrap();
+//! ```
+//!
+//! ### 7.16 Custom languages
+//!
+//! If you need to detect a language which is not supported by *Lingua*, such as a dialect or
+//! an internal language for which you have a corpus, you can define it as a custom language.
+//! Create its language models with `LanguageModelFilesWriter` first, then describe the language
+//! by a name, the alphabets it is written in and optionally the characters which are unique
+//! to it:
+//!
+//! ```no_run
+//! use lingua::{Alphabet, CustomLanguage, Language, LanguageDetectorBuilder, LanguageModelFilesWriter};
+//! use lingua::Language::{English, German};
+//! use std::path::Path;
+//!
+//! let scots = CustomLanguage::new("Scots", &[Alphabet::Latin], None, "/path/to/models/scots");
+//!
+//! LanguageModelFilesWriter::create_and_write_language_model_files(
+//!     Path::new("/path/to/scots.txt"),
+//!     Path::new("/path/to/models/scots"),
+//!     &Language::Custom(scots.clone()),
+//!     "\\p{L}",
+//! ).unwrap();
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+//!     .with_custom_languages(&[scots])
+//!     .build();
+//! ```
+//!
+//! A custom language takes part in the detection like any built-in language and is returned
+//! as `Language::Custom`. Its language models are always read from its own directory,
+//! there is no compiled-in fallback. Its unique characters are treated like the ones of
+//! built-in languages: if at least half of the words of a text contain characters unique to
+//! certain languages, only those languages remain possible. A custom language without unique
+//! characters is ruled out in this case. The name of a custom language must not be the name
+//! of a built-in language, even if that language is left out as Cargo feature.
+//!
+//! ⚠ **Breaking changes in version 2.0.0:** As custom languages have no ISO codes, the methods
+//! `Language::iso_code_639_1()` and `Language::iso_code_639_3()` **panic** for
+//! `Language::Custom` languages. Use `Language::checked_iso_code_639_1()` and
+//! `Language::checked_iso_code_639_3()` instead which return `None` for them.
+//! As `Language` may contain further variants in the future, it is marked as `#[non_exhaustive]`,
+//! so matching on it requires a wildcard arm.
 //!
-//! ### 7.5 Methods to build the LanguageDetector
+//! ### 7.17 Methods to build the LanguageDetector
 //!
 //! There might be classification tasks where you know beforehand that your language data is
 //! definitely not written in Latin, for instance (what a surprise :-). The detection accuracy can
@@ -271,20 +854,45 @@ extern crate maplit;
 
 mod alphabet;
 mod builder;
+mod calibration;
 mod constant;
+mod custom;
 mod detector;
+mod error;
+mod explanation;
 mod fraction;
 mod isocode;
 mod json;
 mod language;
 mod model;
 mod ngram;
+mod options;
+mod outcome;
+mod prior;
+mod rejection;
+mod result;
+mod session;
+mod smoothing;
+mod store;
+mod training;
 mod writer;
 
+pub use alphabet::Alphabet;
 pub use builder::LanguageDetectorBuilder;
+pub use custom::CustomLanguage;
 pub use detector::LanguageDetector;
+pub use error::LinguaError;
+pub use explanation::{DetectionExplanation, LanguageEvidence, RuleDecision};
 pub use isocode::{IsoCode639_1, IsoCode639_3};
 pub use language::Language;
+pub use options::DetectionOptions;
+pub use outcome::{DecisionSource, DetectionOutcome, UndeterminedReason};
+pub use rejection::TextLength;
+pub use result::DetectionResult;
+pub use session::DetectionSession;
+pub use smoothing::Smoothing;
+pub use store::ModelStore;
+pub use training::{TrainedLanguageModels, TrainingOptions};
 pub use writer::{LanguageModelFilesWriter, TestDataFilesWriter};
 
 #[cfg(test)]
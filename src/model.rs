@@ -15,23 +15,33 @@
  */
 
 use crate::constant::LETTER;
+use crate::error::LinguaError;
 use crate::fraction::Fraction;
 use crate::language::Language;
 use crate::ngram::Ngram;
+#[cfg(test)]
+use crate::training::NgramCounter;
 use itertools::Itertools;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::mem::size_of;
 
 #[cfg_attr(test, mockall::automock)]
 pub(crate) trait LanguageModel {
     fn get_relative_frequency(&self, ngram: &Ngram) -> f64;
+
+    /// Returns the approximate amount of memory in bytes occupied by the model.
+    fn memory_size(&self) -> usize;
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 struct JsonLanguageModel {
     language: Language,
     ngrams: BTreeMap<Fraction, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    absolute_frequencies: Option<BTreeMap<u32, String>>,
 }
 
 pub(crate) struct TrainingDataLanguageModel {
@@ -48,35 +58,68 @@ impl LanguageModel for TrainingDataLanguageModel {
             None => 0.0,
         }
     }
+
+    fn memory_size(&self) -> usize {
+        let json_relative_frequencies_size = match &self.json_relative_frequencies {
+            Some(frequencies) => {
+                frequencies.capacity() * (size_of::<(Ngram, f64)>() + 1)
+                    + frequencies
+                        .keys()
+                        .map(|ngram| ngram.value.capacity())
+                        .sum::<usize>()
+            }
+            None => 0,
+        };
+        size_of::<Self>() + json_relative_frequencies_size
+    }
 }
 
 impl TrainingDataLanguageModel {
-    pub(crate) fn from_text<'a>(
-        text: &[&'a str],
+    #[cfg(test)]
+    pub(crate) fn from_text(
+        text: &[&str],
         language: &Language,
         ngram_length: usize,
         char_class: &str,
         lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
     ) -> Self {
-        let absolute_frequencies =
-            Self::compute_absolute_frequencies(text, ngram_length, char_class);
+        let mut counter = NgramCounter::new(char_class).unwrap();
+        for line in text.iter() {
+            counter.count_line(line).unwrap();
+        }
+
+        Self::from_absolute_frequencies(
+            language,
+            ngram_length,
+            counter.into_absolute_frequencies(ngram_length),
+            lower_ngram_absolute_frequencies,
+        )
+        .unwrap()
+    }
 
+    pub(crate) fn from_absolute_frequencies(
+        language: &Language,
+        ngram_length: usize,
+        absolute_frequencies: HashMap<Ngram, u32>,
+        lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
+    ) -> Result<Self, LinguaError> {
         let relative_frequencies = Self::compute_relative_frequencies(
+            language,
             ngram_length,
             &absolute_frequencies,
             lower_ngram_absolute_frequencies,
-        );
+        )?;
 
-        TrainingDataLanguageModel {
+        Ok(TrainingDataLanguageModel {
             language: language.clone(),
             absolute_frequencies: Some(absolute_frequencies),
             relative_frequencies: Some(relative_frequencies),
             json_relative_frequencies: None,
-        }
+        })
     }
 
-    pub(crate) fn from_json(json: &str) -> Self {
-        let json_language_model = serde_json::from_str::<JsonLanguageModel>(json).unwrap();
+    pub(crate) fn from_json(json: &str) -> serde_json::Result<Self> {
+        let json_language_model = serde_json::from_str::<JsonLanguageModel>(json)?;
         let mut json_relative_frequencies = hashmap!();
 
         for (fraction, ngrams) in json_language_model.ngrams {
@@ -86,15 +129,93 @@ impl TrainingDataLanguageModel {
             }
         }
 
-        TrainingDataLanguageModel {
+        Ok(TrainingDataLanguageModel {
             language: json_language_model.language,
             absolute_frequencies: None,
             relative_frequencies: None,
             json_relative_frequencies: Some(json_relative_frequencies),
+        })
+    }
+
+    /// Reads only the language and the absolute frequencies of a model serialized with
+    /// [to_json_with_absolute_frequencies]. They are `None` if the model has been
+    /// serialized without them.
+    ///
+    /// [to_json_with_absolute_frequencies]: TrainingDataLanguageModel::to_json_with_absolute_frequencies
+    pub(crate) fn read_absolute_frequencies(
+        json: &str,
+    ) -> serde_json::Result<(Language, Option<HashMap<Ngram, u32>>)> {
+        let json_language_model = serde_json::from_str::<JsonLanguageModel>(json)?;
+        let absolute_frequencies = json_language_model.absolute_frequencies.map(|frequencies| {
+            let mut absolute_frequencies = hashmap!();
+            for (frequency, ngrams) in frequencies {
+                for ngram in ngrams.split(' ') {
+                    absolute_frequencies.insert(Ngram::new(ngram), frequency);
+                }
+            }
+            absolute_frequencies
+        });
+
+        Ok((json_language_model.language, absolute_frequencies))
+    }
+
+    /// Returns a copy of this model holding only the relative frequencies
+    /// needed to look up ngrams during detection.
+    pub(crate) fn to_lookup_model(&self) -> Self {
+        let json_relative_frequencies = self
+            .relative_frequencies
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|(ngram, fraction)| (ngram.clone(), fraction.to_f64()))
+            .collect();
+
+        TrainingDataLanguageModel {
+            language: self.language.clone(),
+            absolute_frequencies: None,
+            relative_frequencies: None,
+            json_relative_frequencies: Some(json_relative_frequencies),
         }
     }
 
     pub(crate) fn to_json(&self) -> String {
+        self.serialize_to_json(None)
+    }
+
+    /// Returns the number of distinct ngrams in this model.
+    pub(crate) fn ngram_count(&self) -> usize {
+        match (&self.relative_frequencies, &self.json_relative_frequencies) {
+            (Some(frequencies), _) => frequencies.len(),
+            (None, Some(frequencies)) => frequencies.len(),
+            (None, None) => 0,
+        }
+    }
+
+    /// Serializes this model including its absolute frequencies which allow to merge
+    /// additional training text into the model later on.
+    pub(crate) fn to_json_with_absolute_frequencies(&self) -> String {
+        let mut frequencies_to_ngrams = hashmap!();
+        for (ngram, frequency) in self.absolute_frequencies.as_ref().unwrap() {
+            let ngrams = frequencies_to_ngrams
+                .entry(*frequency)
+                .or_insert_with(Vec::new);
+            ngrams.push(ngram);
+        }
+
+        let frequencies_to_joined_ngrams = frequencies_to_ngrams
+            .into_iter()
+            .map(|(frequency, ngrams)| {
+                (
+                    frequency,
+                    ngrams.iter().map(|&it| &it.value).sorted().join(" "),
+                )
+            })
+            .collect();
+
+        self.serialize_to_json(Some(frequencies_to_joined_ngrams))
+    }
+
+    fn serialize_to_json(&self, absolute_frequencies: Option<BTreeMap<u32, String>>) -> String {
         let mut fractions_to_ngrams = hashmap!();
         for (ngram, fraction) in self.relative_frequencies.as_ref().unwrap() {
             let ngrams = fractions_to_ngrams.entry(fraction).or_insert_with(Vec::new);
@@ -112,50 +233,39 @@ impl TrainingDataLanguageModel {
         let model = JsonLanguageModel {
             language: self.language.clone(),
             ngrams: fractions_to_joined_ngrams,
+            absolute_frequencies,
         };
 
         serde_json::to_string(&model).unwrap()
     }
 
-    fn compute_absolute_frequencies<'a>(
-        text: &[&'a str],
-        ngram_length: usize,
-        char_class: &str,
-    ) -> HashMap<Ngram, u32> {
-        let mut absolute_frequencies = hashmap!();
-        let regex = Regex::new(&format!("^[{}]+$", char_class)).unwrap_or_else(|_| {
-            panic!(
-                "The character class '{}' cannot be compiled to a valid regular expression",
-                char_class
-            )
-        });
-
-        for line in text.iter() {
-            let chars = line.to_lowercase().chars().collect_vec();
-
-            for i in 0..=chars.len() - ngram_length {
-                let slice = &chars[i..i + ngram_length].iter().collect::<String>();
-
-                if regex.is_match(slice) {
-                    let counter = absolute_frequencies.entry(Ngram::new(slice)).or_insert(0);
-                    *counter += 1;
-                }
-            }
-        }
-
-        absolute_frequencies
-    }
-
+    /// Divides the absolute frequency of every ngram by the one of its lower ngram
+    /// or, for unigrams, by the total frequency of all ngrams. As fractions consist of
+    /// u32 values, a total frequency exceeding `u32::MAX` is rejected.
     fn compute_relative_frequencies(
+        language: &Language,
         ngram_length: usize,
         absolute_frequencies: &HashMap<Ngram, u32>,
         lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
-    ) -> HashMap<Ngram, Fraction> {
+    ) -> Result<HashMap<Ngram, Fraction>, LinguaError> {
         let mut ngram_probabilities = hashmap!();
-        let total_ngram_frequency = absolute_frequencies.values().sum::<u32>();
+        let is_divided_by_total = ngram_length == 1 || lower_ngram_absolute_frequencies.is_empty();
+        let total_ngram_frequency = if is_divided_by_total {
+            let total = absolute_frequencies
+                .values()
+                .map(|&frequency| u64::from(frequency))
+                .sum::<u64>();
+            u32::try_from(total).map_err(|_| LinguaError::TooManyNgrams {
+                language: language.clone(),
+                ngram_length,
+                count: total,
+            })?
+        } else {
+            0
+        };
 
         for (ngram, frequency) in absolute_frequencies {
-            let denominator = if ngram_length == 1 || lower_ngram_absolute_frequencies.is_empty() {
+            let denominator = if is_divided_by_total {
                 total_ngram_frequency
             } else {
                 let chars = ngram.value.chars().collect_vec();
@@ -168,7 +278,173 @@ impl TrainingDataLanguageModel {
             ngram_probabilities.insert(ngram.clone(), Fraction::new(*frequency, denominator));
         }
 
-        ngram_probabilities
+        Ok(ngram_probabilities)
+    }
+}
+
+const BINARY_MODEL_MAGIC: &[u8; 4] = b"LNGM";
+const BINARY_MODEL_VERSION: u8 = 1;
+const BINARY_MODEL_HEADER_LENGTH: usize = 12;
+const MAXIMUM_NGRAM_BYTE_LENGTH: usize = 4 * 5;
+const LOG_PROBABILITY_SCALE: f64 = 1000.0;
+
+/// A language model stored in a compact binary format that needs no parsing when loaded.
+///
+/// The format consists of a 12 byte header followed by two tables:
+///
+/// - header: the magic bytes `LNGM`, the format version (u8), the ngram length (u8),
+///   two reserved bytes and the number of ngrams (u32, little endian)
+/// - ngram table: every ngram encoded as UTF-8 and zero-padded to `4 * ngram length` bytes,
+///   sorted in ascending byte order
+/// - probability table: for every ngram in the same order, its negative natural logarithm
+///   of the relative frequency multiplied by 1000 and rounded to a u16 (little endian)
+///
+/// As all records have a fixed width, ngrams are looked up by binary search directly
+/// in the raw bytes which makes the layout suitable for memory mapping as well.
+///
+/// This format has two limits so far: a binary file is read into memory in full instead of
+/// being memory-mapped, and it is only loaded from a model directory. The compiled-in
+/// language models are zipped JSON files which are parsed every time they are loaded.
+pub(crate) struct BinaryLanguageModel {
+    ngram_length: usize,
+    ngram_count: usize,
+    bytes: Vec<u8>,
+}
+
+impl LanguageModel for BinaryLanguageModel {
+    fn get_relative_frequency(&self, ngram: &Ngram) -> f64 {
+        let key = match Self::encode_ngram(&ngram.value, self.ngram_length) {
+            Some(key) => key,
+            None => return 0.0,
+        };
+        let record_length = self.record_length();
+        let ngram_table = &self.bytes[BINARY_MODEL_HEADER_LENGTH
+            ..BINARY_MODEL_HEADER_LENGTH + self.ngram_count * record_length];
+
+        let (mut low, mut high) = (0, self.ngram_count);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let record = &ngram_table[middle * record_length..(middle + 1) * record_length];
+            match record.cmp(&key[..record_length]) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => {
+                    let offset =
+                        BINARY_MODEL_HEADER_LENGTH + self.ngram_count * record_length + middle * 2;
+                    let quantized =
+                        u16::from_le_bytes([self.bytes[offset], self.bytes[offset + 1]]);
+                    return (-(quantized as f64) / LOG_PROBABILITY_SCALE).exp();
+                }
+            }
+        }
+
+        0.0
+    }
+
+    fn memory_size(&self) -> usize {
+        size_of::<Self>() + self.bytes.capacity()
+    }
+}
+
+impl BinaryLanguageModel {
+    /// Wraps the given bytes without copying or parsing them after validating the header.
+    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+        if bytes.len() < BINARY_MODEL_HEADER_LENGTH || &bytes[0..4] != BINARY_MODEL_MAGIC {
+            return Err("not a binary language model".to_string());
+        }
+        if bytes[4] != BINARY_MODEL_VERSION {
+            return Err(format!(
+                "unsupported binary language model version {}",
+                bytes[4]
+            ));
+        }
+        let ngram_length = bytes[5] as usize;
+        if !(1..6).contains(&ngram_length) {
+            return Err(format!(
+                "ngram length {} is not in range 1..6",
+                ngram_length
+            ));
+        }
+        let ngram_count = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+        let expected_length = BINARY_MODEL_HEADER_LENGTH + ngram_count * (4 * ngram_length + 2);
+        if bytes.len() != expected_length {
+            return Err(format!(
+                "expected {} bytes but found {}",
+                expected_length,
+                bytes.len()
+            ));
+        }
+
+        Ok(Self {
+            ngram_length,
+            ngram_count,
+            bytes,
+        })
+    }
+
+    /// Encodes the relative frequencies of the given language model in the binary format.
+    pub(crate) fn to_bytes(model: &TrainingDataLanguageModel, ngram_length: usize) -> Vec<u8> {
+        let frequencies: Vec<(&Ngram, f64)> = match (
+            &model.json_relative_frequencies,
+            &model.relative_frequencies,
+        ) {
+            (Some(frequencies), _) => frequencies
+                .iter()
+                .map(|(ngram, frequency)| (ngram, *frequency))
+                .collect(),
+            (None, Some(frequencies)) => frequencies
+                .iter()
+                .map(|(ngram, fraction)| (ngram, fraction.to_f64()))
+                .collect(),
+            (None, None) => vec![],
+        };
+
+        let records = frequencies
+            .into_iter()
+            .filter_map(|(ngram, frequency)| {
+                Self::encode_ngram(&ngram.value, ngram_length).map(|key| (key, frequency))
+            })
+            .sorted_by(|(first, _), (second, _)| first.cmp(second))
+            .collect_vec();
+
+        let record_length = 4 * ngram_length;
+        let mut bytes =
+            Vec::with_capacity(BINARY_MODEL_HEADER_LENGTH + records.len() * (record_length + 2));
+
+        bytes.extend_from_slice(BINARY_MODEL_MAGIC);
+        bytes.push(BINARY_MODEL_VERSION);
+        bytes.push(ngram_length as u8);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&(records.len() as u32).to_le_bytes());
+
+        for (key, _) in records.iter() {
+            bytes.extend_from_slice(&key[..record_length]);
+        }
+        for (_, frequency) in records.iter() {
+            let quantized = (-frequency.ln() * LOG_PROBABILITY_SCALE)
+                .round()
+                .clamp(0.0, u16::MAX as f64) as u16;
+            bytes.extend_from_slice(&quantized.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub(crate) fn ngram_length(&self) -> usize {
+        self.ngram_length
+    }
+
+    fn record_length(&self) -> usize {
+        4 * self.ngram_length
+    }
+
+    fn encode_ngram(ngram: &str, ngram_length: usize) -> Option<[u8; MAXIMUM_NGRAM_BYTE_LENGTH]> {
+        if ngram.chars().count() != ngram_length {
+            return None;
+        }
+        let mut key = [0; MAXIMUM_NGRAM_BYTE_LENGTH];
+        key[..ngram.len()].copy_from_slice(ngram.as_bytes());
+        Some(key)
     }
 }
 
@@ -218,6 +494,7 @@ mod tests {
             let model = JsonLanguageModel {
                 language: Language::English,
                 ngrams: btreemap!(Fraction::new(3, 5) => "a b c d e".to_string()),
+                absolute_frequencies: None,
             };
 
             let serialized = serde_json::to_string(&model).unwrap();
@@ -471,6 +748,61 @@ mod tests {
             );
         }
 
+        #[test]
+        fn assert_total_frequency_exceeding_u32_is_rejected() {
+            let result = TrainingDataLanguageModel::from_absolute_frequencies(
+                &Language::English,
+                1,
+                hashmap!(Ngram::new("a") => u32::MAX, Ngram::new("b") => 1),
+                &hashmap!(),
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::TooManyNgrams {
+                    language: Language::English,
+                    ngram_length: 1,
+                    count,
+                }) if count == u64::from(u32::MAX) + 1
+            ));
+        }
+
+        #[test]
+        fn test_absolute_frequencies_are_serialized_on_demand() {
+            let model = TrainingDataLanguageModel::from_absolute_frequencies(
+                &Language::English,
+                1,
+                expected_unigram_absolute_frequencies(),
+                &hashmap!(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                TrainingDataLanguageModel::read_absolute_frequencies(&model.to_json()).unwrap(),
+                (Language::English, None)
+            );
+            assert_eq!(
+                TrainingDataLanguageModel::read_absolute_frequencies(
+                    &model.to_json_with_absolute_frequencies()
+                )
+                .unwrap(),
+                (
+                    Language::English,
+                    Some(expected_unigram_absolute_frequencies())
+                )
+            );
+
+            let deserialized =
+                TrainingDataLanguageModel::from_json(&model.to_json_with_absolute_frequencies())
+                    .unwrap();
+
+            assert_eq!(deserialized.absolute_frequencies, None);
+            assert_eq!(
+                deserialized.json_relative_frequencies,
+                Some(expected_unigram_json_relative_frequencies())
+            );
+        }
+
         #[test]
         fn test_model_serializer_and_deserializer() {
             let model = TrainingDataLanguageModel {
@@ -479,7 +811,7 @@ mod tests {
                 relative_frequencies: Some(expected_unigram_relative_frequencies()),
                 json_relative_frequencies: None,
             };
-            let deserialized = TrainingDataLanguageModel::from_json(&model.to_json());
+            let deserialized = TrainingDataLanguageModel::from_json(&model.to_json()).unwrap();
 
             assert_eq!(deserialized.language, Language::English);
             assert_eq!(deserialized.absolute_frequencies, None);
@@ -491,6 +823,69 @@ mod tests {
         }
     }
 
+    mod binary_data {
+        use super::*;
+        use float_cmp::approx_eq;
+
+        fn create_model(ngram_length: usize) -> TrainingDataLanguageModel {
+            TrainingDataLanguageModel::from_text(
+                &TEXT.trim().to_lowercase().lines().collect::<Vec<_>>(),
+                &Language::English,
+                ngram_length,
+                "\\p{L}&&\\p{Latin}",
+                &hashmap!(),
+            )
+        }
+
+        #[rstest(ngram_length, case(1), case(2), case(3), case(4), case(5))]
+        fn test_binary_model_round_trip(ngram_length: usize) {
+            let model = create_model(ngram_length);
+            let bytes = BinaryLanguageModel::to_bytes(&model, ngram_length);
+            let binary_model = BinaryLanguageModel::from_bytes(bytes).unwrap();
+
+            assert_eq!(binary_model.ngram_length(), ngram_length);
+
+            for (ngram, fraction) in model.relative_frequencies.as_ref().unwrap() {
+                let expected = fraction.to_f64();
+                let actual = binary_model.get_relative_frequency(ngram);
+                assert!(
+                    approx_eq!(f64, actual.ln(), expected.ln(), epsilon = 0.0005),
+                    "expected {} for ngram '{}', got {}",
+                    expected,
+                    ngram,
+                    actual
+                );
+            }
+        }
+
+        #[test]
+        fn test_binary_model_returns_zero_for_unknown_ngrams() {
+            let model = create_model(3);
+            let binary_model =
+                BinaryLanguageModel::from_bytes(BinaryLanguageModel::to_bytes(&model, 3)).unwrap();
+
+            assert_eq!(binary_model.get_relative_frequency(&Ngram::new("xyz")), 0.0);
+            assert_eq!(binary_model.get_relative_frequency(&Ngram::new("te")), 0.0);
+            assert_eq!(binary_model.get_relative_frequency(&Ngram::new("äöü")), 0.0);
+        }
+
+        #[test]
+        fn test_invalid_binary_model_is_rejected() {
+            let bytes = BinaryLanguageModel::to_bytes(&create_model(2), 2);
+
+            assert!(
+                BinaryLanguageModel::from_bytes(b"{\"language\":\"ENGLISH\"}".to_vec()).is_err()
+            );
+            assert!(BinaryLanguageModel::from_bytes(bytes[..bytes.len() - 1].to_vec()).is_err());
+
+            let mut wrong_version = bytes.clone();
+            wrong_version[4] = 99;
+            assert!(BinaryLanguageModel::from_bytes(wrong_version).is_err());
+
+            assert!(BinaryLanguageModel::from_bytes(bytes).is_ok());
+        }
+    }
+
     mod test_data {
         use super::*;
 
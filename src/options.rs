@@ -0,0 +1,216 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::language::Language;
+use crate::prior::{collect_weights, parse_accept_language, parse_country};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// This struct overrides the configuration of a [LanguageDetector] for a single call,
+/// such as [LanguageDetector::detect_language_with_options].
+///
+/// By default, a text is detected among all languages of the detector with the minimum
+/// relative distance the detector has been built with. Restricting the languages does not
+/// require building a new detector, so the language models already loaded are reused.
+/// Hints about the languages a text is likely written in, such as the locale, the
+/// `Accept-Language` header or the country of a user, are combined with the prior weights
+/// of the detector.
+///
+/// [LanguageDetector]: crate::LanguageDetector
+/// [LanguageDetector::detect_language_with_options]: crate::LanguageDetector::detect_language_with_options
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DetectionOptions {
+    languages: Option<HashSet<Language>>,
+    minimum_relative_distance: Option<f64>,
+    hints: HashMap<Language, f64>,
+}
+
+impl DetectionOptions {
+    /// Creates and returns the default `DetectionOptions` which keep
+    /// the configuration of the detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the languages a text is detected among. Languages which the detector
+    /// has not been built from are ignored.
+    pub fn with_languages(mut self, languages: &[Language]) -> Self {
+        self.languages = Some(languages.iter().cloned().collect());
+        self
+    }
+
+    /// Overrides the minimum relative distance of the detector.
+    ///
+    /// ⚠ Panics if `distance` is smaller than 0.0 or greater than 0.99.
+    /// Use [try_with_minimum_relative_distance] to get an error instead.
+    ///
+    /// [try_with_minimum_relative_distance]: DetectionOptions::try_with_minimum_relative_distance
+    pub fn with_minimum_relative_distance(self, distance: f64) -> Self {
+        self.try_with_minimum_relative_distance(distance)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Overrides the minimum relative distance of the detector.
+    ///
+    /// Returns [LinguaError::InvalidMinimumRelativeDistance] if `distance` is smaller
+    /// than 0.0 or greater than 0.99.
+    pub fn try_with_minimum_relative_distance(
+        mut self,
+        distance: f64,
+    ) -> Result<Self, LinguaError> {
+        if !(0.0..=0.99).contains(&distance) {
+            return Err(LinguaError::InvalidMinimumRelativeDistance(distance));
+        }
+        self.minimum_relative_distance = Some(distance);
+        Ok(self)
+    }
+
+    /// Hints at the languages a text is likely written in. Each weight is multiplied with the
+    /// prior weight of the language set with [with_prior_weights]. Languages without a hint
+    /// are weighted with 1.0, so a weight of 3.0 makes a language three times as likely.
+    ///
+    /// ⚠ Panics if a weight is not a finite number greater than 0.0.
+    /// Use [try_with_hints] to get an error instead.
+    ///
+    /// [with_prior_weights]: crate::LanguageDetectorBuilder::with_prior_weights
+    /// [try_with_hints]: DetectionOptions::try_with_hints
+    pub fn with_hints(self, hints: &[(Language, f64)]) -> Self {
+        self.try_with_hints(hints)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Hints at the languages a text is likely written in.
+    ///
+    /// See [with_hints] for details.
+    ///
+    /// Returns [LinguaError::InvalidPriorWeight] if a weight is not a finite number
+    /// greater than 0.0.
+    ///
+    /// [with_hints]: DetectionOptions::with_hints
+    pub fn try_with_hints(mut self, hints: &[(Language, f64)]) -> Result<Self, LinguaError> {
+        for (language, weight) in collect_weights(hints)? {
+            *self.hints.entry(language).or_insert(1.0) *= weight;
+        }
+        Ok(self)
+    }
+
+    /// Hints at the languages listed in an HTTP `Accept-Language` header such as
+    /// `de-CH, de;q=0.9, en;q=0.8` or in a locale such as `pt_BR`. A language with quality
+    /// 1.0 is weighted with 5.0, a language with quality 0.5 with 3.0 and so on, as if given
+    /// to [with_hints]. Unsupported languages and malformed entries are ignored.
+    ///
+    /// [with_hints]: DetectionOptions::with_hints
+    pub fn with_accept_language(mut self, header: &str) -> Self {
+        for (language, weight) in parse_accept_language(header) {
+            *self.hints.entry(language).or_insert(1.0) *= weight;
+        }
+        self
+    }
+
+    /// Hints at the languages commonly written in the country given by its ISO 3166-1 alpha-2
+    /// code such as `CH`, for example the country a request originates from. Each of these
+    /// languages is weighted with 3.0, as if given to [with_hints]. Only the countries of the
+    /// most widely spoken supported languages are known, unknown countries are ignored.
+    ///
+    /// [with_hints]: DetectionOptions::with_hints
+    pub fn with_country(mut self, country: &str) -> Self {
+        for (language, weight) in parse_country(country) {
+            *self.hints.entry(language).or_insert(1.0) *= weight;
+        }
+        self
+    }
+
+    /// Returns the languages among the given ones of the detector which are allowed.
+    pub(crate) fn resolve_languages<'a>(
+        &self,
+        languages: &'a HashSet<Language>,
+    ) -> Cow<'a, HashSet<Language>> {
+        match &self.languages {
+            Some(allowed_languages) => {
+                Cow::Owned(languages.intersection(allowed_languages).cloned().collect())
+            }
+            None => Cow::Borrowed(languages),
+        }
+    }
+
+    pub(crate) fn hints(&self) -> &HashMap<Language, f64> {
+        &self.hints
+    }
+
+    /// Returns the minimum relative distance, falling back to the one of the detector.
+    pub(crate) fn resolve_minimum_relative_distance(&self, distance: f64) -> f64 {
+        self.minimum_relative_distance.unwrap_or(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_languages_are_restricted_to_detector_languages() {
+        let languages = hashset!(English, French, German);
+
+        assert_eq!(
+            DetectionOptions::new().resolve_languages(&languages),
+            Cow::Borrowed(&languages)
+        );
+        assert_eq!(
+            DetectionOptions::new()
+                .with_languages(&[German, Spanish])
+                .resolve_languages(&languages)
+                .into_owned(),
+            hashset!(German)
+        );
+    }
+
+    #[test]
+    fn assert_minimum_relative_distance_is_overridden() {
+        assert_eq!(
+            DetectionOptions::new().resolve_minimum_relative_distance(0.1),
+            0.1
+        );
+        assert_eq!(
+            DetectionOptions::new()
+                .with_minimum_relative_distance(0.25)
+                .resolve_minimum_relative_distance(0.1),
+            0.25
+        );
+        assert!(matches!(
+            DetectionOptions::new().try_with_minimum_relative_distance(1.5),
+            Err(LinguaError::InvalidMinimumRelativeDistance(_))
+        ));
+    }
+
+    #[test]
+    fn assert_hints_are_combined() {
+        let options = DetectionOptions::new()
+            .with_hints(&[(English, 2.0), (French, 1.5)])
+            .with_accept_language("en-GB, de;q=0.5")
+            .with_country("CA");
+
+        assert_eq!(
+            options.hints(),
+            &hashmap!(English => 30.0, French => 4.5, German => 3.0)
+        );
+        assert!(matches!(
+            DetectionOptions::new().try_with_hints(&[(English, -1.0)]),
+            Err(LinguaError::InvalidPriorWeight(_))
+        ));
+    }
+}
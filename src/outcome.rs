@@ -0,0 +1,149 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use serde::Serialize;
+
+/// This enum describes the outcome of detecting the language of a single text.
+///
+/// In contrast to the `Option<Language>` returned by [LanguageDetector::detect_language_of],
+/// it tells apart the reasons why no language has been detected.
+///
+/// [LanguageDetector::detect_language_of]: crate::LanguageDetector::detect_language_of
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum DetectionOutcome {
+    /// A single language has been detected.
+    Detected {
+        language: Language,
+        source: DecisionSource,
+    },
+
+    /// The most likely languages cannot be told apart because their confidence values
+    /// differ by less than the minimum relative distance. The candidates are sorted
+    /// by their confidence values in descending order.
+    Ambiguous { candidates: Vec<Language> },
+
+    /// No language has been detected for the given reason.
+    Undetermined { reason: UndeterminedReason },
+}
+
+impl DetectionOutcome {
+    /// Returns the detected language or `None` if the outcome is ambiguous or undetermined.
+    pub fn language(&self) -> Option<Language> {
+        match self {
+            DetectionOutcome::Detected { language, .. } => Some(language.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the languages which are considered possible. This is the detected language,
+    /// the ambiguous candidates or no language at all.
+    pub fn candidates(&self) -> Vec<Language> {
+        match self {
+            DetectionOutcome::Detected { language, .. } => vec![language.clone()],
+            DetectionOutcome::Ambiguous { candidates } => candidates.clone(),
+            DetectionOutcome::Undetermined { .. } => vec![],
+        }
+    }
+}
+
+/// This enum specifies which part of the detection has decided on a language.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionSource {
+    /// The rule-based engine has decided by the alphabets and characters of the text.
+    Rules,
+
+    /// The language models have decided by the ngram probabilities of the text.
+    Ngrams,
+}
+
+/// This enum specifies why no language has been detected for a text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UndeterminedReason {
+    /// The text is empty or consists of whitespace only.
+    EmptyText,
+
+    /// The text contains no letters, such as numbers or punctuation only.
+    NoLetters,
+
+    /// The text is written in an alphabet which none of the detector's languages uses.
+    UnsupportedScript,
+
+    /// The text has fewer characters than the shortest ngrams the text is scored with.
+    TooShort,
+
+    /// None of the languages which the text is to be detected among belongs to the detector.
+    NoLanguages,
+
+    /// None of the text's ngrams is known to the language models of the possible languages.
+    UnknownNgrams,
+
+    /// The text fits the language model of the detected language, or those of all ambiguous
+    /// candidates, worse than their rejection thresholds, so it is probably written in a
+    /// language the detector does not know.
+    UnknownLanguage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use serde_json::json;
+
+    #[test]
+    fn assert_languages_and_candidates_are_returned_correctly() {
+        let detected = DetectionOutcome::Detected {
+            language: English,
+            source: DecisionSource::Ngrams,
+        };
+        let ambiguous = DetectionOutcome::Ambiguous {
+            candidates: vec![English, German],
+        };
+        let undetermined = DetectionOutcome::Undetermined {
+            reason: UndeterminedReason::TooShort,
+        };
+
+        assert_eq!(detected.language(), Some(English));
+        assert_eq!(detected.candidates(), vec![English]);
+        assert_eq!(ambiguous.language(), None);
+        assert_eq!(ambiguous.candidates(), vec![English, German]);
+        assert_eq!(undetermined.language(), None);
+        assert!(undetermined.candidates().is_empty());
+    }
+
+    #[test]
+    fn assert_outcomes_are_serialized_to_json() {
+        let detected = DetectionOutcome::Detected {
+            language: English,
+            source: DecisionSource::Rules,
+        };
+        let undetermined = DetectionOutcome::Undetermined {
+            reason: UndeterminedReason::NoLetters,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&detected).unwrap(),
+            json!({ "outcome": "detected", "language": "ENGLISH", "source": "rules" })
+        );
+        assert_eq!(
+            serde_json::to_value(&undetermined).unwrap(),
+            json!({ "outcome": "undetermined", "reason": "no_letters" })
+        );
+    }
+}
@@ -0,0 +1,299 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::isocode::{IsoCode639_1, IsoCode639_3};
+use crate::language::Language;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// The weight of a language requested with quality 1.0 in an `Accept-Language` header,
+/// relative to the languages not requested at all.
+pub(crate) const ACCEPT_LANGUAGE_WEIGHT: f64 = 5.0;
+
+/// The weight of a language spoken in the country of a user, relative to the languages
+/// not spoken there.
+pub(crate) const COUNTRY_WEIGHT: f64 = 3.0;
+
+/// The ISO 639-1 codes of the languages commonly written in a country,
+/// keyed by the ISO 3166-1 alpha-2 code of the country and sorted by it.
+const COUNTRY_LANGUAGES: [(&str, &[&str]); 74] = [
+    ("AL", &["sq"]),
+    ("AM", &["hy"]),
+    ("AR", &["es"]),
+    ("AT", &["de"]),
+    ("AU", &["en"]),
+    ("AZ", &["az"]),
+    ("BA", &["bs", "hr", "sr"]),
+    ("BD", &["bn"]),
+    ("BE", &["nl", "fr", "de"]),
+    ("BG", &["bg"]),
+    ("BR", &["pt"]),
+    ("BY", &["be", "ru"]),
+    ("CA", &["en", "fr"]),
+    ("CH", &["de", "fr", "it"]),
+    ("CL", &["es"]),
+    ("CN", &["zh"]),
+    ("CO", &["es"]),
+    ("CY", &["el", "tr"]),
+    ("CZ", &["cs"]),
+    ("DE", &["de"]),
+    ("DK", &["da"]),
+    ("EE", &["et"]),
+    ("ES", &["es", "ca", "eu"]),
+    ("FI", &["fi", "sv"]),
+    ("FR", &["fr"]),
+    ("GB", &["en", "cy"]),
+    ("GE", &["ka"]),
+    ("GR", &["el"]),
+    ("HR", &["hr"]),
+    ("HU", &["hu"]),
+    ("ID", &["id"]),
+    ("IE", &["en", "ga"]),
+    ("IL", &["he", "ar"]),
+    (
+        "IN",
+        &["hi", "en", "bn", "mr", "te", "ta", "gu", "pa", "ur"],
+    ),
+    ("IR", &["fa"]),
+    ("IS", &["is"]),
+    ("IT", &["it"]),
+    ("JP", &["ja"]),
+    ("KE", &["sw", "en"]),
+    ("KR", &["ko"]),
+    ("KZ", &["kk", "ru"]),
+    ("LT", &["lt"]),
+    ("LU", &["fr", "de"]),
+    ("LV", &["lv"]),
+    ("MK", &["mk", "sq"]),
+    ("MN", &["mn"]),
+    ("MX", &["es"]),
+    ("MY", &["ms"]),
+    ("NG", &["en", "yo"]),
+    ("NL", &["nl"]),
+    ("NO", &["nb", "nn"]),
+    ("NZ", &["en", "mi"]),
+    ("PE", &["es"]),
+    ("PH", &["tl", "en"]),
+    ("PK", &["ur", "en", "pa"]),
+    ("PL", &["pl"]),
+    ("PT", &["pt"]),
+    ("RO", &["ro"]),
+    ("RS", &["sr"]),
+    ("RU", &["ru"]),
+    ("SA", &["ar"]),
+    ("SE", &["sv"]),
+    ("SI", &["sl"]),
+    ("SK", &["sk"]),
+    ("SO", &["so", "ar"]),
+    ("TH", &["th"]),
+    ("TR", &["tr"]),
+    ("TZ", &["sw", "en"]),
+    ("UA", &["uk"]),
+    ("UG", &["en", "lg", "sw"]),
+    ("US", &["en", "es"]),
+    ("VN", &["vi"]),
+    ("ZA", &["af", "en", "zu", "xh", "st", "tn", "ts"]),
+    ("ZW", &["en", "sn"]),
+];
+
+/// Collects the given weights, rejecting weights which are not finite numbers greater than 0.0.
+pub(crate) fn collect_weights(
+    weights: &[(Language, f64)],
+) -> Result<HashMap<Language, f64>, LinguaError> {
+    weights
+        .iter()
+        .map(|(language, weight)| {
+            if weight.is_finite() && *weight > 0.0 {
+                Ok((language.clone(), *weight))
+            } else {
+                Err(LinguaError::InvalidPriorWeight(*weight))
+            }
+        })
+        .collect()
+}
+
+/// Turns the languages of an `Accept-Language` header such as `de-CH, de;q=0.9, en;q=0.8`
+/// into weights. A language with quality `q` is weighted with `1 + (ACCEPT_LANGUAGE_WEIGHT - 1) * q`.
+/// Unsupported languages, wildcards and malformed entries are ignored.
+pub(crate) fn parse_accept_language(header: &str) -> HashMap<Language, f64> {
+    let mut weights = HashMap::new();
+
+    for entry in header.split(',') {
+        let mut parts = entry.split(';');
+        let tag = parts.next().unwrap_or_default().trim();
+        let primary_subtag = tag.split(&['-', '_'][..]).next().unwrap_or_default();
+        let quality = parts
+            .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+            .map(|value| f64::from_str(value.trim()).ok())
+            .next()
+            .unwrap_or(Some(1.0));
+
+        let (language, quality) = match (parse_language_code(primary_subtag), quality) {
+            (Some(language), Some(quality)) if (0.0..=1.0).contains(&quality) => {
+                (language, quality)
+            }
+            _ => continue,
+        };
+
+        let weight = 1.0 + (ACCEPT_LANGUAGE_WEIGHT - 1.0) * quality;
+        let entry = weights.entry(language).or_insert(weight);
+        *entry = f64::max(*entry, weight);
+    }
+
+    weights
+}
+
+/// Turns the ISO 3166-1 alpha-2 code of a country such as `CH` into weights of the languages
+/// commonly written in that country. Each of them is weighted with `COUNTRY_WEIGHT`.
+/// Unknown countries and unsupported languages are ignored.
+pub(crate) fn parse_country(country: &str) -> HashMap<Language, f64> {
+    let country = country.trim().to_ascii_uppercase();
+
+    COUNTRY_LANGUAGES
+        .binary_search_by(|(code, _)| code.cmp(&country.as_str()))
+        .map(|index| COUNTRY_LANGUAGES[index].1)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|code| parse_language_code(code))
+        .map(|language| (language, COUNTRY_WEIGHT))
+        .collect()
+}
+
+fn parse_language_code(code: &str) -> Option<Language> {
+    match code.len() {
+        2 => IsoCode639_1::from_str(code)
+            .ok()
+            .map(|iso_code| Language::from_iso_code_639_1(&iso_code)),
+        3 => IsoCode639_3::from_str(code)
+            .ok()
+            .map(|iso_code| Language::from_iso_code_639_3(&iso_code)),
+        _ => None,
+    }
+}
+
+/// Adds the logarithm of the prior probability of each language to its summed up
+/// log-probability. The prior probability of a language is its weight, multiplied by its
+/// hint, divided by the sum of all weights of the given languages. Languages without
+/// a weight or a hint are weighted with 1.0.
+pub(crate) fn add_log_prior_probabilities(
+    summed_up_probabilities: HashMap<Language, f64>,
+    weights: &HashMap<Language, f64>,
+    hints: &HashMap<Language, f64>,
+) -> HashMap<Language, f64> {
+    if weights.is_empty() && hints.is_empty() {
+        return summed_up_probabilities;
+    }
+
+    let weight_of = |language: &Language| {
+        weights.get(language).unwrap_or(&1.0) * hints.get(language).unwrap_or(&1.0)
+    };
+    let total_weight: f64 = summed_up_probabilities.keys().map(weight_of).sum();
+
+    summed_up_probabilities
+        .into_iter()
+        .map(|(language, probability)| {
+            let prior_probability = weight_of(&language) / total_weight;
+            (language, probability + prior_probability.ln())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn assert_invalid_weights_are_rejected() {
+        assert_eq!(
+            collect_weights(&[(English, 3.0)]).unwrap(),
+            hashmap!(English => 3.0)
+        );
+        assert!(matches!(
+            collect_weights(&[(English, 3.0), (German, 0.0)]),
+            Err(LinguaError::InvalidPriorWeight(_))
+        ));
+        assert!(matches!(
+            collect_weights(&[(English, f64::INFINITY)]),
+            Err(LinguaError::InvalidPriorWeight(_))
+        ));
+    }
+
+    #[test]
+    fn assert_accept_language_header_is_parsed_correctly() {
+        assert_eq!(
+            parse_accept_language("de-CH, de;q=0.9, en;q=0.5, deu;q=0.2, *;q=0.1, xx, fr;q=2"),
+            hashmap!(German => 5.0, English => 3.0)
+        );
+        assert_eq!(parse_accept_language("pt_BR"), hashmap!(Portuguese => 5.0));
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn assert_country_is_parsed_correctly() {
+        assert_eq!(
+            parse_country("ch"),
+            hashmap!(German => 3.0, French => 3.0, Italian => 3.0)
+        );
+        assert_eq!(
+            parse_country(" NZ "),
+            hashmap!(English => 3.0, Maori => 3.0)
+        );
+        assert!(parse_country("XX").is_empty());
+        assert!(parse_country("").is_empty());
+    }
+
+    #[test]
+    fn assert_country_languages_are_sorted_and_supported() {
+        for window in COUNTRY_LANGUAGES.windows(2) {
+            assert!(window[0].0 < window[1].0);
+        }
+        for (country, codes) in COUNTRY_LANGUAGES.iter() {
+            assert_eq!(parse_country(country).len(), codes.len());
+        }
+    }
+
+    #[test]
+    fn assert_log_prior_probabilities_are_added() {
+        let summed_up_probabilities = hashmap!(English => -10.0, German => -9.0);
+
+        assert_eq!(
+            add_log_prior_probabilities(summed_up_probabilities.clone(), &hashmap!(), &hashmap!()),
+            summed_up_probabilities
+        );
+
+        let weighted = add_log_prior_probabilities(
+            summed_up_probabilities,
+            &hashmap!(English => 3.0),
+            &hashmap!(English => 2.0, French => 5.0),
+        );
+
+        assert!(approx_eq!(
+            f64,
+            weighted[&English],
+            -10.0 + (6.0_f64 / 7.0).ln(),
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            weighted[&German],
+            -9.0 + (1.0_f64 / 7.0).ln(),
+            ulps = 2
+        ));
+    }
+}
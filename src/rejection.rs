@@ -0,0 +1,224 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::language::Language;
+use crate::model::TestDataLanguageModel;
+use crate::ngram::Ngram;
+use crate::smoothing::UNKNOWN_NGRAM_FREQUENCY;
+use std::collections::HashMap;
+
+/// This enum specifies the length of a text which rejection thresholds apply to.
+///
+/// The goodness of fit of a text is its log-likelihood per character. As every distinct
+/// ngram is scored only once, longer texts with repeated ngrams get higher values, and texts
+/// reaching the long-text threshold are scored with other ngram lengths. The goodness of fit
+/// of texts of different lengths is therefore not comparable, so rejection thresholds are set
+/// for each length separately.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TextLength {
+    /// A single word shorter than the long-text threshold.
+    SingleWord,
+
+    /// Several words shorter than the long-text threshold.
+    ShortText,
+
+    /// A text reaching the long-text threshold, which is scored with the ngram lengths
+    /// for long texts.
+    LongText,
+}
+
+impl TextLength {
+    /// Returns all text lengths in ascending order.
+    pub fn all() -> [TextLength; 3] {
+        [
+            TextLength::SingleWord,
+            TextLength::ShortText,
+            TextLength::LongText,
+        ]
+    }
+}
+
+/// The size of a text which its goodness of fit and rejection threshold depend on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TextSize {
+    pub(crate) character_count: usize,
+    pub(crate) length: TextLength,
+}
+
+impl TextSize {
+    pub(crate) fn from_counts(
+        word_count: usize,
+        character_count: usize,
+        long_text_threshold: usize,
+    ) -> Self {
+        let length = if character_count >= long_text_threshold {
+            TextLength::LongText
+        } else if word_count <= 1 {
+            TextLength::SingleWord
+        } else {
+            TextLength::ShortText
+        };
+        Self {
+            character_count,
+            length,
+        }
+    }
+}
+
+/// Collects the given rejection thresholds, rejecting thresholds which are not finite numbers.
+pub(crate) fn collect_thresholds(
+    thresholds: &[(Language, TextLength, f64)],
+) -> Result<HashMap<(Language, TextLength), f64>, LinguaError> {
+    thresholds
+        .iter()
+        .map(|(language, text_length, threshold)| {
+            if threshold.is_finite() {
+                Ok(((language.clone(), *text_length), *threshold))
+            } else {
+                Err(LinguaError::InvalidRejectionThreshold(*threshold))
+            }
+        })
+        .collect()
+}
+
+/// Computes how well the given ngrams of a text with the given number of characters fit
+/// a language model as the average log-likelihood per character. The log-likelihood is the
+/// sum of the log-probabilities of the ngrams of all lengths, as returned by `look_up` with
+/// the smoothing strategy of the detector. Ngrams without any probability, which the
+/// detector ignores, are scored with the frequency assumed for unknown ngrams instead,
+/// so that they lower the goodness of fit. The log-probabilities are summed up in sorted
+/// order, so the same text always gets exactly the same value regardless of the iteration
+/// order of its ngrams.
+pub(crate) fn compute_goodness_of_fit<F>(
+    test_data_models: &[(usize, TestDataLanguageModel)],
+    character_count: usize,
+    look_up: F,
+) -> f64
+where
+    F: Fn(&Ngram) -> f64,
+{
+    let mut log_probabilities = test_data_models
+        .iter()
+        .flat_map(|(_, test_data_model)| test_data_model.ngrams.iter())
+        .map(|ngram| {
+            let probability = look_up(ngram);
+            if probability > 0.0 {
+                probability.ln()
+            } else {
+                UNKNOWN_NGRAM_FREQUENCY.ln()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if log_probabilities.is_empty() || character_count == 0 {
+        return UNKNOWN_NGRAM_FREQUENCY.ln();
+    }
+
+    log_probabilities.sort_by(|first, second| first.partial_cmp(second).unwrap());
+
+    log_probabilities.iter().sum::<f64>() / character_count as f64
+}
+
+/// Returns the score at index `min(floor(n * false_rejection_rate), n - 1)` of the given
+/// `n` goodness-of-fit scores sorted in ascending order. As scores below the threshold are
+/// rejected, at most `floor(n * false_rejection_rate)` of the scored texts are rejected, fewer
+/// if scores are equal to the threshold, and never all of them. A rate of 0.0 returns the
+/// lowest score, so that no scored text is rejected. Rates outside of [0.0, 1.0] are clamped.
+pub(crate) fn fit_threshold(mut scores: Vec<f64>, false_rejection_rate: f64) -> Option<f64> {
+    if scores.is_empty() {
+        return None;
+    }
+
+    scores.sort_by(|first, second| first.partial_cmp(second).unwrap());
+
+    let index = (scores.len() as f64 * false_rejection_rate.clamp(0.0, 1.0)).floor() as usize;
+
+    Some(scores[index.min(scores.len() - 1)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn assert_invalid_thresholds_are_rejected() {
+        assert_eq!(
+            collect_thresholds(&[(English, TextLength::SingleWord, -6.5)]).unwrap(),
+            hashmap!((English, TextLength::SingleWord) => -6.5)
+        );
+        assert!(matches!(
+            collect_thresholds(&[
+                (English, TextLength::SingleWord, -6.5),
+                (German, TextLength::LongText, f64::NAN)
+            ]),
+            Err(LinguaError::InvalidRejectionThreshold(_))
+        ));
+    }
+
+    #[test]
+    fn assert_text_length_is_derived_from_counts() {
+        let length_of = |word_count, character_count| {
+            TextSize::from_counts(word_count, character_count, 120).length
+        };
+
+        assert_eq!(length_of(1, 5), TextLength::SingleWord);
+        assert_eq!(length_of(2, 11), TextLength::ShortText);
+        assert_eq!(length_of(20, 120), TextLength::LongText);
+        assert_eq!(length_of(1, 150), TextLength::LongText);
+    }
+
+    #[test]
+    fn assert_goodness_of_fit_is_averaged_over_characters() {
+        let test_data_models = vec![
+            (1, TestDataLanguageModel::from("ab", 1)),
+            (2, TestDataLanguageModel::from("ab", 2)),
+        ];
+        let look_up = |ngram: &Ngram| match ngram.value.as_str() {
+            "a" => 0.5,
+            "b" => 0.25,
+            _ => 0.0,
+        };
+
+        let log_likelihood = 0.5_f64.ln() + 0.25_f64.ln() + UNKNOWN_NGRAM_FREQUENCY.ln();
+
+        assert!(approx_eq!(
+            f64,
+            compute_goodness_of_fit(&test_data_models, 2, look_up),
+            log_likelihood / 2.0,
+            ulps = 2
+        ));
+        assert_eq!(
+            compute_goodness_of_fit(&[], 0, look_up),
+            UNKNOWN_NGRAM_FREQUENCY.ln()
+        );
+    }
+
+    #[test]
+    fn assert_threshold_is_fitted_to_false_rejection_rate() {
+        let scores = vec![-3.0, -1.0, -5.0, -2.0, -4.0];
+
+        assert_eq!(fit_threshold(scores.clone(), 0.0), Some(-5.0));
+        assert_eq!(fit_threshold(scores.clone(), 0.2), Some(-4.0));
+        assert_eq!(fit_threshold(scores.clone(), 0.5), Some(-3.0));
+        assert_eq!(fit_threshold(scores.clone(), 1.0), Some(-1.0));
+        assert_eq!(fit_threshold(scores.clone(), -0.5), Some(-5.0));
+        assert_eq!(fit_threshold(scores, 1.5), Some(-1.0));
+        assert_eq!(fit_threshold(vec![], 0.1), None);
+    }
+}
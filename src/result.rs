@@ -0,0 +1,69 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+
+/// This struct describes a contiguous single-language text section within a possibly
+/// mixed-language text.
+///
+/// Instances of it are returned by [LanguageDetector::detect_multiple_languages_of].
+///
+/// [LanguageDetector::detect_multiple_languages_of]: crate::LanguageDetector::detect_multiple_languages_of
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DetectionResult {
+    pub(crate) start_index: usize,
+    pub(crate) end_index: usize,
+    pub(crate) word_count: usize,
+    pub(crate) language: Language,
+}
+
+impl DetectionResult {
+    pub(crate) fn new(
+        start_index: usize,
+        end_index: usize,
+        word_count: usize,
+        language: Language,
+    ) -> Self {
+        Self {
+            start_index,
+            end_index,
+            word_count,
+            language,
+        }
+    }
+
+    /// Returns the byte offset in the original input text
+    /// at which the identified section starts.
+    pub fn start_index(&self) -> usize {
+        self.start_index
+    }
+
+    /// Returns the byte offset in the original input text
+    /// at which the identified section ends (exclusive).
+    pub fn end_index(&self) -> usize {
+        self.end_index
+    }
+
+    /// Returns the number of words being part of the identified section.
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns the language of the identified section.
+    pub fn language(&self) -> Language {
+        self.language.clone()
+    }
+}
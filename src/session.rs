@@ -0,0 +1,607 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::constant::{LETTER, NUMBERS, PUNCTUATION};
+use crate::detector::{LanguageDetector, WordCounts};
+use crate::language::Language;
+use crate::model::TestDataLanguageModel;
+use crate::ngram::Ngram;
+use crate::rejection::TextSize;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The running ngram probability sums of a single language.
+#[derive(Clone, Default)]
+struct LanguageScore {
+    scored_ngram_counts: [usize; 5],
+    probability_sums: [f64; 5],
+    unigram_count: u32,
+}
+
+/// This struct detects the language of text arriving in pieces, such as the messages of a
+/// chat or the output of a speech-to-text engine.
+///
+/// A session is obtained from [LanguageDetector::start_session]. Each piece of text passed
+/// to [feed] is appended to the text fed so far. The confidence values of the accumulated
+/// text can be requested at any time without processing this text once again: The session
+/// keeps the counts the rule-based engine decides on as well as the running sums of the ngram
+/// probabilities for each language and only looks up those ngrams which have not been scored
+/// yet. The results are the same as those of
+/// [LanguageDetector::compute_language_confidence_values] for the entire text. Likewise,
+/// the detected language is rejected by the thresholds set with
+/// [LanguageDetectorBuilder::with_rejection_thresholds].
+///
+/// A session keeps each distinct ngram of the text fed so far once, so its memory grows with
+/// the number of distinct ngrams rather than with the length of the text. As the ngrams of the
+/// lengths for short texts are only collected until a text is long enough, the memory is
+/// dominated by the distinct ngrams of the lengths for long texts, trigrams by default. If
+/// the language of an unbounded stream of text shall be detected, start a new session from
+/// time to time.
+///
+/// [LanguageDetectorBuilder::with_rejection_thresholds]: crate::LanguageDetectorBuilder::with_rejection_thresholds
+///
+/// [feed]: DetectionSession::feed
+pub struct DetectionSession<'a> {
+    detector: &'a LanguageDetector,
+    character_count: usize,
+    letter_count: usize,
+    has_pending_whitespace: bool,
+    current_word: String,
+    word_counts: WordCounts,
+    current_letters: VecDeque<char>,
+    known_ngrams: HashSet<Ngram>,
+    ngrams: [Vec<Ngram>; 5],
+    language_scores: HashMap<Language, LanguageScore>,
+    pending_text: String,
+}
+
+/// The state of a [DetectionSession] which is restored after the pending text has been taken
+/// into account temporarily.
+struct SessionSnapshot {
+    character_count: usize,
+    letter_count: usize,
+    has_pending_whitespace: bool,
+    current_word: String,
+    word_counts: WordCounts,
+    current_letters: VecDeque<char>,
+    ngram_counts: [usize; 5],
+    language_scores: HashMap<Language, LanguageScore>,
+}
+
+impl<'a> DetectionSession<'a> {
+    pub(crate) fn new(detector: &'a LanguageDetector) -> Self {
+        Self {
+            detector,
+            character_count: 0,
+            letter_count: 0,
+            has_pending_whitespace: false,
+            current_word: String::new(),
+            word_counts: WordCounts::default(),
+            current_letters: VecDeque::with_capacity(5),
+            known_ngrams: HashSet::new(),
+            ngrams: Default::default(),
+            language_scores: HashMap::new(),
+            pending_text: String::new(),
+        }
+    }
+
+    /// Returns the number of characters of the cleaned up text fed so far,
+    /// without the pending text of a word which may not be complete yet.
+    pub(crate) fn character_count(&self) -> usize {
+        self.character_count
+    }
+
+    /// Appends the given piece of text to the text fed so far.
+    ///
+    /// Pieces are concatenated as they are, so a word may be split across several pieces.
+    /// The text following the last whitespace or logogram of the text fed so far is kept
+    /// pending until the word it belongs to is complete, so that the word is lowercased as a
+    /// whole. The pending text is still taken into account when detecting the language, which
+    /// processes it anew each time. Text written without whitespace and logograms therefore
+    /// stays pending until whitespace follows and is processed once per detection until then.
+    pub fn feed(&mut self, text: &str) {
+        let detector = self.detector;
+        let word_boundary = text
+            .char_indices()
+            .rev()
+            .find(|(_, character)| character.is_whitespace() || detector.is_logogram(*character))
+            .map(|(index, character)| index + character.len_utf8());
+
+        match word_boundary {
+            Some(word_boundary) => {
+                let mut complete_text = std::mem::take(&mut self.pending_text);
+                complete_text.push_str(&text[..word_boundary]);
+                self.push_text(&complete_text);
+                self.pending_text.push_str(&text[word_boundary..]);
+            }
+            None => self.pending_text.push_str(text),
+        }
+    }
+
+    /// Detects the language of the text fed so far.
+    /// If the language cannot be reliably detected, `None` is returned.
+    pub fn current_language(&mut self) -> Option<Language> {
+        self.current_language_with_confidence_values().0
+    }
+
+    /// Computes confidence values for each language considered possible for the text fed
+    /// so far.
+    ///
+    /// See [LanguageDetector::compute_language_confidence_values] for the meaning of the
+    /// returned values.
+    pub fn current_confidence_values(&mut self) -> Vec<(Language, f64)> {
+        self.with_pending_text(|session| session.compute_confidence_values().0)
+    }
+
+    /// Detects the language of the text fed so far together with the confidence values
+    /// it is derived from.
+    pub(crate) fn current_language_with_confidence_values(
+        &mut self,
+    ) -> (Option<Language>, Vec<(Language, f64)>) {
+        self.with_pending_text(|session| {
+            let (confidence_values, text_size) = session.compute_confidence_values();
+            let language = session
+                .detector
+                .detect_language_from_confidence_values(&confidence_values)
+                .filter(|language| match text_size {
+                    Some(text_size) => !session.is_rejected(language, text_size),
+                    None => true,
+                });
+            (language, confidence_values)
+        })
+    }
+
+    /// Calls the given function with the pending text taken into account as if it were
+    /// complete. Afterwards, the pending text is kept pending again.
+    fn with_pending_text<T>(&mut self, function: impl FnOnce(&mut Self) -> T) -> T {
+        if self.pending_text.is_empty() {
+            return function(self);
+        }
+
+        let snapshot = SessionSnapshot {
+            character_count: self.character_count,
+            letter_count: self.letter_count,
+            has_pending_whitespace: self.has_pending_whitespace,
+            current_word: self.current_word.clone(),
+            word_counts: self.word_counts.clone(),
+            current_letters: self.current_letters.clone(),
+            ngram_counts: [0, 1, 2, 3, 4].map(|index| self.ngrams[index].len()),
+            language_scores: self.language_scores.clone(),
+        };
+        let pending_text = std::mem::take(&mut self.pending_text);
+
+        self.push_text(&pending_text);
+        let result = function(self);
+
+        for (ngrams, ngram_count) in self.ngrams.iter_mut().zip(snapshot.ngram_counts) {
+            for ngram in ngrams.drain(ngram_count..) {
+                self.known_ngrams.remove(&ngram);
+            }
+        }
+        self.character_count = snapshot.character_count;
+        self.letter_count = snapshot.letter_count;
+        self.has_pending_whitespace = snapshot.has_pending_whitespace;
+        self.current_word = snapshot.current_word;
+        self.word_counts = snapshot.word_counts;
+        self.current_letters = snapshot.current_letters;
+        self.language_scores = snapshot.language_scores;
+        self.pending_text = pending_text;
+
+        result
+    }
+
+    /// Cleans up the given text like the detector does and processes its characters.
+    fn push_text(&mut self, text: &str) {
+        let lowercased_text = text.to_lowercase();
+        let without_punctuation = PUNCTUATION.replace_all(&lowercased_text, "");
+        let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
+
+        for character in without_numbers.chars() {
+            self.push_character(character);
+        }
+    }
+
+    /// Computes the confidence values of the text fed so far. If the ngram models decide
+    /// on them, the size of the text is returned as well.
+    fn compute_confidence_values(&mut self) -> (Vec<(Language, f64)>, Option<TextSize>) {
+        if self.letter_count == 0 {
+            return (vec![], None);
+        }
+
+        let detector = self.detector;
+        let mut word_counts = self.word_counts.clone();
+
+        if !self.current_word.is_empty() {
+            detector.count_word(&self.current_word, &detector.languages, &mut word_counts);
+        }
+
+        if let Some(language) = detector.detect_language_with_rules(&word_counts) {
+            return (vec![(language, 1.0)], None);
+        }
+
+        let filtered_languages =
+            detector.filter_languages_by_rules(&word_counts, &detector.languages);
+
+        if filtered_languages.len() == 1 {
+            let filtered_language = filtered_languages.into_iter().next().unwrap();
+            return (vec![(filtered_language, 1.0)], None);
+        }
+
+        let ngram_lengths = detector
+            .ngram_lengths
+            .for_character_count(self.character_count)
+            .collect::<Vec<_>>();
+
+        if ngram_lengths.is_empty() {
+            return (vec![], None);
+        }
+
+        self.score_ngrams(&filtered_languages, &ngram_lengths);
+
+        let all_probabilities = ngram_lengths
+            .iter()
+            .map(|ngram_length| {
+                filtered_languages
+                    .iter()
+                    .filter_map(|language| {
+                        let probability_sums = &self.language_scores[language].probability_sums;
+                        let sum = probability_sums[ngram_length - 1];
+                        if sum < 0.0 {
+                            Some((language.clone(), sum))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<HashMap<_, _>>()
+            })
+            .collect::<Vec<_>>();
+
+        let unigram_counts = if ngram_lengths[0] == 1 {
+            let counted_languages = if !all_probabilities[0].is_empty() {
+                all_probabilities[0].keys().cloned().collect()
+            } else {
+                filtered_languages.clone()
+            };
+            Some(
+                counted_languages
+                    .into_iter()
+                    .filter_map(|language| {
+                        let unigram_count = self.language_scores[&language].unigram_count;
+                        if unigram_count > 0 {
+                            Some((language, unigram_count))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        let summed_up_probabilities = detector.sum_up_probabilities(
+            all_probabilities.iter().collect(),
+            &unigram_counts,
+            filtered_languages,
+            Some(&hashmap!()),
+        );
+
+        let text_size = TextSize::from_counts(
+            word_counts.word_count,
+            self.character_count,
+            detector.ngram_lengths.long_text_threshold,
+        );
+
+        (
+            detector.compute_confidence_values_of_summed_up_probabilities(summed_up_probabilities),
+            Some(text_size),
+        )
+    }
+
+    /// Checks whether the ngrams fed so far fit the language model of the given language
+    /// worse than its rejection threshold for texts of the given length, if any.
+    fn is_rejected(&self, language: &Language, text_size: TextSize) -> bool {
+        let detector = self.detector;
+
+        if detector
+            .rejection_threshold(language, text_size.length)
+            .is_none()
+        {
+            return false;
+        }
+
+        let test_data_models = detector
+            .ngram_lengths
+            .for_character_count(self.character_count)
+            .map(|ngram_length| {
+                (
+                    ngram_length,
+                    TestDataLanguageModel {
+                        ngrams: self.ngrams[ngram_length - 1].iter().cloned().collect(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let _detection = detector.model_store.begin_detection();
+
+        detector.is_rejected(language, text_size, &test_data_models)
+    }
+
+    fn push_character(&mut self, character: char) {
+        if character.is_whitespace() {
+            if self.character_count > 0 {
+                self.has_pending_whitespace = true;
+            }
+            self.current_letters.clear();
+            return;
+        }
+
+        if self.has_pending_whitespace {
+            self.has_pending_whitespace = false;
+            self.character_count += 1;
+            self.complete_word();
+        }
+
+        self.character_count += 1;
+        self.current_word.push(character);
+
+        if self.detector.is_logogram(character) {
+            self.complete_word();
+        }
+
+        let mut buffer = [0; 4];
+
+        if LETTER.is_match(character.encode_utf8(&mut buffer)) {
+            self.letter_count += 1;
+            if self.current_letters.len() == 5 {
+                self.current_letters.pop_front();
+            }
+            self.current_letters.push_back(character);
+            self.collect_ngrams();
+        } else {
+            self.current_letters.clear();
+        }
+    }
+
+    fn complete_word(&mut self) {
+        if !self.current_word.is_empty() {
+            let detector = self.detector;
+            detector.count_word(
+                &self.current_word,
+                &detector.languages,
+                &mut self.word_counts,
+            );
+            self.current_word.clear();
+        }
+    }
+
+    /// Collects the ngrams ending with the most recent letter. Once the text is long enough
+    /// to be classified by the ngram lengths for long texts only, ngrams of other lengths
+    /// are not needed anymore.
+    fn collect_ngrams(&mut self) {
+        let ngram_lengths = &self.detector.ngram_lengths;
+        let is_long_text = self.character_count >= ngram_lengths.long_text_threshold;
+        let letter_count = self.current_letters.len();
+
+        for ngram_length in 1..=letter_count {
+            let is_needed = ngram_lengths.long_text.contains(&ngram_length)
+                || (!is_long_text && ngram_lengths.short_text.contains(&ngram_length));
+
+            if !is_needed {
+                continue;
+            }
+
+            let ngram = Ngram::new(
+                &self
+                    .current_letters
+                    .iter()
+                    .skip(letter_count - ngram_length)
+                    .collect::<String>(),
+            );
+
+            if !self.known_ngrams.contains(&ngram) {
+                self.known_ngrams.insert(ngram.clone());
+                self.ngrams[ngram_length - 1].push(ngram);
+            }
+        }
+    }
+
+    /// Adds the probabilities of all ngrams not scored yet to the sums of the given languages.
+    fn score_ngrams(&mut self, languages: &HashSet<Language>, ngram_lengths: &[usize]) {
+        for language in languages.iter() {
+            self.language_scores.entry(language.clone()).or_default();
+        }
+
+        let detector = self.detector;
+        let ngrams = &self.ngrams;
+
+        let _detection = detector.model_store.begin_detection();
+
+        self.language_scores
+            .par_iter_mut()
+            .filter(|(language, _)| languages.contains(language))
+            .for_each(|(language, score)| {
+                for ngram_length in ngram_lengths.iter() {
+                    let index = ngram_length - 1;
+                    for ngram in ngrams[index][score.scored_ngram_counts[index]..].iter() {
+                        let probability =
+                            detector.look_up_smoothed_ngram_probability(language, ngram);
+                        if probability > 0.0 {
+                            score.probability_sums[index] += probability.ln();
+                            if *ngram_length == 1 {
+                                score.unigram_count += 1;
+                            }
+                        }
+                    }
+                    score.scored_ngram_counts[index] = ngrams[index].len();
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::DEFAULT_LONG_TEXT_THRESHOLD;
+    use crate::language::Language::*;
+    use crate::LanguageDetectorBuilder;
+    use float_cmp::approx_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn detector() -> LanguageDetector {
+        LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish]).build()
+    }
+
+    fn assert_confidence_values_are_equal(
+        confidence_values: Vec<(Language, f64)>,
+        expected_confidence_values: Vec<(Language, f64)>,
+        text: &str,
+    ) {
+        assert_eq!(
+            confidence_values.len(),
+            expected_confidence_values.len(),
+            "expected {:?} for text '{}', got {:?}",
+            expected_confidence_values,
+            text,
+            confidence_values
+        );
+
+        for ((language, confidence), (expected_language, expected_confidence)) in confidence_values
+            .into_iter()
+            .zip(expected_confidence_values)
+        {
+            assert_eq!(language, expected_language, "for text '{}'", text);
+            assert!(
+                approx_eq!(f64, confidence, expected_confidence, epsilon = 1e-9),
+                "expected confidence {} for language '{:?}' of text '{}', got {}",
+                expected_confidence,
+                language,
+                text,
+                confidence
+            );
+        }
+    }
+
+    #[rstest]
+    fn assert_new_session_returns_no_confidence_values(detector: LanguageDetector) {
+        let mut session = detector.start_session();
+        assert_eq!(session.current_confidence_values(), vec![]);
+        assert_eq!(session.current_language(), None);
+    }
+
+    #[rstest]
+    fn assert_session_matches_detection_of_accumulated_text_after_each_piece(
+        detector: LanguageDetector,
+    ) {
+        let pieces = [
+            "  Ich ",
+            "spre",
+            "che Franz",
+            "ösisch nur ",
+            "ein bisschen. ",
+            "A little bit ",
+            "is better than nothing, ",
+            "pero no hablo español ",
+            "42 ",
+            "¿verdad?",
+        ];
+        let mut session = detector.start_session();
+        let mut accumulated_text = String::new();
+
+        for piece in pieces.iter() {
+            session.feed(piece);
+            accumulated_text.push_str(piece);
+
+            assert_confidence_values_are_equal(
+                session.current_confidence_values(),
+                detector.compute_language_confidence_values(accumulated_text.clone()),
+                &accumulated_text,
+            );
+            assert_eq!(
+                session.current_language(),
+                detector.detect_language_of(accumulated_text.clone())
+            );
+        }
+    }
+
+    #[rstest]
+    fn assert_session_switches_to_trigrams_for_long_texts(detector: LanguageDetector) {
+        let text = "Languages are awesome. They allow people from all over the world to talk \
+                    to each other and to share their thoughts, ideas, and stories.";
+        let mut session = detector.start_session();
+
+        for word in text.split_inclusive(' ') {
+            session.feed(word);
+        }
+
+        assert!(session.character_count() >= DEFAULT_LONG_TEXT_THRESHOLD);
+        assert_confidence_values_are_equal(
+            session.current_confidence_values(),
+            detector.compute_language_confidence_values(text),
+            text,
+        );
+        assert_eq!(session.current_language(), Some(English));
+    }
+
+    #[rstest]
+    fn assert_words_split_across_pieces_are_lowercased_as_a_whole(detector: LanguageDetector) {
+        let mut session = detector.start_session();
+        let mut piecewise_session = detector.start_session();
+
+        session.feed("ΟΔΟΣ ΚΑΙ ΔΡΟΜΟΣ ");
+        for piece in ["ΟΔΟ", "Σ ΚΑΙ ΔΡΟΜΟ", "Σ "].iter() {
+            piecewise_session.feed(piece);
+        }
+
+        assert_eq!(piecewise_session.ngrams, session.ngrams);
+        assert_eq!(
+            piecewise_session.character_count(),
+            session.character_count()
+        );
+        assert!(session.ngrams[0].contains(&Ngram::new("ς")));
+        assert!(!session.ngrams[0].contains(&Ngram::new("σ")));
+    }
+
+    #[rstest]
+    fn assert_pending_text_is_taken_into_account_temporarily(detector: LanguageDetector) {
+        let mut session = detector.start_session();
+
+        session.feed("Ich spre");
+        let character_count = session.character_count();
+
+        assert_confidence_values_are_equal(
+            session.current_confidence_values(),
+            detector.compute_language_confidence_values("Ich spre"),
+            "Ich spre",
+        );
+        assert_eq!(session.character_count(), character_count);
+        assert_eq!(session.pending_text, "spre");
+
+        session.feed("che Deutsch");
+
+        assert_eq!(session.current_language(), Some(German));
+        assert_eq!(session.pending_text, "Deutsch");
+    }
+
+    #[rstest]
+    fn assert_session_decides_with_rules(detector: LanguageDetector) {
+        let mut session = detector.start_session();
+        session.feed("groß");
+        assert_eq!(session.current_confidence_values(), vec![(German, 1.0)]);
+    }
+}
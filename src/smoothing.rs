@@ -0,0 +1,220 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::ngram::Ngram;
+use itertools::Itertools;
+
+/// The relative frequency assumed for ngrams which are unknown to a language model
+/// at every order, used by [Smoothing::PenalizedBackOff] and [Smoothing::LinearInterpolation].
+/// It is also the lowest probability an ngram contributes to the goodness of fit of a text.
+pub(crate) const UNKNOWN_NGRAM_FREQUENCY: f64 = 1e-7;
+
+/// This enum specifies how the probability of an ngram is computed from the relative
+/// frequencies stored in the language models, in particular for ngrams which a language
+/// model does not know.
+///
+/// The language models only store relative frequencies, not the counts they have been
+/// computed from. The strategies are therefore applied to relative frequencies directly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing {
+    /// Uses the relative frequency of an ngram or, if it is unknown, of its longest known
+    /// lower-order ngram. Ngrams which are unknown at every order are ignored.
+    ///
+    /// This is the default strategy. Languages with smaller models tend to be favored
+    /// because unknown ngrams do not lower their probabilities.
+    BackOff,
+
+    /// Adds the pseudo-frequency `alpha` to the relative frequency of every ngram and divides
+    /// the sum by `1 + alpha`, without backing off to lower-order ngrams. Unknown ngrams get a
+    /// probability of `alpha / (1 + alpha)`. As the models do not store the number of possible
+    /// ngrams, the probabilities are not renormalized and do not sum up to 1.
+    Additive { alpha: f64 },
+
+    /// Backs off to lower-order ngrams like [Smoothing::BackOff] but multiplies the relative
+    /// frequency by `penalty` for every order backed off, similar to Katz back-off. Ngrams
+    /// which are unknown at every order get a small fixed probability, penalized as well.
+    /// A penalty of 0.4 is a common choice.
+    PenalizedBackOff { penalty: f64 },
+
+    /// Linearly interpolates the relative frequencies of an ngram and of all its lower-order
+    /// ngrams with a fixed weight, similar to Jelinek-Mercer smoothing. The relative frequency
+    /// of each order is weighted with `weight`, the interpolated probability of the next lower
+    /// order with `1 - weight`. Below unigrams, a small fixed probability is assumed.
+    LinearInterpolation { weight: f64 },
+}
+
+impl Smoothing {
+    /// Returns the parameter of this strategy, if any.
+    fn parameter(&self) -> Option<f64> {
+        match *self {
+            Smoothing::BackOff => None,
+            Smoothing::Additive { alpha } => Some(alpha),
+            Smoothing::PenalizedBackOff { penalty } => Some(penalty),
+            Smoothing::LinearInterpolation { weight } => Some(weight),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), LinguaError> {
+        match self.parameter() {
+            Some(parameter) if !(parameter > 0.0 && parameter <= 1.0) => {
+                Err(LinguaError::InvalidSmoothingParameter(parameter))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Computes the probability of the given ngram from the relative frequencies
+    /// returned by `look_up` for the ngram and its lower-order ngrams.
+    pub(crate) fn compute_probability<F>(&self, ngram: &Ngram, look_up: F) -> f64
+    where
+        F: Fn(&Ngram) -> f64,
+    {
+        match *self {
+            Smoothing::BackOff => ngram
+                .range_of_lower_order_ngrams()
+                .map(|it| look_up(&it))
+                .find(|&probability| probability > 0.0)
+                .unwrap_or(0.0),
+
+            Smoothing::Additive { alpha } => (look_up(ngram) + alpha) / (1.0 + alpha),
+
+            Smoothing::PenalizedBackOff { penalty } => {
+                let mut factor = 1.0;
+                for elem in ngram.range_of_lower_order_ngrams() {
+                    let probability = look_up(&elem);
+                    if probability > 0.0 {
+                        return factor * probability;
+                    }
+                    factor *= penalty;
+                }
+                factor * UNKNOWN_NGRAM_FREQUENCY
+            }
+
+            Smoothing::LinearInterpolation { weight } => ngram
+                .range_of_lower_order_ngrams()
+                .collect_vec()
+                .into_iter()
+                .rev()
+                .fold(UNKNOWN_NGRAM_FREQUENCY, |lower_order_probability, elem| {
+                    weight * look_up(&elem) + (1.0 - weight) * lower_order_probability
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use std::collections::HashMap;
+
+    fn look_up(ngram: &Ngram) -> f64 {
+        let frequencies: HashMap<&str, f64> = hashmap!("a" => 0.1, "ab" => 0.5);
+        *frequencies.get(ngram.value.as_str()).unwrap_or(&0.0)
+    }
+
+    #[test]
+    fn assert_back_off_uses_longest_known_ngram() {
+        let smoothing = Smoothing::BackOff;
+
+        assert_eq!(
+            smoothing.compute_probability(&Ngram::new("ab"), look_up),
+            0.5
+        );
+        assert_eq!(
+            smoothing.compute_probability(&Ngram::new("abc"), look_up),
+            0.5
+        );
+        assert_eq!(
+            smoothing.compute_probability(&Ngram::new("bc"), look_up),
+            0.0
+        );
+    }
+
+    #[test]
+    fn assert_additive_smoothing_adds_pseudo_frequency() {
+        let smoothing = Smoothing::Additive { alpha: 0.01 };
+
+        assert!(approx_eq!(
+            f64,
+            smoothing.compute_probability(&Ngram::new("ab"), look_up),
+            0.51 / 1.01,
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            smoothing.compute_probability(&Ngram::new("abc"), look_up),
+            0.01 / 1.01,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn assert_penalized_back_off_penalizes_every_order() {
+        let smoothing = Smoothing::PenalizedBackOff { penalty: 0.4 };
+
+        assert_eq!(
+            smoothing.compute_probability(&Ngram::new("ab"), look_up),
+            0.5
+        );
+        assert!(approx_eq!(
+            f64,
+            smoothing.compute_probability(&Ngram::new("abc"), look_up),
+            0.4 * 0.5,
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            smoothing.compute_probability(&Ngram::new("bc"), look_up),
+            0.4 * 0.4 * UNKNOWN_NGRAM_FREQUENCY,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn assert_interpolation_weights_all_orders() {
+        let smoothing = Smoothing::LinearInterpolation { weight: 0.75 };
+        let unigram_probability = 0.75 * 0.1 + 0.25 * UNKNOWN_NGRAM_FREQUENCY;
+        let bigram_probability = 0.75 * 0.5 + 0.25 * unigram_probability;
+        let trigram_probability = 0.25 * bigram_probability;
+
+        assert!(approx_eq!(
+            f64,
+            smoothing.compute_probability(&Ngram::new("abc"), look_up),
+            trigram_probability,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn assert_invalid_parameters_are_rejected() {
+        assert!(Smoothing::BackOff.validate().is_ok());
+        assert!(Smoothing::Additive { alpha: 1.0 }.validate().is_ok());
+        assert!(matches!(
+            Smoothing::Additive { alpha: 0.0 }.validate(),
+            Err(LinguaError::InvalidSmoothingParameter(_))
+        ));
+        assert!(matches!(
+            Smoothing::PenalizedBackOff { penalty: 1.5 }.validate(),
+            Err(LinguaError::InvalidSmoothingParameter(_))
+        ));
+        assert!(matches!(
+            Smoothing::LinearInterpolation { weight: f64::NAN }.validate(),
+            Err(LinguaError::InvalidSmoothingParameter(_))
+        ));
+    }
+}
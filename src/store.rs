@@ -0,0 +1,782 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::json::{
+    check_language_model_file_exists, load_binary_model_from_directory, load_json,
+    load_json_from_directory,
+};
+use crate::language::Language;
+use crate::model::{LanguageModel, TrainingDataLanguageModel};
+use crate::ngram::Ngram;
+use crate::training::TrainedLanguageModels;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+pub(crate) type BoxedLanguageModel = Box<dyn LanguageModel + Send + Sync>;
+type LanguageModelMap = RwLock<HashMap<Language, LoadedLanguageModel>>;
+
+/// This struct holds the language models used by instances of [LanguageDetector].
+///
+/// Language models are loaded into a store lazily or, if configured, eagerly when building
+/// a detector. A store lives as long as there is a detector using it, so dropping the last
+/// detector releases the memory of all language models in its store.
+///
+/// By default, each detector gets a store of its own. If several detectors are to share
+/// their language models, create a store yourself and pass it to each of them with
+/// [LanguageDetectorBuilder::with_model_store].
+///
+/// [LanguageDetector]: crate::LanguageDetector
+/// [LanguageDetectorBuilder::with_model_store]: crate::LanguageDetectorBuilder::with_model_store
+pub struct ModelStore {
+    unigram_language_models: LanguageModelMap,
+    bigram_language_models: LanguageModelMap,
+    trigram_language_models: LanguageModelMap,
+    quadrigram_language_models: LanguageModelMap,
+    fivegram_language_models: LanguageModelMap,
+    failed_language_models: RwLock<HashSet<(Language, usize)>>,
+    model_directory: Option<PathBuf>,
+    memory_budget: Option<usize>,
+    current_tick: AtomicU64,
+    active_detections: Mutex<BTreeMap<u64, usize>>,
+}
+
+/// Marks a detection in progress. As long as it exists, the language models used since
+/// its start are protected from being unloaded to meet the memory budget.
+pub(crate) struct ActiveDetection<'a> {
+    model_store: &'a ModelStore,
+    start_tick: u64,
+}
+
+impl Drop for ActiveDetection<'_> {
+    fn drop(&mut self) {
+        let mut active_detections = self.model_store.active_detections.lock().unwrap();
+        if let Some(count) = active_detections.get_mut(&self.start_tick) {
+            *count -= 1;
+            if *count == 0 {
+                active_detections.remove(&self.start_tick);
+            }
+        }
+    }
+}
+
+struct LoadedLanguageModel {
+    model: BoxedLanguageModel,
+    memory_size: usize,
+    last_used_tick: AtomicU64,
+    is_inserted: bool,
+}
+
+impl LoadedLanguageModel {
+    fn new(model: BoxedLanguageModel, tick: u64) -> Self {
+        let memory_size = model.memory_size();
+        Self {
+            model,
+            memory_size,
+            last_used_tick: AtomicU64::new(tick),
+            is_inserted: false,
+        }
+    }
+}
+
+impl ModelStore {
+    /// Creates and returns an empty `ModelStore` which loads the language models
+    /// compiled into the binary.
+    pub fn new() -> Self {
+        Self::with_source(None)
+    }
+
+    /// Creates and returns an empty `ModelStore` which loads the language models
+    /// from the given directory. See [LanguageDetectorBuilder::with_model_directory]
+    /// for the expected directory layout.
+    ///
+    /// ⚠ Panics if `directory` does not exist or is not a directory.
+    /// Use [try_from_directory] to get an error instead.
+    ///
+    /// [LanguageDetectorBuilder::with_model_directory]: crate::LanguageDetectorBuilder::with_model_directory
+    /// [try_from_directory]: ModelStore::try_from_directory
+    pub fn from_directory<P: AsRef<Path>>(directory: P) -> Self {
+        Self::try_from_directory(directory).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates and returns an empty `ModelStore` which loads the language models
+    /// from the given directory.
+    ///
+    /// Returns [LinguaError::ModelDirectoryNotFound] if `directory` does not exist
+    /// or is not a directory.
+    pub fn try_from_directory<P: AsRef<Path>>(directory: P) -> Result<Self, LinguaError> {
+        let directory = directory.as_ref();
+        if !directory.is_dir() {
+            return Err(LinguaError::ModelDirectoryNotFound(directory.to_path_buf()));
+        }
+        Ok(Self::with_source(Some(directory.to_path_buf())))
+    }
+
+    /// Limits the approximate amount of memory in bytes that the language models
+    /// in this store may occupy.
+    ///
+    /// Whenever loading a language model exceeds the budget, the least recently used
+    /// language models are unloaded until the budget is met again. Language models
+    /// used since the start of the oldest detection in progress are never unloaded, so the
+    /// budget may be exceeded temporarily while detections require more memory than available.
+    /// Unloaded language models are loaded again on demand.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Inserts language models created in memory into this store, replacing any language
+    /// models of the same language loaded so far.
+    ///
+    /// Detectors using this store look up the inserted language models instead of loading
+    /// them from their source, so the language of the models only needs to be passed to
+    /// [LanguageDetectorBuilder] together with this store. Inserted language models are
+    /// never unloaded to meet the memory budget, only if unloaded explicitly.
+    ///
+    /// [LanguageDetectorBuilder]: crate::LanguageDetectorBuilder
+    pub fn insert_language_models(&self, models: &TrainedLanguageModels) {
+        let tick = self.current_tick.load(Ordering::Relaxed);
+        for ngram_length in 1..6 {
+            let model = models.model(ngram_length).to_lookup_model();
+            let mut loaded_model = LoadedLanguageModel::new(Box::new(model), tick);
+            loaded_model.is_inserted = true;
+            self.language_models(ngram_length)
+                .write()
+                .unwrap()
+                .insert(models.language().clone(), loaded_model);
+        }
+    }
+
+    /// Unloads all language models of the given languages from this store.
+    ///
+    /// Language models which have failed to load are attempted to be loaded again
+    /// the next time they are needed.
+    pub fn unload_language_models(&self, languages: &[Language]) {
+        for ngram_length in 1..6 {
+            let mut models = self.language_models(ngram_length).write().unwrap();
+            models.retain(|language, _| !languages.contains(language));
+        }
+        self.failed_language_models
+            .write()
+            .unwrap()
+            .retain(|(language, _)| !languages.contains(language));
+    }
+
+    /// Unloads the language models of the given ngram lengths for all languages
+    /// from this store. Unigram models have length 1, fivegram models have length 5.
+    ///
+    /// ⚠ Panics if any of the ngram lengths is not in range 1..6.
+    pub fn unload_language_models_of_ngram_lengths(&self, ngram_lengths: &[usize]) {
+        for &ngram_length in ngram_lengths {
+            self.language_models(ngram_length).write().unwrap().clear();
+        }
+        self.failed_language_models
+            .write()
+            .unwrap()
+            .retain(|(_, ngram_length)| !ngram_lengths.contains(ngram_length));
+    }
+
+    /// Returns the approximate amount of memory in bytes occupied by the currently
+    /// loaded language models of each language.
+    pub fn memory_usage(&self) -> HashMap<Language, usize> {
+        let mut memory_usage = HashMap::new();
+        for ngram_length in 1..6 {
+            let models = self.language_models(ngram_length).read().unwrap();
+            for (language, loaded_model) in models.iter() {
+                *memory_usage.entry(language.clone()).or_insert(0) += loaded_model.memory_size;
+            }
+        }
+        memory_usage
+    }
+
+    /// Marks the start of a new detection. Language models used from now on are protected
+    /// from being unloaded to meet the memory budget until the returned value is dropped,
+    /// so that concurrent detections cannot unload each other's language models.
+    pub(crate) fn begin_detection(&self) -> ActiveDetection<'_> {
+        let mut active_detections = self.active_detections.lock().unwrap();
+        let start_tick = self.current_tick.fetch_add(1, Ordering::Relaxed) + 1;
+        *active_detections.entry(start_tick).or_insert(0) += 1;
+        ActiveDetection {
+            model_store: self,
+            start_tick,
+        }
+    }
+
+    pub(crate) fn get_relative_frequency(&self, language: &Language, ngram: &Ngram) -> f64 {
+        let ngram_length = ngram.value.chars().count();
+        let language_models = self.language_models(ngram_length);
+        let tick = self.current_tick.load(Ordering::Relaxed);
+
+        if let Some(loaded_model) = language_models.read().unwrap().get(language) {
+            loaded_model.last_used_tick.store(tick, Ordering::Relaxed);
+            return loaded_model.model.get_relative_frequency(ngram);
+        }
+
+        let key = (language.clone(), ngram_length);
+
+        if self.failed_language_models.read().unwrap().contains(&key) {
+            return 0.0;
+        }
+
+        match self.insert_language_model(language, ngram_length, tick) {
+            Ok(()) => language_models
+                .read()
+                .unwrap()
+                .get(language)
+                .map_or(0.0, |loaded_model| {
+                    loaded_model.model.get_relative_frequency(ngram)
+                }),
+            Err(_) => {
+                self.failed_language_models.write().unwrap().insert(key);
+                0.0
+            }
+        }
+    }
+
+    /// Checks whether the language model of the given language and ngram length is loaded
+    /// or its file exists, without loading it.
+    pub(crate) fn check_language_model(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+    ) -> Result<(), LinguaError> {
+        if self
+            .language_models(ngram_length)
+            .read()
+            .unwrap()
+            .contains_key(language)
+        {
+            return Ok(());
+        }
+        check_language_model_file_exists(
+            self.language_model_directory(language),
+            language,
+            ngram_length,
+        )
+    }
+
+    pub(crate) fn load_language_models(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+    ) -> Result<(), LinguaError> {
+        if self
+            .language_models(ngram_length)
+            .read()
+            .unwrap()
+            .contains_key(language)
+        {
+            return Ok(());
+        }
+        let tick = self.current_tick.fetch_add(1, Ordering::Relaxed) + 1;
+        self.insert_language_model(language, ngram_length, tick)
+    }
+
+    fn insert_language_model(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+        tick: u64,
+    ) -> Result<(), LinguaError> {
+        let model = self.load_language_model(language, ngram_length)?;
+        self.language_models(ngram_length)
+            .write()
+            .unwrap()
+            .entry(language.clone())
+            .or_insert_with(|| LoadedLanguageModel::new(model, tick));
+
+        if let Some(memory_budget) = self.memory_budget {
+            self.evict_language_models(memory_budget, language, ngram_length);
+        }
+
+        Ok(())
+    }
+
+    /// Unloads the least recently used language models until the memory budget is met,
+    /// sparing the given language model and all language models used since the start
+    /// of the oldest detection still in progress.
+    fn evict_language_models(
+        &self,
+        memory_budget: usize,
+        spared_language: &Language,
+        spared_ngram_length: usize,
+    ) {
+        let oldest_active_tick = self
+            .active_detections
+            .lock()
+            .unwrap()
+            .keys()
+            .next()
+            .copied()
+            .unwrap_or(u64::MAX);
+        let mut total_memory_size = 0;
+        let mut candidates = vec![];
+
+        for ngram_length in 1..6 {
+            let models = self.language_models(ngram_length).read().unwrap();
+            for (language, loaded_model) in models.iter() {
+                total_memory_size += loaded_model.memory_size;
+                let last_used_tick = loaded_model.last_used_tick.load(Ordering::Relaxed);
+                let is_spared = loaded_model.is_inserted
+                    || last_used_tick >= oldest_active_tick
+                    || (ngram_length == spared_ngram_length && language == spared_language);
+                if !is_spared {
+                    candidates.push((last_used_tick, ngram_length, language.clone()));
+                }
+            }
+        }
+
+        candidates.sort_by_key(|(last_used_tick, _, _)| *last_used_tick);
+
+        for (_, ngram_length, language) in candidates {
+            if total_memory_size <= memory_budget {
+                break;
+            }
+            let mut models = self.language_models(ngram_length).write().unwrap();
+            if let Some(loaded_model) = models.remove(&language) {
+                total_memory_size -= loaded_model.memory_size;
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn loaded_model_count(&self, ngram_length: usize) -> usize {
+        self.language_models(ngram_length).read().unwrap().len()
+    }
+
+    fn language_models(&self, ngram_length: usize) -> &LanguageModelMap {
+        match ngram_length {
+            5 => &self.fivegram_language_models,
+            4 => &self.quadrigram_language_models,
+            3 => &self.trigram_language_models,
+            2 => &self.bigram_language_models,
+            1 => &self.unigram_language_models,
+            0 => panic!("zerogram detected"),
+            _ => panic!("unsupported ngram length detected: {}", ngram_length),
+        }
+    }
+
+    fn load_language_model(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+    ) -> Result<BoxedLanguageModel, LinguaError> {
+        let json = match self.language_model_directory(language) {
+            Some(directory) => {
+                if let Some(model) =
+                    load_binary_model_from_directory(directory, language.clone(), ngram_length)?
+                {
+                    return Ok(Box::new(model));
+                }
+                load_json_from_directory(directory, language.clone(), ngram_length)?
+            }
+            None => load_json(language.clone(), ngram_length)?,
+        };
+        let model = TrainingDataLanguageModel::from_json(&json).map_err(|err| {
+            LinguaError::CorruptLanguageModel {
+                language: Some(language.clone()),
+                ngram_length,
+                reason: err.to_string(),
+            }
+        })?;
+        Ok(Box::new(model))
+    }
+
+    /// Returns the directory the language models of the given language are loaded from,
+    /// if they are not compiled in.
+    fn language_model_directory<'a>(&'a self, language: &'a Language) -> Option<&'a Path> {
+        match language {
+            Language::Custom(language) => Some(language.model_directory()),
+            _ => self.model_directory.as_deref(),
+        }
+    }
+
+    pub(crate) fn with_source(model_directory: Option<PathBuf>) -> Self {
+        Self {
+            unigram_language_models: RwLock::new(HashMap::new()),
+            bigram_language_models: RwLock::new(HashMap::new()),
+            trigram_language_models: RwLock::new(HashMap::new()),
+            quadrigram_language_models: RwLock::new(HashMap::new()),
+            fivegram_language_models: RwLock::new(HashMap::new()),
+            failed_language_models: RwLock::new(HashSet::new()),
+            model_directory,
+            memory_budget: None,
+            current_tick: AtomicU64::new(0),
+            active_detections: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_language_models(
+        unigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        bigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        trigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        quadrigram_language_models: HashMap<Language, BoxedLanguageModel>,
+        fivegram_language_models: HashMap<Language, BoxedLanguageModel>,
+    ) -> Self {
+        let to_map = |models: HashMap<Language, BoxedLanguageModel>| {
+            RwLock::new(
+                models
+                    .into_iter()
+                    .map(|(language, model)| {
+                        let loaded_model = LoadedLanguageModel {
+                            model,
+                            memory_size: 0,
+                            last_used_tick: AtomicU64::new(0),
+                            is_inserted: true,
+                        };
+                        (language, loaded_model)
+                    })
+                    .collect(),
+            )
+        };
+        Self {
+            unigram_language_models: to_map(unigram_language_models),
+            bigram_language_models: to_map(bigram_language_models),
+            trigram_language_models: to_map(trigram_language_models),
+            quadrigram_language_models: to_map(quadrigram_language_models),
+            fivegram_language_models: to_map(fivegram_language_models),
+            failed_language_models: RwLock::new(HashSet::new()),
+            model_directory: None,
+            memory_budget: None,
+            current_tick: AtomicU64::new(0),
+            active_detections: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl Default for ModelStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::custom::CustomLanguage;
+    use crate::language::Language::*;
+    use crate::{LanguageDetectorBuilder, LanguageModelFilesWriter};
+    use itertools::Itertools;
+    use std::fs::File;
+    use std::io::Write;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn create_model_directory() -> TempDir {
+        let model_directory = tempfile::tempdir().unwrap();
+        for (iso_code, language_name) in [("en", "ENGLISH"), ("de", "GERMAN")] {
+            let language_directory = model_directory.path().join(iso_code);
+            std::fs::create_dir(&language_directory).unwrap();
+
+            for (ngram_length, ngram) in ["a", "ab", "abc", "abcd", "abcde"].iter().enumerate() {
+                write_model_file(&language_directory, language_name, ngram_length + 1, ngram);
+            }
+        }
+        model_directory
+    }
+
+    fn write_model_file(
+        language_directory: &Path,
+        language_name: &str,
+        ngram_length: usize,
+        ngram: &str,
+    ) {
+        let file_name = Ngram::find_ngram_name_by_length(ngram_length);
+        let zip_file =
+            File::create(language_directory.join(format!("{}s.json.zip", file_name))).unwrap();
+        let mut zip = ZipWriter::new(zip_file);
+        zip.start_file(format!("{}s.json", file_name), FileOptions::default())
+            .unwrap();
+        write!(
+            zip,
+            r#"{{"language":"{}","ngrams":{{"1/2":"{}"}}}}"#,
+            language_name, ngram
+        )
+        .unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn load_all_language_models(store: &ModelStore) {
+        for language in [English, German] {
+            for ngram_length in 1..6 {
+                store.load_language_models(&language, ngram_length).unwrap();
+            }
+        }
+    }
+
+    fn detect(store: &ModelStore, language: &Language, ngram: &str) -> f64 {
+        let _detection = store.begin_detection();
+        store.get_relative_frequency(language, &Ngram::new(ngram))
+    }
+
+    fn loaded_languages(store: &ModelStore, ngram_length: usize) -> Vec<Language> {
+        let models = store.language_models(ngram_length).read().unwrap();
+        models.keys().cloned().sorted().collect()
+    }
+
+    #[test]
+    fn assert_detectors_get_stores_of_their_own_by_default() {
+        let first_detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        let second_detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+
+        assert!(!Arc::ptr_eq(
+            &first_detector.model_store,
+            &second_detector.model_store
+        ));
+        assert_eq!(Arc::strong_count(&first_detector.model_store), 1);
+    }
+
+    #[test]
+    fn assert_dedicated_stores_are_isolated() {
+        let first_store = Arc::new(ModelStore::new());
+        let second_store = Arc::new(ModelStore::new());
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_model_store(first_store.clone())
+            .with_preloaded_language_models()
+            .build();
+
+        for ngram_length in 1..6 {
+            let first_models = first_store.language_models(ngram_length).read().unwrap();
+            let second_models = second_store.language_models(ngram_length).read().unwrap();
+
+            assert!(first_models.contains_key(&English));
+            assert!(first_models.contains_key(&German));
+            assert!(second_models.is_empty());
+        }
+
+        assert_eq!(Arc::strong_count(&first_store), 2);
+        drop(detector);
+        assert_eq!(Arc::strong_count(&first_store), 1);
+    }
+
+    #[test]
+    fn assert_language_models_can_be_unloaded_by_language() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path());
+        load_all_language_models(&store);
+
+        store.unload_language_models(&[German]);
+
+        for ngram_length in 1..6 {
+            assert_eq!(loaded_languages(&store, ngram_length), vec![English]);
+        }
+        assert_eq!(
+            store.get_relative_frequency(&German, &Ngram::new("abc")),
+            0.5
+        );
+        assert_eq!(loaded_languages(&store, 3), vec![English, German]);
+    }
+
+    #[test]
+    fn assert_language_models_can_be_unloaded_by_ngram_length() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path());
+        load_all_language_models(&store);
+
+        store.unload_language_models_of_ngram_lengths(&[1, 5]);
+
+        assert!(loaded_languages(&store, 1).is_empty());
+        assert_eq!(loaded_languages(&store, 2), vec![English, German]);
+        assert_eq!(loaded_languages(&store, 3), vec![English, German]);
+        assert_eq!(loaded_languages(&store, 4), vec![English, German]);
+        assert!(loaded_languages(&store, 5).is_empty());
+    }
+
+    #[test]
+    fn assert_memory_usage_is_reported_per_language() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path());
+
+        assert!(store.memory_usage().is_empty());
+
+        load_all_language_models(&store);
+        let memory_usage = store.memory_usage();
+
+        assert_eq!(memory_usage.len(), 2);
+        assert!(memory_usage[&English] > 0);
+        assert!(memory_usage[&German] > 0);
+
+        store.unload_language_models(&[English]);
+
+        assert_eq!(store.memory_usage().keys().collect_vec(), vec![&German]);
+    }
+
+    #[test]
+    fn assert_least_recently_used_language_models_are_evicted() {
+        let model_directory = create_model_directory();
+        let unlimited_store = ModelStore::from_directory(model_directory.path());
+        unlimited_store.load_language_models(&English, 3).unwrap();
+        let model_size = unlimited_store.memory_usage()[&English];
+
+        let store = ModelStore::from_directory(model_directory.path())
+            .with_memory_budget(2 * model_size + 16);
+
+        detect(&store, &English, "abc");
+        detect(&store, &German, "abc");
+        detect(&store, &English, "abc");
+
+        assert_eq!(loaded_languages(&store, 3), vec![English, German]);
+
+        detect(&store, &German, "abcd");
+
+        assert_eq!(loaded_languages(&store, 3), vec![English]);
+        assert_eq!(loaded_languages(&store, 4), vec![German]);
+    }
+
+    #[test]
+    fn assert_language_models_of_current_detection_are_not_evicted() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path()).with_memory_budget(0);
+
+        {
+            let _detection = store.begin_detection();
+            assert_eq!(
+                store.get_relative_frequency(&English, &Ngram::new("ab")),
+                0.5
+            );
+            assert_eq!(
+                store.get_relative_frequency(&German, &Ngram::new("ab")),
+                0.5
+            );
+            assert_eq!(loaded_languages(&store, 2), vec![English, German]);
+        }
+
+        detect(&store, &English, "a");
+
+        assert!(loaded_languages(&store, 2).is_empty());
+        assert_eq!(loaded_languages(&store, 1), vec![English]);
+    }
+
+    #[test]
+    fn assert_language_models_of_concurrent_detections_are_not_evicted() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path()).with_memory_budget(0);
+
+        let first_detection = store.begin_detection();
+        store.get_relative_frequency(&English, &Ngram::new("ab"));
+
+        {
+            let _second_detection = store.begin_detection();
+            store.get_relative_frequency(&German, &Ngram::new("abc"));
+            store.get_relative_frequency(&German, &Ngram::new("abcd"));
+        }
+
+        assert_eq!(
+            store.get_relative_frequency(&English, &Ngram::new("ab")),
+            0.5
+        );
+        assert_eq!(loaded_languages(&store, 2), vec![English]);
+        assert_eq!(loaded_languages(&store, 3), vec![German]);
+        assert_eq!(loaded_languages(&store, 4), vec![German]);
+
+        drop(first_detection);
+        detect(&store, &English, "a");
+
+        assert!(loaded_languages(&store, 2).is_empty());
+        assert!(loaded_languages(&store, 3).is_empty());
+        assert!(loaded_languages(&store, 4).is_empty());
+        assert_eq!(loaded_languages(&store, 1), vec![English]);
+    }
+
+    #[test]
+    fn assert_language_models_failing_to_load_are_not_loaded_again() {
+        let model_directory = create_model_directory();
+        let language_directory = model_directory.path().join("en");
+        std::fs::write(language_directory.join("unigrams.json.zip"), "corrupt").unwrap();
+        let store = ModelStore::from_directory(model_directory.path());
+
+        assert_eq!(
+            store.get_relative_frequency(&English, &Ngram::new("a")),
+            0.0
+        );
+
+        write_model_file(&language_directory, "ENGLISH", 1, "a");
+
+        assert_eq!(
+            store.get_relative_frequency(&English, &Ngram::new("a")),
+            0.0
+        );
+        assert!(loaded_languages(&store, 1).is_empty());
+
+        store.unload_language_models(&[English]);
+
+        assert_eq!(
+            store.get_relative_frequency(&English, &Ngram::new("a")),
+            0.5
+        );
+    }
+
+    #[test]
+    fn assert_missing_language_model_files_are_reported() {
+        let model_directory = create_model_directory();
+        std::fs::remove_file(model_directory.path().join("de").join("bigrams.json.zip")).unwrap();
+        let store = ModelStore::from_directory(model_directory.path());
+
+        assert!(store.check_language_model(&German, 2).is_ok());
+
+        let custom_directory = tempfile::tempdir().unwrap();
+        let language = Language::Custom(CustomLanguage::new(
+            "Klingon",
+            &[Alphabet::Latin],
+            None,
+            custom_directory.path(),
+        ));
+
+        assert!(matches!(
+            store.check_language_model(&language, 2),
+            Err(LinguaError::LanguageModelNotFound {
+                ngram_length: 2,
+                path: Some(path),
+                ..
+            }) if path == custom_directory.path()
+        ));
+        assert!(matches!(
+            LanguageDetectorBuilder::from_languages(&[English, language])
+                .with_model_store(Arc::new(store))
+                .try_build(),
+            Err(LinguaError::LanguageModelNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn assert_inserted_language_models_are_used_and_never_evicted() {
+        let model_directory = create_model_directory();
+        let store = ModelStore::from_directory(model_directory.path()).with_memory_budget(0);
+        let models =
+            LanguageModelFilesWriter::create_language_models(vec!["xyz", "xy"], &English, "\\p{L}")
+                .unwrap();
+
+        store.insert_language_models(&models);
+
+        assert_eq!(detect(&store, &English, "xy"), 1.0);
+        assert_eq!(detect(&store, &English, "ab"), 0.0);
+
+        detect(&store, &German, "ab");
+
+        assert_eq!(loaded_languages(&store, 2), vec![English, German]);
+
+        store.unload_language_models(&[English]);
+
+        assert_eq!(loaded_languages(&store, 2), vec![German]);
+    }
+}
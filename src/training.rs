@@ -0,0 +1,725 @@
+/*
+ * Copyright © 2020-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::LinguaError;
+use crate::language::Language;
+use crate::model::TrainingDataLanguageModel;
+use crate::ngram::Ngram;
+use itertools::Itertools;
+use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const MAXIMUM_NGRAM_LENGTH: usize = 5;
+
+/// The number of bits needed to store any Unicode scalar value.
+const BITS_PER_CHAR: usize = 21;
+
+/// The approximate number of bytes occupied by a single counted ngram in a hash map.
+const BYTES_PER_COUNTED_NGRAM: usize = 2 * size_of::<(u128, u32)>();
+
+/// The number of bytes of a single record in a spill file: the ngram key and its count.
+const SPILL_RECORD_LENGTH: usize = size_of::<u128>() + size_of::<u32>();
+
+/// The maximum number of spill files which are opened at the same time while merging them.
+const MAXIMUM_MERGED_SPILL_FILES: usize = 64;
+
+static SPILL_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// This struct holds the language models of all ngram lengths for a single language
+/// which have been created in memory by [LanguageModelFilesWriter::create_language_models]
+/// or [LanguageModelFilesWriter::create_language_models_from_files].
+///
+/// The models can be written to a directory with
+/// [LanguageModelFilesWriter::write_language_model_files] or be inserted directly into a
+/// [ModelStore] with [ModelStore::insert_language_models] to be used by a detector.
+///
+/// [LanguageModelFilesWriter::create_language_models]: crate::LanguageModelFilesWriter::create_language_models
+/// [LanguageModelFilesWriter::create_language_models_from_files]: crate::LanguageModelFilesWriter::create_language_models_from_files
+/// [LanguageModelFilesWriter::write_language_model_files]: crate::LanguageModelFilesWriter::write_language_model_files
+/// [ModelStore]: crate::ModelStore
+/// [ModelStore::insert_language_models]: crate::ModelStore::insert_language_models
+pub struct TrainedLanguageModels {
+    language: Language,
+    models: Vec<TrainingDataLanguageModel>,
+}
+
+/// This struct configures the creation of language models from very large corpora
+/// with [LanguageModelFilesWriter::create_language_models_from_files_with_options].
+///
+/// By default, all ngrams are counted in memory and kept in the language models.
+/// With a memory budget, the counts are written to temporary files on disk whenever
+/// the budget is exceeded and merged once the whole corpus has been read. A minimum
+/// count removes rare ngrams from the language models, which mostly stem from typos
+/// and foreign words and would otherwise make up the bulk of the models.
+///
+/// [LanguageModelFilesWriter::create_language_models_from_files_with_options]: crate::LanguageModelFilesWriter::create_language_models_from_files_with_options
+#[derive(Clone, Debug)]
+pub struct TrainingOptions {
+    minimum_count: u32,
+    memory_budget: Option<usize>,
+    spill_directory: PathBuf,
+    progress_interval: u64,
+    progress_callback: Option<fn(&Path, u64)>,
+}
+
+impl TrainingOptions {
+    /// Creates and returns the default `TrainingOptions` which count all ngrams in memory
+    /// and keep every ngram in the language models.
+    pub fn new() -> Self {
+        Self {
+            minimum_count: 1,
+            memory_budget: None,
+            spill_directory: std::env::temp_dir(),
+            progress_interval: 0,
+            progress_callback: None,
+        }
+    }
+
+    /// Removes all ngrams which occur less often than `count` in the whole corpus
+    /// from the language models. A count of 0 or 1 keeps all ngrams.
+    pub fn with_minimum_count(mut self, count: u32) -> Self {
+        self.minimum_count = count.max(1);
+        self
+    }
+
+    /// Limits the approximate amount of memory in bytes that the ngram counts may occupy
+    /// while reading the corpus. Whenever the budget is exceeded, the counts are written
+    /// to a temporary file in the spill directory and counting starts afresh.
+    ///
+    /// The budget only applies to counting. The language models created in the end hold
+    /// every ngram reaching the minimum count together with its absolute frequency in a
+    /// hash map and still need to fit into memory, so combine the budget with a minimum
+    /// count for really large corpora.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Sets the directory where the temporary files holding the ngram counts are written to
+    /// if the memory budget is exceeded. It defaults to the temporary directory of the
+    /// operating system. The files are removed as soon as the language models are created.
+    pub fn with_spill_directory<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.spill_directory = directory.as_ref().to_path_buf();
+        self
+    }
+
+    /// Reports the progress of reading the txt files. The given callback is called with the
+    /// path of the file being read and the number of lines read from it so far after every
+    /// `interval` lines and once more when the file has been read completely.
+    pub fn with_progress_callback(mut self, interval: u64, callback: fn(&Path, u64)) -> Self {
+        self.progress_interval = interval.max(1);
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    pub(crate) fn spill_directory(&self) -> &Path {
+        &self.spill_directory
+    }
+
+    // `u64::is_multiple_of` requires Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub(crate) fn report_progress(&self, file_path: &Path, line_count: u64, is_complete: bool) {
+        if let Some(callback) = self.progress_callback {
+            if is_complete || line_count % self.progress_interval == 0 {
+                callback(file_path, line_count);
+            }
+        }
+    }
+}
+
+impl Default for TrainingOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrainedLanguageModels {
+    /// Returns the language the models have been created for.
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// Returns the language model of the given ngram length serialized to JSON,
+    /// in the same format as the files written by
+    /// [LanguageModelFilesWriter::write_language_model_files].
+    ///
+    /// ⚠ Panics if `ngram_length` is not in range 1..6.
+    ///
+    /// [LanguageModelFilesWriter::write_language_model_files]: crate::LanguageModelFilesWriter::write_language_model_files
+    pub fn to_json(&self, ngram_length: usize) -> String {
+        self.model(ngram_length).to_json()
+    }
+
+    /// Returns the language model of the given ngram length serialized to JSON together
+    /// with the absolute frequencies of its ngrams. Language model files containing them
+    /// can be extended with additional training text by
+    /// [LanguageModelFilesWriter::merge_into_language_model_files].
+    ///
+    /// ⚠ Panics if `ngram_length` is not in range 1..6.
+    ///
+    /// [LanguageModelFilesWriter::merge_into_language_model_files]: crate::LanguageModelFilesWriter::merge_into_language_model_files
+    pub fn to_json_with_absolute_frequencies(&self, ngram_length: usize) -> String {
+        self.model(ngram_length).to_json_with_absolute_frequencies()
+    }
+
+    /// Returns the number of distinct ngrams in the language model of the given ngram length.
+    ///
+    /// ⚠ Panics if `ngram_length` is not in range 1..6.
+    pub fn ngram_count(&self, ngram_length: usize) -> usize {
+        self.model(ngram_length).ngram_count()
+    }
+
+    /// Computes the language models from the absolute frequencies of the ngrams
+    /// of every length, starting with the unigrams.
+    pub(crate) fn from_absolute_frequencies(
+        language: &Language,
+        absolute_frequencies: Vec<HashMap<Ngram, u32>>,
+    ) -> Result<Self, LinguaError> {
+        let no_frequencies = HashMap::new();
+        let mut models: Vec<TrainingDataLanguageModel> = Vec::with_capacity(MAXIMUM_NGRAM_LENGTH);
+
+        for (index, frequencies) in absolute_frequencies.into_iter().enumerate() {
+            let lower_ngram_absolute_frequencies = match models.last() {
+                Some(model) => model.absolute_frequencies.as_ref().unwrap(),
+                None => &no_frequencies,
+            };
+            let model = TrainingDataLanguageModel::from_absolute_frequencies(
+                language,
+                index + 1,
+                frequencies,
+                lower_ngram_absolute_frequencies,
+            )?;
+            models.push(model);
+        }
+
+        Ok(Self {
+            language: language.clone(),
+            models,
+        })
+    }
+
+    /// Adds the absolute frequencies of the other models to those of these models
+    /// and recomputes the relative frequencies of all ngrams.
+    pub(crate) fn merge(self, other: TrainedLanguageModels) -> Result<Self, LinguaError> {
+        let absolute_frequencies = self
+            .models
+            .into_iter()
+            .zip(other.models)
+            .map(|(model, other_model)| {
+                let mut frequencies = model.absolute_frequencies.unwrap();
+                for (ngram, frequency) in other_model.absolute_frequencies.unwrap() {
+                    let total = frequencies.entry(ngram).or_insert(0);
+                    *total = total.saturating_add(frequency);
+                }
+                frequencies
+            })
+            .collect();
+
+        Self::from_absolute_frequencies(&self.language, absolute_frequencies)
+    }
+
+    pub(crate) fn model(&self, ngram_length: usize) -> &TrainingDataLanguageModel {
+        if !(1..=MAXIMUM_NGRAM_LENGTH).contains(&ngram_length) {
+            panic!("ngram length {} is not in range 1..6", ngram_length);
+        }
+        &self.models[ngram_length - 1]
+    }
+}
+
+/// Counts the ngrams of all lengths in a single pass over the training text.
+///
+/// Ngrams are counted by compact keys packing their characters into a single integer.
+/// If a memory budget is set, the counts are spilled to sorted run files on disk
+/// which are merged when the language models are computed.
+pub(crate) struct NgramCounter {
+    char_class_regex: Regex,
+    matching_chars: HashMap<char, bool>,
+    absolute_frequencies: Vec<HashMap<u128, u32>>,
+    counted_ngrams: usize,
+    options: TrainingOptions,
+    spill_files: Vec<Vec<SpillFile>>,
+}
+
+/// A temporary file holding ngram counts sorted by key, removed when dropped.
+struct SpillFile {
+    path: PathBuf,
+}
+
+impl NgramCounter {
+    #[cfg(test)]
+    pub(crate) fn new(char_class: &str) -> Result<Self, LinguaError> {
+        Self::with_options(char_class, TrainingOptions::new())
+    }
+
+    pub(crate) fn with_options(
+        char_class: &str,
+        options: TrainingOptions,
+    ) -> Result<Self, LinguaError> {
+        let char_class_regex = Regex::new(&format!("^[{}]$", char_class))
+            .map_err(|_| LinguaError::InvalidCharacterClass(char_class.to_string()))?;
+
+        Ok(Self {
+            char_class_regex,
+            matching_chars: HashMap::new(),
+            absolute_frequencies: vec![HashMap::new(); MAXIMUM_NGRAM_LENGTH],
+            counted_ngrams: 0,
+            options,
+            spill_files: (0..MAXIMUM_NGRAM_LENGTH).map(|_| vec![]).collect(),
+        })
+    }
+
+    /// Counts all ngrams of the given line that consist of matching characters only.
+    pub(crate) fn count_line(&mut self, line: &str) -> io::Result<()> {
+        let chars = line.to_lowercase().chars().collect_vec();
+        let is_matching = chars
+            .iter()
+            .map(|&character| self.is_matching_char(character))
+            .collect_vec();
+
+        for start in 0..chars.len() {
+            let mut key = 0;
+            for ngram_length in 1..=MAXIMUM_NGRAM_LENGTH {
+                let end = start + ngram_length;
+                if end > chars.len() || !is_matching[end - 1] {
+                    break;
+                }
+                key = (key << BITS_PER_CHAR) | chars[end - 1] as u128;
+                let counted_ngrams = &mut self.counted_ngrams;
+                let counter = self.absolute_frequencies[ngram_length - 1]
+                    .entry(key)
+                    .or_insert_with(|| {
+                        *counted_ngrams += 1;
+                        0
+                    });
+                *counter = counter.saturating_add(1);
+            }
+        }
+
+        if let Some(memory_budget) = self.options.memory_budget {
+            if self.counted_ngrams * BYTES_PER_COUNTED_NGRAM > memory_budget {
+                self.spill()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn into_absolute_frequencies(self, ngram_length: usize) -> HashMap<Ngram, u32> {
+        self.into_language_models(&Language::English)
+            .unwrap()
+            .models
+            .into_iter()
+            .nth(ngram_length - 1)
+            .unwrap()
+            .absolute_frequencies
+            .unwrap()
+    }
+
+    /// Computes the language models of all ngram lengths from the counted ngrams,
+    /// merging the counts spilled to disk and removing ngrams below the minimum count.
+    ///
+    /// The resulting models map every remaining ngram to its count in memory,
+    /// regardless of the memory budget.
+    pub(crate) fn into_language_models(
+        mut self,
+        language: &Language,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        if self.spill_files.iter().any(|files| !files.is_empty()) {
+            self.spill()?;
+        }
+
+        let minimum_count = self.options.minimum_count;
+        let mut absolute_frequencies = Vec::with_capacity(MAXIMUM_NGRAM_LENGTH);
+
+        for (index, (counts, spill_files)) in self
+            .absolute_frequencies
+            .into_iter()
+            .zip(self.spill_files)
+            .enumerate()
+        {
+            let ngram_length = index + 1;
+            let mut frequencies = HashMap::new();
+            let mut insert = |key: u128, count: u32| {
+                if count >= minimum_count {
+                    frequencies.insert(decode_ngram(key, ngram_length), count);
+                }
+                Ok(())
+            };
+
+            if spill_files.is_empty() {
+                for (key, count) in counts.into_iter() {
+                    insert(key, count)?;
+                }
+            } else {
+                let spill_files = reduce_spill_files(
+                    spill_files,
+                    MAXIMUM_MERGED_SPILL_FILES,
+                    self.options.spill_directory(),
+                )?;
+                merge_spill_files(&spill_files, insert)?;
+            }
+
+            absolute_frequencies.push(frequencies);
+        }
+
+        TrainedLanguageModels::from_absolute_frequencies(language, absolute_frequencies)
+    }
+
+    /// Writes the current counts of every ngram length to a new spill file
+    /// sorted by key and clears them.
+    fn spill(&mut self) -> io::Result<()> {
+        for (counts, spill_files) in self
+            .absolute_frequencies
+            .iter_mut()
+            .zip(self.spill_files.iter_mut())
+        {
+            let spill_file = SpillFile::create(self.options.spill_directory())?;
+            let mut writer = BufWriter::new(File::create(&spill_file.path)?);
+
+            for (key, count) in counts.drain().sorted_unstable_by_key(|(key, _)| *key) {
+                SpillFile::write_record(&mut writer, key, count)?;
+            }
+
+            writer.flush()?;
+            spill_files.push(spill_file);
+        }
+
+        self.counted_ngrams = 0;
+        Ok(())
+    }
+
+    fn is_matching_char(&mut self, character: char) -> bool {
+        let char_class_regex = &self.char_class_regex;
+        *self
+            .matching_chars
+            .entry(character)
+            .or_insert_with(|| char_class_regex.is_match(character.encode_utf8(&mut [0; 4])))
+    }
+}
+
+impl SpillFile {
+    fn create(directory: &Path) -> io::Result<Self> {
+        let path = directory.join(format!(
+            "lingua-ngrams-{}-{}.bin",
+            process::id(),
+            SPILL_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        Ok(Self { path })
+    }
+
+    fn write_record<W: Write>(writer: &mut W, key: u128, count: u32) -> io::Result<()> {
+        writer.write_all(&key.to_le_bytes())?;
+        writer.write_all(&count.to_le_bytes())
+    }
+
+    fn read_record<R: Read>(reader: &mut R) -> io::Result<Option<(u128, u32)>> {
+        let mut record = [0; SPILL_RECORD_LENGTH];
+        match reader.read_exact(&mut record) {
+            Ok(()) => {
+                let mut key = [0; size_of::<u128>()];
+                let mut count = [0; size_of::<u32>()];
+                key.copy_from_slice(&record[..size_of::<u128>()]);
+                count.copy_from_slice(&record[size_of::<u128>()..]);
+                Ok(Some((u128::from_le_bytes(key), u32::from_le_bytes(count))))
+            }
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Merges the sorted spill files in several passes until at most `maximum_files` remain,
+/// so that no more than `maximum_files` files are opened at the same time. Each pass
+/// merges groups of `maximum_files` files into a single new spill file.
+fn reduce_spill_files(
+    mut spill_files: Vec<SpillFile>,
+    maximum_files: usize,
+    directory: &Path,
+) -> io::Result<Vec<SpillFile>> {
+    let maximum_files = maximum_files.max(2);
+
+    while spill_files.len() > maximum_files {
+        let mut merged_files = Vec::with_capacity(spill_files.len() / maximum_files + 1);
+
+        for group in spill_files.chunks(maximum_files) {
+            let merged_file = SpillFile::create(directory)?;
+            let mut writer = BufWriter::new(File::create(&merged_file.path)?);
+            merge_spill_files(group, |key, count| {
+                SpillFile::write_record(&mut writer, key, count)
+            })?;
+            writer.flush()?;
+            merged_files.push(merged_file);
+        }
+
+        spill_files = merged_files;
+    }
+
+    Ok(spill_files)
+}
+
+/// Merges the sorted spill files, passing each key together with its total count
+/// in ascending key order to the given function.
+fn merge_spill_files<F: FnMut(u128, u32) -> io::Result<()>>(
+    spill_files: &[SpillFile],
+    mut consume: F,
+) -> io::Result<()> {
+    let mut readers = spill_files
+        .iter()
+        .map(|spill_file| File::open(&spill_file.path).map(BufReader::new))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::new();
+
+    for (index, reader) in readers.iter_mut().enumerate() {
+        if let Some((key, count)) = SpillFile::read_record(reader)? {
+            heap.push(Reverse((key, index, count)));
+        }
+    }
+
+    let mut current: Option<(u128, u32)> = None;
+
+    while let Some(Reverse((key, index, count))) = heap.pop() {
+        current = match current {
+            Some((current_key, current_count)) if current_key == key => {
+                Some((key, current_count.saturating_add(count)))
+            }
+            Some((current_key, current_count)) => {
+                consume(current_key, current_count)?;
+                Some((key, count))
+            }
+            None => Some((key, count)),
+        };
+        if let Some((key, count)) = SpillFile::read_record(&mut readers[index])? {
+            heap.push(Reverse((key, index, count)));
+        }
+    }
+
+    if let Some((key, count)) = current {
+        consume(key, count)?;
+    }
+
+    Ok(())
+}
+
+fn decode_ngram(key: u128, ngram_length: usize) -> Ngram {
+    let value = (0..ngram_length)
+        .rev()
+        .map(|index| {
+            let code_point = (key >> (index * BITS_PER_CHAR)) as u32 & ((1 << BITS_PER_CHAR) - 1);
+            char::from_u32(code_point).unwrap()
+        })
+        .collect::<String>();
+    Ngram::new(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_ngrams_of_all_lengths_are_counted_in_one_pass() {
+        let mut counter = NgramCounter::new("\\p{L}").unwrap();
+        counter.count_line("Aber abe!").unwrap();
+        counter.count_line("ab").unwrap();
+
+        let models = counter.into_language_models(&Language::German).unwrap();
+
+        assert_eq!(models.language(), &Language::German);
+        assert_eq!(
+            models.model(1).absolute_frequencies,
+            Some(map_keys_to_ngrams(
+                hashmap!("a" => 3, "b" => 3, "e" => 2, "r" => 1)
+            ))
+        );
+        assert_eq!(
+            models.model(2).absolute_frequencies,
+            Some(map_keys_to_ngrams(
+                hashmap!("ab" => 3, "be" => 2, "er" => 1)
+            ))
+        );
+        assert_eq!(
+            models.model(4).absolute_frequencies,
+            Some(map_keys_to_ngrams(hashmap!("aber" => 1)))
+        );
+        assert_eq!(models.model(5).absolute_frequencies, Some(hashmap!()));
+    }
+
+    #[test]
+    fn assert_characters_outside_of_character_class_are_skipped() {
+        let mut counter = NgramCounter::new("a-z").unwrap();
+        counter.count_line("ab1cd é").unwrap();
+
+        assert_eq!(
+            counter.into_absolute_frequencies(2),
+            map_keys_to_ngrams(hashmap!("ab" => 1, "cd" => 1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ngram length 6 is not in range 1..6")]
+    fn assert_unsupported_ngram_length_panics() {
+        let counter = NgramCounter::new("\\p{L}").unwrap();
+        counter
+            .into_language_models(&Language::English)
+            .unwrap()
+            .to_json(6);
+    }
+
+    #[test]
+    fn assert_spilled_counts_are_merged_and_pruned() {
+        let spill_directory = tempfile::tempdir().unwrap();
+        let lines = ["abc abd", "ab xyz", "abc", "zzz abcd"];
+
+        let mut in_memory_counter = NgramCounter::new("\\p{L}").unwrap();
+        let mut spilling_counter = NgramCounter::with_options(
+            "\\p{L}",
+            TrainingOptions::new()
+                .with_memory_budget(0)
+                .with_spill_directory(spill_directory.path()),
+        )
+        .unwrap();
+
+        for line in lines.iter() {
+            in_memory_counter.count_line(line).unwrap();
+            spilling_counter.count_line(line).unwrap();
+        }
+
+        assert_eq!(
+            std::fs::read_dir(spill_directory.path()).unwrap().count(),
+            lines.len() * MAXIMUM_NGRAM_LENGTH
+        );
+
+        let in_memory_models = in_memory_counter
+            .into_language_models(&Language::English)
+            .unwrap();
+        let spilled_models = spilling_counter
+            .into_language_models(&Language::English)
+            .unwrap();
+
+        for ngram_length in 1..6 {
+            assert_eq!(
+                spilled_models.to_json(ngram_length),
+                in_memory_models.to_json(ngram_length)
+            );
+        }
+        assert_eq!(
+            std::fs::read_dir(spill_directory.path()).unwrap().count(),
+            0
+        );
+
+        let mut pruning_counter = NgramCounter::with_options(
+            "\\p{L}",
+            TrainingOptions::new()
+                .with_minimum_count(3)
+                .with_memory_budget(0)
+                .with_spill_directory(spill_directory.path()),
+        )
+        .unwrap();
+
+        for line in lines.iter() {
+            pruning_counter.count_line(line).unwrap();
+        }
+
+        let pruned_models = pruning_counter
+            .into_language_models(&Language::English)
+            .unwrap();
+
+        assert_eq!(
+            pruned_models.model(2).absolute_frequencies,
+            Some(map_keys_to_ngrams(hashmap!("ab" => 5, "bc" => 3)))
+        );
+        assert_eq!(
+            pruned_models.model(3).absolute_frequencies,
+            Some(map_keys_to_ngrams(hashmap!("abc" => 3)))
+        );
+    }
+
+    #[test]
+    fn assert_spill_files_are_merged_in_bounded_passes() {
+        let spill_directory = tempfile::tempdir().unwrap();
+        let mut counter = NgramCounter::with_options(
+            "\\p{L}",
+            TrainingOptions::new()
+                .with_memory_budget(0)
+                .with_spill_directory(spill_directory.path()),
+        )
+        .unwrap();
+
+        for line in ["abc", "abd", "bcd", "abc", "xab", "ab", "cab"].iter() {
+            counter.count_line(line).unwrap();
+        }
+
+        let spill_files = counter.spill_files.remove(1);
+        assert_eq!(spill_files.len(), 7);
+
+        let read_counts = |spill_files: &[SpillFile]| {
+            let mut counts = vec![];
+            merge_spill_files(spill_files, |key, count| {
+                counts.push((decode_ngram(key, 2).value, count));
+                Ok(())
+            })
+            .unwrap();
+            counts
+        };
+        let expected_counts = read_counts(&spill_files);
+
+        let reduced_files = reduce_spill_files(spill_files, 2, spill_directory.path()).unwrap();
+
+        assert_eq!(reduced_files.len(), 2);
+        assert_eq!(read_counts(&reduced_files), expected_counts);
+        assert_eq!(
+            expected_counts,
+            vec![
+                ("ab".to_string(), 6),
+                ("bc".to_string(), 3),
+                ("bd".to_string(), 1),
+                ("ca".to_string(), 1),
+                ("cd".to_string(), 1),
+                ("xa".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn assert_ngram_keys_are_decoded_correctly() {
+        let mut counter = NgramCounter::new("\\p{L}").unwrap();
+        counter.count_line("ÄÖ𝔘ëé").unwrap();
+
+        assert_eq!(
+            counter.into_absolute_frequencies(5),
+            map_keys_to_ngrams(hashmap!("äö𝔘ëé" => 1))
+        );
+    }
+
+    fn map_keys_to_ngrams(map: HashMap<&str, u32>) -> HashMap<Ngram, u32> {
+        map.into_iter()
+            .map(|(key, value)| (Ngram::new(key), value))
+            .collect()
+    }
+}
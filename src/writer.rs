@@ -15,13 +15,16 @@
  */
 
 use crate::constant::{MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION};
-use crate::model::TrainingDataLanguageModel;
-use crate::ngram::Ngram;
+use crate::error::LinguaError;
+use crate::json::{
+    get_binary_language_model_file_name, get_language_model_file_name, read_zipped_json,
+};
+use crate::model::{BinaryLanguageModel, TrainingDataLanguageModel};
+use crate::training::{NgramCounter, TrainedLanguageModels, TrainingOptions};
 use crate::Language;
 use itertools::Itertools;
 use regex::Regex;
-use std::collections::HashMap;
-use std::fs::{remove_file, File};
+use std::fs::{remove_file, rename, File};
 use std::io;
 use std::io::{BufRead, BufReader, LineWriter, Write};
 use std::path::Path;
@@ -49,121 +52,438 @@ impl LanguageModelFilesWriter {
     /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
     /// that the language models are built from.
     ///
-    /// ⚠ Panics if:
+    /// Returns a [LinguaError] if:
     /// - the input file path is not absolute or does not point to an existing txt file
     /// - the input file's encoding is not UTF-8
     /// - the output directory path is not absolute or does not point to an existing directory
     /// - the character class cannot be compiled to a valid regular expression
+    /// - the training text contains more than `u32::MAX` matching characters, in which case
+    ///   [LinguaError::TooManyNgrams] is returned
     pub fn create_and_write_language_model_files(
         input_file_path: &Path,
         output_directory_path: &Path,
         language: &Language,
         char_class: &str,
-    ) -> io::Result<()> {
-        check_input_file_path(input_file_path);
-        check_output_directory_path(output_directory_path);
+    ) -> Result<(), LinguaError> {
+        check_input_file_path(input_file_path)?;
+        check_output_directory_path(output_directory_path)?;
+        check_character_class(char_class)?;
 
-        let unigram_model =
-            Self::create_language_model(input_file_path, language, 1, char_class, &hashmap!())?;
+        let models =
+            Self::create_language_models_from_files(&[input_file_path], language, char_class)?;
 
-        let bigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            2,
-            char_class,
-            unigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        Self::write_language_model_files(&models, output_directory_path)
+    }
 
-        let trigram_model = Self::create_language_model(
-            input_file_path,
+    /// Creates the language models of all ngram lengths in memory from the given lines of text
+    /// in a single pass.
+    ///
+    /// `lines`: The lines of text used for language model creation, such as `&str` or `String`
+    /// values. They are consumed one by one, so lines read lazily are never held in memory
+    /// all at once.
+    ///
+    /// `language`: The language for which to create language models.
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the language models are built from.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - the character class cannot be compiled to a valid regular expression
+    /// - the training text contains more than `u32::MAX` matching characters, in which case
+    ///   [LinguaError::TooManyNgrams] is returned
+    pub fn create_language_models<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        lines: I,
+        language: &Language,
+        char_class: &str,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        Self::create_language_models_with_options(
+            lines,
             language,
-            3,
             char_class,
-            bigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+            &TrainingOptions::new(),
+        )
+    }
 
-        let quadrigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            4,
-            char_class,
-            trigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+    /// Creates the language models of all ngram lengths in memory from the given lines of text
+    /// in a single pass, configured by the given [TrainingOptions].
+    ///
+    /// See [create_language_models] for the other parameters.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - the character class cannot be compiled to a valid regular expression
+    /// - the spill directory does not exist or does not represent a directory
+    /// - the ngram counts cannot be spilled to the spill directory
+    /// - the training text contains more than `u32::MAX` matching characters, in which case
+    ///   [LinguaError::TooManyNgrams] is returned
+    ///
+    /// [create_language_models]: LanguageModelFilesWriter::create_language_models
+    pub fn create_language_models_with_options<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        lines: I,
+        language: &Language,
+        char_class: &str,
+        options: &TrainingOptions,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        check_spill_directory(options)?;
 
-        let fivegram_model = Self::create_language_model(
-            input_file_path,
+        let mut counter = NgramCounter::with_options(char_class, options.clone())?;
+
+        for line in lines {
+            counter.count_line(line.as_ref())?;
+        }
+
+        counter.into_language_models(language)
+    }
+
+    /// Creates the language models of all ngram lengths in memory from the given txt files
+    /// in a single pass. The files are read line by line, so they are never held in memory
+    /// as a whole.
+    ///
+    /// `input_file_paths`: The paths to txt files used for language model creation.
+    /// The assumed encoding of the txt files is UTF-8.
+    ///
+    /// `language`: The language for which to create language models.
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the language models are built from.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - one of the input file paths is not absolute or does not point to an existing txt file
+    /// - one of the input files' encoding is not UTF-8
+    /// - the character class cannot be compiled to a valid regular expression
+    /// - the training text contains more than `u32::MAX` matching characters, in which case
+    ///   [LinguaError::TooManyNgrams] is returned
+    pub fn create_language_models_from_files(
+        input_file_paths: &[&Path],
+        language: &Language,
+        char_class: &str,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        Self::create_language_models_from_files_with_options(
+            input_file_paths,
             language,
-            5,
             char_class,
-            quadrigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+            &TrainingOptions::new(),
+        )
+    }
 
-        Self::write_compressed_language_model(
-            &unigram_model,
-            output_directory_path,
-            "unigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &bigram_model,
-            output_directory_path,
-            "bigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &trigram_model,
-            output_directory_path,
-            "trigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &quadrigram_model,
-            output_directory_path,
-            "quadrigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &fivegram_model,
-            output_directory_path,
-            "fivegrams.json",
-        )?;
+    /// Creates the language models of all ngram lengths in memory from the given txt files
+    /// in a single pass, configured by the given [TrainingOptions].
+    ///
+    /// Use this method for corpora too large to count all of their ngrams in memory.
+    /// See [create_language_models_from_files] for the other parameters.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - one of the input file paths is not absolute or does not point to an existing txt file
+    /// - one of the input files' encoding is not UTF-8
+    /// - the character class cannot be compiled to a valid regular expression
+    /// - the spill directory does not exist or does not represent a directory
+    /// - the ngram counts cannot be spilled to the spill directory
+    /// - the training text contains more than `u32::MAX` matching characters, in which case
+    ///   [LinguaError::TooManyNgrams] is returned
+    ///
+    /// [create_language_models_from_files]: LanguageModelFilesWriter::create_language_models_from_files
+    pub fn create_language_models_from_files_with_options(
+        input_file_paths: &[&Path],
+        language: &Language,
+        char_class: &str,
+        options: &TrainingOptions,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        for input_file_path in input_file_paths.iter() {
+            check_input_file_path(input_file_path)?;
+        }
+        check_spill_directory(options)?;
+
+        let mut counter = NgramCounter::with_options(char_class, options.clone())?;
+
+        for input_file_path in input_file_paths.iter() {
+            let reader = BufReader::new(File::open(input_file_path)?);
+            let mut line_count = 0;
+            for line in reader.lines() {
+                counter.count_line(&line?)?;
+                line_count += 1;
+                options.report_progress(input_file_path, line_count, false);
+            }
+            options.report_progress(input_file_path, line_count, true);
+        }
+
+        counter.into_language_models(language)
+    }
+
+    /// Writes language models created in memory to a directory as the files
+    /// `unigrams.json.zip`, `bigrams.json.zip`, `trigrams.json.zip`, `quadrigrams.json.zip`
+    /// and `fivegrams.json.zip`.
+    ///
+    /// `models`: The language models as created by [create_language_models] or
+    /// [create_language_models_from_files].
+    ///
+    /// `output_directory_path`: The path to an existing directory where the language model files
+    /// are to be written.
+    ///
+    /// Returns a [LinguaError] if the output directory path is not absolute or does not point
+    /// to an existing directory.
+    ///
+    /// [create_language_models]: LanguageModelFilesWriter::create_language_models
+    /// [create_language_models_from_files]: LanguageModelFilesWriter::create_language_models_from_files
+    pub fn write_language_model_files(
+        models: &TrainedLanguageModels,
+        output_directory_path: &Path,
+    ) -> Result<(), LinguaError> {
+        check_output_directory_path(output_directory_path)?;
+
+        for ngram_length in 1..6 {
+            Self::write_compressed_language_model(
+                &models.to_json(ngram_length),
+                output_directory_path,
+                ngram_length,
+            )?;
+        }
 
         Ok(())
     }
 
-    fn create_language_model(
-        input_file_path: &Path,
+    /// Writes language models created in memory to a directory like
+    /// [write_language_model_files] but includes the absolute frequencies of all ngrams.
+    ///
+    /// The files are larger than without absolute frequencies but can be extended
+    /// with additional training text by [merge_into_language_model_files] later on.
+    /// Detection with these files works the same.
+    ///
+    /// Returns a [LinguaError] if the output directory path is not absolute or does not point
+    /// to an existing directory.
+    ///
+    /// [write_language_model_files]: LanguageModelFilesWriter::write_language_model_files
+    /// [merge_into_language_model_files]: LanguageModelFilesWriter::merge_into_language_model_files
+    pub fn write_language_model_files_with_absolute_frequencies(
+        models: &TrainedLanguageModels,
+        output_directory_path: &Path,
+    ) -> Result<(), LinguaError> {
+        check_output_directory_path(output_directory_path)?;
+
+        for ngram_length in 1..6 {
+            Self::write_compressed_language_model(
+                &models.to_json_with_absolute_frequencies(ngram_length),
+                output_directory_path,
+                ngram_length,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Merges additional training text into existing language model files.
+    ///
+    /// The ngrams of the input files are counted and added to the absolute frequencies
+    /// stored in the language model files. The relative frequencies of all ngrams are
+    /// then recomputed and the files are replaced, including the updated absolute
+    /// frequencies, so that they can be extended again later on. The merged language models
+    /// are returned as well.
+    ///
+    /// The merged language models are written to temporary files in the model directory
+    /// first. Only once all five of them have been written, they are renamed to replace the
+    /// existing files, so a failure while writing leaves the existing files untouched.
+    ///
+    /// `input_file_paths`: The paths to txt files containing the additional training text.
+    /// The assumed encoding of the txt files is UTF-8.
+    ///
+    /// `model_directory_path`: The path to a directory containing the files `unigrams.json.zip`
+    /// to `fivegrams.json.zip` as written by
+    /// [write_language_model_files_with_absolute_frequencies].
+    ///
+    /// `language`: The language of the existing language models. The merged language models
+    /// belong to this language, so a [Language::Custom] language keeps its alphabets and
+    /// model directory and the models can be passed to [ModelStore::insert_language_models].
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the language models are built from. It should be the same one that the existing
+    /// language models have been created with.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - one of the input file paths is not absolute or does not point to an existing txt file
+    /// - one of the input files' encoding is not UTF-8
+    /// - the model directory path is not absolute or does not point to an existing directory
+    /// - one of the language model files is missing, invalid or contains no absolute frequencies
+    /// - one of the language model files contains a language model for another language
+    /// - the character class cannot be compiled to a valid regular expression
+    /// - the merged training text contains more than `u32::MAX` matching characters,
+    ///   in which case [LinguaError::TooManyNgrams] is returned
+    ///
+    /// [write_language_model_files_with_absolute_frequencies]: LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies
+    /// [ModelStore::insert_language_models]: crate::ModelStore::insert_language_models
+    pub fn merge_into_language_model_files(
+        input_file_paths: &[&Path],
+        model_directory_path: &Path,
         language: &Language,
-        ngram_length: usize,
         char_class: &str,
-        lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
-    ) -> io::Result<TrainingDataLanguageModel> {
-        let file = File::open(input_file_path)?;
-        let reader = BufReader::new(file);
-        let lines = reader
-            .lines()
-            .map(|line| line.unwrap())
-            .filter(|line| !line.trim().is_empty())
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        check_output_directory_path(model_directory_path)?;
+
+        let existing_models = Self::read_language_model_files(model_directory_path, language)?;
+        let additional_models =
+            Self::create_language_models_from_files(input_file_paths, language, char_class)?;
+
+        let merged_models = existing_models.merge(additional_models)?;
+        let temporary_file_paths = (1..6)
+            .map(|ngram_length| {
+                model_directory_path.join(format!(
+                    "{}.tmp",
+                    get_language_model_file_name(ngram_length)
+                ))
+            })
             .collect_vec();
-        let lines_as_str = lines.iter().map(|line| line.as_str()).collect_vec();
 
-        Ok(TrainingDataLanguageModel::from_text(
-            &lines_as_str,
-            language,
-            ngram_length,
-            char_class,
-            lower_ngram_absolute_frequencies,
-        ))
+        let written = (1..6).zip(temporary_file_paths.iter()).try_for_each(
+            |(ngram_length, temporary_file_path)| {
+                Self::write_compressed_language_model_file(
+                    &merged_models.to_json_with_absolute_frequencies(ngram_length),
+                    temporary_file_path,
+                    &get_language_model_file_name(ngram_length),
+                )
+            },
+        );
+
+        if let Err(err) = written {
+            for temporary_file_path in temporary_file_paths.iter() {
+                let _ = remove_file(temporary_file_path);
+            }
+            return Err(err.into());
+        }
+
+        for (ngram_length, temporary_file_path) in (1..6).zip(temporary_file_paths.iter()) {
+            rename(
+                temporary_file_path,
+                model_directory_path.join(get_language_model_file_name(ngram_length)),
+            )?;
+        }
+
+        Ok(merged_models)
+    }
+
+    /// Converts zipped JSON language model files into the compact binary format
+    /// which can be loaded without any parsing.
+    ///
+    /// `input_directory_path`: The path to a directory containing the files `unigrams.json.zip`,
+    /// `bigrams.json.zip`, `trigrams.json.zip`, `quadrigrams.json.zip` and `fivegrams.json.zip`
+    /// as written by [create_and_write_language_model_files]. Missing files are skipped.
+    ///
+    /// `output_directory_path`: The path to an existing directory where the binary language
+    /// model files `unigrams.bin` to `fivegrams.bin` are to be written.
+    ///
+    /// When loading language models from a directory with
+    /// [LanguageDetectorBuilder::with_model_directory], binary files take precedence over
+    /// zipped JSON files. They are read into memory in full when they are loaded, not
+    /// memory-mapped. The compiled-in language models are always zipped JSON files
+    /// which are parsed when they are loaded.
+    ///
+    /// Returns a [LinguaError] if:
+    /// - the input directory path does not point to an existing directory
+    /// - the output directory path is not absolute or does not point to an existing directory
+    /// - one of the input files is not a valid zipped JSON language model, in which case
+    ///   [LinguaError::CorruptLanguageModel] is returned
+    ///
+    /// [create_and_write_language_model_files]: LanguageModelFilesWriter::create_and_write_language_model_files
+    /// [LanguageDetectorBuilder::with_model_directory]: crate::LanguageDetectorBuilder::with_model_directory
+    pub fn convert_language_model_files_to_binary(
+        input_directory_path: &Path,
+        output_directory_path: &Path,
+    ) -> Result<(), LinguaError> {
+        if !input_directory_path.is_dir() {
+            return Err(LinguaError::InputDirectoryNotFound(
+                input_directory_path.to_path_buf(),
+            ));
+        }
+        check_output_directory_path(output_directory_path)?;
+
+        for ngram_length in 1..6 {
+            let input_file_path =
+                input_directory_path.join(get_language_model_file_name(ngram_length));
+
+            if !input_file_path.is_file() {
+                continue;
+            }
+
+            let corrupt_file_error = |reason: String| {
+                corrupt_language_model_file_error(&input_file_path, ngram_length, reason)
+            };
+            let json =
+                read_zipped_json(File::open(&input_file_path)?).map_err(corrupt_file_error)?;
+            let model = TrainingDataLanguageModel::from_json(&json)
+                .map_err(|err| corrupt_file_error(err.to_string()))?;
+            let output_file_path =
+                output_directory_path.join(get_binary_language_model_file_name(ngram_length));
+
+            File::create(output_file_path)?
+                .write_all(&BinaryLanguageModel::to_bytes(&model, ngram_length))?;
+        }
+
+        Ok(())
+    }
+
+    fn read_language_model_files(
+        model_directory_path: &Path,
+        language: &Language,
+    ) -> Result<TrainedLanguageModels, LinguaError> {
+        let mut absolute_frequencies = Vec::with_capacity(5);
+
+        for ngram_length in 1..6 {
+            let file_path = model_directory_path.join(get_language_model_file_name(ngram_length));
+            let corrupt_file_error = |reason: String| {
+                corrupt_language_model_file_error(&file_path, ngram_length, reason)
+            };
+            let json = read_zipped_json(File::open(&file_path)?).map_err(corrupt_file_error)?;
+            let (file_language, frequencies) =
+                TrainingDataLanguageModel::read_absolute_frequencies(&json)
+                    .map_err(|err| corrupt_file_error(err.to_string()))?;
+
+            // A deserialized custom language only retains its name,
+            // so custom languages are compared by name.
+            let is_expected_language = match (language, &file_language) {
+                (Language::Custom(expected), Language::Custom(actual)) => {
+                    expected.name() == actual.name()
+                }
+                _ => *language == file_language,
+            };
+
+            if !is_expected_language {
+                return Err(corrupt_file_error(format!(
+                    "file contains a language model for {:?} instead of {:?}",
+                    file_language, language
+                )));
+            }
+
+            absolute_frequencies.push(frequencies.ok_or_else(|| {
+                corrupt_file_error("file contains no absolute frequencies".to_string())
+            })?);
+        }
+
+        TrainedLanguageModels::from_absolute_frequencies(language, absolute_frequencies)
     }
 
     fn write_compressed_language_model(
-        model: &TrainingDataLanguageModel,
+        json: &str,
         output_directory_path: &Path,
-        file_name: &str,
+        ngram_length: usize,
+    ) -> io::Result<()> {
+        let zip_file_name = get_language_model_file_name(ngram_length);
+        Self::write_compressed_language_model_file(
+            json,
+            &output_directory_path.join(&zip_file_name),
+            &zip_file_name,
+        )
+    }
+
+    fn write_compressed_language_model_file(
+        json: &str,
+        zip_file_path: &Path,
+        zip_file_name: &str,
     ) -> io::Result<()> {
-        let zip_file_name = format!("{}.zip", file_name);
-        let zip_file_path = output_directory_path.join(zip_file_name);
         let zip_file = File::create(zip_file_path)?;
         let mut zip = ZipWriter::new(zip_file);
 
-        zip.start_file(file_name, FileOptions::default())?;
-        zip.write_all(model.to_json().as_bytes())?;
+        zip.start_file(
+            zip_file_name.trim_end_matches(".zip"),
+            FileOptions::default(),
+        )?;
+        zip.write_all(json.as_bytes())?;
+        zip.finish()?;
 
         Ok(())
     }
@@ -183,7 +503,7 @@ impl TestDataFilesWriter {
     ///
     /// `maximum_lines`: The maximum number of lines each test data file should have.
     ///
-    /// ⚠ Panics if:
+    /// Returns a [LinguaError] if:
     /// - the input file path is not absolute or does not point to an existing txt file
     /// - the input file's encoding is not UTF-8
     /// - the output directory path is not absolute or does not point to an existing directory
@@ -193,9 +513,10 @@ impl TestDataFilesWriter {
         output_directory_path: &Path,
         char_class: &str,
         maximum_lines: u32,
-    ) -> io::Result<()> {
-        check_input_file_path(input_file_path);
-        check_output_directory_path(output_directory_path);
+    ) -> Result<(), LinguaError> {
+        check_input_file_path(input_file_path)?;
+        check_output_directory_path(output_directory_path)?;
+        check_character_class(char_class)?;
 
         Self::create_and_write_sentences_file(
             input_file_path,
@@ -227,7 +548,7 @@ impl TestDataFilesWriter {
         }
 
         let input_file = File::open(input_file_path)?;
-        let input_lines = BufReader::new(input_file).lines().map(|line| line.unwrap());
+        let input_lines = BufReader::new(input_file).lines();
 
         let sentences_file = File::create(sentences_file_path)?;
         let mut sentences_writer = LineWriter::new(sentences_file);
@@ -235,6 +556,7 @@ impl TestDataFilesWriter {
         let mut line_counter = 0;
 
         for line in input_lines {
+            let line = line?;
             let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&line, " ");
             let removed_quotes = normalized_whitespace.replace("\"", "");
 
@@ -255,9 +577,10 @@ impl TestDataFilesWriter {
         output_directory_path: &Path,
         char_class: &str,
         maximum_lines: u32,
-    ) -> io::Result<Vec<String>> {
+    ) -> Result<Vec<String>, LinguaError> {
         let single_words_file_path = output_directory_path.join("single-words.txt");
-        let word_regex = Regex::new(&format!("[{}]{{5,}}", char_class)).unwrap();
+        let word_regex = Regex::new(&format!("[{}]{{5,}}", char_class))
+            .map_err(|_| LinguaError::InvalidCharacterClass(char_class.to_string()))?;
         let mut words = vec![];
 
         if single_words_file_path.is_file() {
@@ -265,7 +588,7 @@ impl TestDataFilesWriter {
         }
 
         let input_file = File::open(input_file_path)?;
-        let input_lines = BufReader::new(input_file).lines().map(|line| line.unwrap());
+        let input_lines = BufReader::new(input_file).lines();
 
         let single_words_file = File::create(single_words_file_path)?;
         let mut single_words_writer = LineWriter::new(single_words_file);
@@ -273,6 +596,7 @@ impl TestDataFilesWriter {
         let mut line_counter = 0;
 
         for line in input_lines {
+            let line = line?;
             let removed_punctuation = PUNCTUATION.replace_all(&line, "");
             let removed_numbers = NUMBERS.replace_all(&removed_punctuation, "");
             let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&removed_numbers, " ");
@@ -311,8 +635,7 @@ impl TestDataFilesWriter {
             remove_file(&word_pairs_file_path)?;
         }
 
-        for i in (0..=(words.len() - 2)).step_by(2) {
-            let slice = &words[i..i + 2];
+        for slice in words.chunks_exact(2) {
             word_pairs.push(slice.join(" "));
         }
 
@@ -334,42 +657,75 @@ impl TestDataFilesWriter {
     }
 }
 
-fn check_input_file_path(input_file_path: &Path) {
+fn check_input_file_path(input_file_path: &Path) -> Result<(), LinguaError> {
     if !input_file_path.is_absolute() {
-        panic!(
-            "Input file path '{}' is not absolute",
-            input_file_path.display()
-        );
+        return Err(LinguaError::InputFilePathNotAbsolute(
+            input_file_path.to_path_buf(),
+        ));
     }
     if !input_file_path.exists() {
-        panic!("Input file '{}' does not exist", input_file_path.display());
+        return Err(LinguaError::InputFileNotFound(
+            input_file_path.to_path_buf(),
+        ));
     }
     if !input_file_path.is_file() {
-        panic!(
-            "Input file path '{}' does not represent a regular file",
-            input_file_path.display()
-        );
+        return Err(LinguaError::InputFilePathNotAFile(
+            input_file_path.to_path_buf(),
+        ));
     }
+    Ok(())
 }
 
-fn check_output_directory_path(output_directory_path: &Path) {
+fn check_output_directory_path(output_directory_path: &Path) -> Result<(), LinguaError> {
     if !output_directory_path.is_absolute() {
-        panic!(
-            "Output directory path '{}' is not absolute",
-            output_directory_path.display()
-        );
+        return Err(LinguaError::OutputDirectoryPathNotAbsolute(
+            output_directory_path.to_path_buf(),
+        ));
     }
     if !output_directory_path.exists() {
-        panic!(
-            "Output directory '{}' does not exist",
-            output_directory_path.display()
-        );
+        return Err(LinguaError::OutputDirectoryNotFound(
+            output_directory_path.to_path_buf(),
+        ));
     }
     if !output_directory_path.is_dir() {
-        panic!(
-            "Output directory path '{}' does not represent a directory",
-            output_directory_path.display()
-        );
+        return Err(LinguaError::OutputDirectoryPathNotADirectory(
+            output_directory_path.to_path_buf(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_spill_directory(options: &TrainingOptions) -> Result<(), LinguaError> {
+    let spill_directory = options.spill_directory();
+    if !spill_directory.exists() {
+        return Err(LinguaError::OutputDirectoryNotFound(
+            spill_directory.to_path_buf(),
+        ));
+    }
+    if !spill_directory.is_dir() {
+        return Err(LinguaError::OutputDirectoryPathNotADirectory(
+            spill_directory.to_path_buf(),
+        ));
+    }
+    Ok(())
+}
+
+fn corrupt_language_model_file_error(
+    file_path: &Path,
+    ngram_length: usize,
+    reason: String,
+) -> LinguaError {
+    LinguaError::CorruptLanguageModel {
+        language: None,
+        ngram_length,
+        reason: format!("{}: {}", file_path.display(), reason),
+    }
+}
+
+fn check_character_class(char_class: &str) -> Result<(), LinguaError> {
+    match Regex::new(&format!("^[{}]+$", char_class)) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(LinguaError::InvalidCharacterClass(char_class.to_string())),
     }
 }
 
@@ -402,6 +758,8 @@ mod tests {
     mod language_model_files {
         use super::*;
         use crate::minify;
+        use crate::{Alphabet, CustomLanguage, ModelStore};
+        use std::sync::Mutex;
         use zip::ZipArchive;
 
         const TEXT: &str = "
@@ -528,10 +886,378 @@ mod tests {
             );
         }
 
+        #[test]
+        fn assert_progress_of_reading_files_is_reported() {
+            static REPORTED_LINE_COUNTS: Mutex<Vec<u64>> = Mutex::new(vec![]);
+
+            let input_file = create_temp_input_file(TEXT);
+            let options = TrainingOptions::new().with_progress_callback(2, |_, line_count| {
+                REPORTED_LINE_COUNTS.lock().unwrap().push(line_count)
+            });
+
+            let models = LanguageModelFilesWriter::create_language_models_from_files_with_options(
+                &[input_file.path()],
+                &Language::English,
+                "\\p{L}",
+                &options,
+            )
+            .unwrap();
+
+            assert_eq!(*REPORTED_LINE_COUNTS.lock().unwrap(), vec![2, 4, 5]);
+            assert_eq!(models.ngram_count(1), 20);
+            assert_eq!(models.ngram_count(4), 38);
+        }
+
+        #[test]
+        fn test_language_model_files_conversion_to_binary() {
+            let input_file = create_temp_input_file(TEXT);
+            let json_directory = tempdir().expect("Temporary directory could not be created");
+            let binary_directory = tempdir().expect("Temporary directory could not be created");
+
+            LanguageModelFilesWriter::create_and_write_language_model_files(
+                input_file.path(),
+                json_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            let result = LanguageModelFilesWriter::convert_language_model_files_to_binary(
+                json_directory.path(),
+                binary_directory.path(),
+            );
+
+            assert!(result.is_ok());
+
+            let files = read_directory_content(binary_directory.path());
+            let file_names = files
+                .iter()
+                .map(|it| it.file_name().unwrap().to_str().unwrap())
+                .collect_vec();
+
+            assert_eq!(
+                file_names,
+                vec![
+                    "bigrams.bin",
+                    "fivegrams.bin",
+                    "quadrigrams.bin",
+                    "trigrams.bin",
+                    "unigrams.bin"
+                ]
+            );
+
+            for (file_path, ngram_length) in files.iter().zip(vec![2, 5, 4, 3, 1]) {
+                let bytes = std::fs::read(file_path).unwrap();
+                let model = BinaryLanguageModel::from_bytes(bytes).unwrap();
+                assert_eq!(model.ngram_length(), ngram_length);
+            }
+        }
+
+        #[test]
+        fn assert_conversion_of_corrupt_language_model_files_is_rejected() {
+            let json_directory = tempdir().expect("Temporary directory could not be created");
+            let binary_directory = tempdir().expect("Temporary directory could not be created");
+            std::fs::write(
+                json_directory.path().join("bigrams.json.zip"),
+                "no zip file",
+            )
+            .unwrap();
+
+            let result = LanguageModelFilesWriter::convert_language_model_files_to_binary(
+                json_directory.path(),
+                binary_directory.path(),
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::CorruptLanguageModel {
+                    language: None,
+                    ngram_length: 2,
+                    ref reason,
+                }) if reason.contains("bigrams.json.zip")
+            ));
+        }
+
+        #[test]
+        fn test_additional_text_is_merged_into_language_model_files() {
+            let input_file = create_temp_input_file(TEXT);
+            let model_directory = tempdir().expect("Temporary directory could not be created");
+            let models = LanguageModelFilesWriter::create_language_models(
+                TEXT.lines(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+                &models,
+                model_directory.path(),
+            )
+            .unwrap();
+
+            let result = LanguageModelFilesWriter::merge_into_language_model_files(
+                &[input_file.path()],
+                model_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().ngram_count(1), models.ngram_count(1));
+            assert_eq!(read_dir(model_directory.path()).unwrap().count(), 5);
+
+            let expected_models = LanguageModelFilesWriter::create_language_models(
+                TEXT.lines().chain(TEXT.lines()),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            for ngram_length in 1..6 {
+                let file_path = model_directory
+                    .path()
+                    .join(get_language_model_file_name(ngram_length));
+                let json = read_zipped_json(File::open(file_path).unwrap()).unwrap();
+                let (language, absolute_frequencies) =
+                    TrainingDataLanguageModel::read_absolute_frequencies(&json).unwrap();
+
+                assert_eq!(language, Language::English);
+                assert_eq!(
+                    json,
+                    expected_models.to_json_with_absolute_frequencies(ngram_length)
+                );
+
+                let absolute_frequencies = absolute_frequencies.unwrap();
+                let (_, initial_frequencies) =
+                    TrainingDataLanguageModel::read_absolute_frequencies(
+                        &models.to_json_with_absolute_frequencies(ngram_length),
+                    )
+                    .unwrap();
+
+                for (ngram, frequency) in initial_frequencies.unwrap() {
+                    assert_eq!(absolute_frequencies[&ngram], 2 * frequency);
+                }
+            }
+        }
+
+        #[test]
+        fn assert_merged_models_of_custom_language_are_inserted_into_store() {
+            let input_file = create_temp_input_file(TEXT);
+            let model_directory = tempdir().expect("Temporary directory could not be created");
+            let language = Language::Custom(CustomLanguage::new(
+                "Scots",
+                &[Alphabet::Latin],
+                None,
+                model_directory.path(),
+            ));
+            let models =
+                LanguageModelFilesWriter::create_language_models(TEXT.lines(), &language, "\\p{L}")
+                    .unwrap();
+
+            LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+                &models,
+                model_directory.path(),
+            )
+            .unwrap();
+
+            let merged_models = LanguageModelFilesWriter::merge_into_language_model_files(
+                &[input_file.path()],
+                model_directory.path(),
+                &language,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            assert_eq!(merged_models.language(), &language);
+
+            let store = ModelStore::new();
+            store.insert_language_models(&merged_models);
+
+            assert_eq!(store.memory_usage().keys().collect_vec(), vec![&language]);
+        }
+
+        #[test]
+        fn assert_merging_into_files_of_other_language_is_rejected() {
+            let input_file = create_temp_input_file(TEXT);
+            let model_directory = tempdir().expect("Temporary directory could not be created");
+            let models = LanguageModelFilesWriter::create_language_models(
+                TEXT.lines(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+                &models,
+                model_directory.path(),
+            )
+            .unwrap();
+
+            let result = LanguageModelFilesWriter::merge_into_language_model_files(
+                &[input_file.path()],
+                model_directory.path(),
+                &Language::German,
+                "\\p{L}",
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::CorruptLanguageModel {
+                    language: None,
+                    ngram_length: 1,
+                    ..
+                })
+            ));
+        }
+
+        #[test]
+        fn assert_language_model_files_are_untouched_if_merging_fails() {
+            let input_file = create_temp_input_file(TEXT);
+            let model_directory = tempdir().expect("Temporary directory could not be created");
+            let models = LanguageModelFilesWriter::create_language_models(
+                TEXT.lines(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            LanguageModelFilesWriter::write_language_model_files_with_absolute_frequencies(
+                &models,
+                model_directory.path(),
+            )
+            .unwrap();
+
+            let blocking_path = model_directory.path().join("fivegrams.json.zip.tmp");
+            std::fs::create_dir(&blocking_path).unwrap();
+
+            let result = LanguageModelFilesWriter::merge_into_language_model_files(
+                &[input_file.path()],
+                model_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            );
+
+            assert!(matches!(result, Err(LinguaError::Io(_))));
+
+            for ngram_length in 1..6 {
+                let file_path = model_directory
+                    .path()
+                    .join(get_language_model_file_name(ngram_length));
+                let json = read_zipped_json(File::open(file_path).unwrap()).unwrap();
+                assert_eq!(json, models.to_json_with_absolute_frequencies(ngram_length));
+            }
+
+            std::fs::remove_dir(&blocking_path).unwrap();
+            assert_eq!(read_dir(model_directory.path()).unwrap().count(), 5);
+        }
+
+        #[test]
+        fn assert_merging_into_files_without_absolute_frequencies_is_rejected() {
+            let input_file = create_temp_input_file(TEXT);
+            let model_directory = tempdir().expect("Temporary directory could not be created");
+
+            LanguageModelFilesWriter::create_and_write_language_model_files(
+                input_file.path(),
+                model_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            let result = LanguageModelFilesWriter::merge_into_language_model_files(
+                &[input_file.path()],
+                model_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::CorruptLanguageModel {
+                    language: None,
+                    ngram_length: 1,
+                    ..
+                })
+            ));
+        }
+
+        #[test]
+        fn assert_invalid_spill_directory_is_rejected() {
+            let spill_directory = tempdir().expect("Temporary directory could not be created");
+            let missing_directory = spill_directory.path().join("missing");
+            let spill_file = create_temp_input_file(TEXT);
+            let create_language_models = |directory: &Path| {
+                LanguageModelFilesWriter::create_language_models_with_options(
+                    TEXT.lines(),
+                    &Language::English,
+                    "\\p{L}",
+                    &TrainingOptions::new().with_spill_directory(directory),
+                )
+            };
+
+            assert!(matches!(
+                create_language_models(&missing_directory),
+                Err(LinguaError::OutputDirectoryNotFound(path)) if path == missing_directory
+            ));
+            assert!(matches!(
+                create_language_models(spill_file.path()),
+                Err(LinguaError::OutputDirectoryPathNotADirectory(path)) if path == spill_file.path()
+            ));
+        }
+
+        #[test]
+        fn assert_relative_input_file_path_is_rejected() {
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = LanguageModelFilesWriter::create_and_write_language_model_files(
+                Path::new("some/relative/path/file.txt"),
+                output_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::InputFilePathNotAbsolute(_))
+            ));
+        }
+
+        #[test]
+        fn assert_invalid_character_class_is_rejected() {
+            let input_file = create_temp_input_file(TEXT);
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = LanguageModelFilesWriter::create_and_write_language_model_files(
+                input_file.path(),
+                output_directory.path(),
+                &Language::English,
+                "\\p{Foo}",
+            );
+
+            assert!(matches!(result, Err(LinguaError::InvalidCharacterClass(_))));
+            assert!(read_directory_content(output_directory.path()).is_empty());
+        }
+
         fn assert_file_names(file_path: &Path, expected_file_name: &str) {
             assert_eq!(file_path.file_name().unwrap(), expected_file_name);
         }
 
+        #[test]
+        fn assert_missing_output_directory_is_rejected() {
+            let input_file = create_temp_input_file(TEXT);
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let missing_directory = output_directory.path().join("missing");
+            let result = TestDataFilesWriter::create_and_write_test_data_files(
+                input_file.path(),
+                &missing_directory,
+                "\\p{L}",
+                4,
+            );
+
+            assert!(matches!(
+                result,
+                Err(LinguaError::OutputDirectoryNotFound(path)) if path == missing_directory
+            ));
+        }
+
         fn assert_file_content(
             file_path: &Path,
             expected_file_name: &str,